@@ -0,0 +1,50 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+
+fn detection(c: &mut Criterion) {
+    c.bench_function("get", |b| b.iter(osinfo::get));
+    c.bench_function("global", |b| b.iter(osinfo::global));
+
+    // Each iteration reads the release files again, so on network file systems and cold caches
+    // the parallel reads hide the latency of all but the slowest file.
+    let mut group = c.benchmark_group("release files");
+    for parallel in [false, true] {
+        let config = osinfo::Config {
+            parallel,
+            ..Default::default()
+        };
+        let name = if parallel { "parallel" } else { "sequential" };
+        group.bench_function(name, |b| b.iter(|| osinfo::get_with(black_box(&config))));
+    }
+    group.finish();
+}
+
+fn getters(c: &mut Criterion) {
+    let info = osinfo::get();
+
+    c.bench_function("borrowing getters", |b| {
+        b.iter(|| {
+            let info = black_box(&info);
+            (info.id(), info.name(), info.version(), info.variant(), info.edition(), info.codename())
+        })
+    });
+
+    // What the deprecated `get_*` getters cost: an owned copy of every field.
+    c.bench_function("cloning getters", |b| {
+        b.iter(|| {
+            let info = black_box(&info);
+            (
+                info.id().to_owned(),
+                info.name().to_owned(),
+                info.version().clone(),
+                info.variant().unwrap_or_default().to_owned(),
+                info.edition().unwrap_or_default().to_owned(),
+                info.codename().unwrap_or_default().to_owned(),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, detection, getters);
+criterion_main!(benches);
@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use osinfo::Matcher;
+
+fuzz_target!(|input: (&str, char, char)| {
+    let (string, start, end) = input;
+    let matchers = [
+        Matcher::AllTrimmed,
+        Matcher::PrefixedWord { prefix: "VERSION" },
+        Matcher::PrefixedVersion { prefix: "release" },
+        Matcher::KeyValue { key: "ID" },
+        Matcher::Between { start, end },
+    ];
+
+    for matcher in &matchers {
+        let _ = matcher.find(string);
+    }
+    let combined = Matcher::KeyValue { key: "VERSION" }.then(Matcher::Between { start, end }).or(Matcher::AllTrimmed);
+    let _ = combined.find(string);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use osinfo::Version;
+
+fuzz_target!(|input: &str| {
+    let _ = Version::from_string(input);
+});
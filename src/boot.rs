@@ -0,0 +1,125 @@
+//! Boot-time security state of the operating system: Secure Boot, kernel lockdown and kernel
+//! taint flags.
+
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Boot-time security state. Each field is `None` if it couldn't be determined or doesn't apply
+/// to the operating system.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct BootStatus {
+    /// Whether the system booted with UEFI Secure Boot enabled. `Some(false)` on legacy BIOS
+    /// systems.
+    pub secure_boot: Option<bool>,
+    /// Kernel lockdown mode (Linux).
+    pub lockdown: Option<Lockdown>,
+    /// Kernel taint flags (Linux).
+    pub taint: Option<KernelTaint>,
+}
+
+/// Linux kernel lockdown mode, restricting what even root can do to the running kernel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Lockdown {
+    /// No restrictions ("none").
+    Disabled,
+    /// Features allowing userland to modify the running kernel are disabled.
+    Integrity,
+    /// Features allowing userland to extract confidential information from the kernel are
+    /// disabled too.
+    Confidentiality,
+}
+
+/// Linux kernel taint flags, i.e. the value of `/proc/sys/kernel/tainted`. Each bit records an
+/// event that may make the kernel unreliable for debugging, such as loading a proprietary module.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KernelTaint(pub u64);
+
+/// Letters the kernel uses for the taint flags, indexed by bit.
+const TAINT_LETTERS: [char; 19] = [
+    'P', 'F', 'S', 'R', 'M', 'B', 'U', 'D', 'A', 'W', 'C', 'I', 'O', 'E', 'L', 'K', 'X', 'T', 'N',
+];
+
+impl KernelTaint {
+    /// Returns `true` if any taint flag is set.
+    pub fn is_tainted(self) -> bool {
+        self.0 != 0
+    }
+
+    /// Returns `true` if the flag with the given bit is set, e.g. 0 for a proprietary module.
+    pub fn has(self, bit: u32) -> bool {
+        bit < u64::BITS && self.0 & (1 << bit) != 0
+    }
+
+    /// Returns `true` if a proprietary module (flag `P`) was loaded.
+    pub fn proprietary_module(self) -> bool {
+        self.has(0)
+    }
+
+    /// Returns `true` if an unsigned module (flag `E`) was loaded.
+    pub fn unsigned_module(self) -> bool {
+        self.has(13)
+    }
+}
+
+/// Formats the set flags the way the kernel does in oops messages, e.g. "POE". Unknown flags
+/// are shown as `?`.
+///
+/// # Example
+/// ```
+/// use osinfo::boot::KernelTaint;
+/// assert_eq!("POE", KernelTaint(0x3001).to_string());
+/// ```
+impl Display for KernelTaint {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for bit in (0..u64::BITS).filter(|&bit| self.has(bit)) {
+            let letter = TAINT_LETTERS.get(bit as usize).copied().unwrap_or('?');
+            write!(f, "{letter}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the boot-time security state of the system.
+///
+/// Secure Boot is read from the EFI variables on Linux and from the firmware (or registry) on
+/// Windows. Lockdown mode and taint flags only exist on Linux. All checks are read-only and don't
+/// require elevated privileges.
+///
+/// # Examples
+///
+/// ```
+/// let status = osinfo::boot::status();
+/// println!("Secure Boot: {:?}", status.secure_boot);
+/// if let Some(taint) = status.taint.filter(|taint| taint.is_tainted()) {
+///     println!("Kernel tainted: {taint}");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn status() -> BootStatus {
+    crate::osimp::get_boot_status()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn taint() {
+        let taint = KernelTaint(0x3001);
+        assert!(taint.is_tainted());
+        assert!(taint.proprietary_module());
+        assert!(taint.unsigned_module());
+        assert!(!taint.has(64));
+        assert_eq!("POE", taint.to_string());
+
+        assert!(!KernelTaint(0).is_tainted());
+        assert_eq!("", KernelTaint(0).to_string());
+        assert_eq!("N?", KernelTaint(0xC_0000).to_string());
+    }
+}
@@ -0,0 +1,153 @@
+//! This module defines the `OSInfoBuilder` struct, the way to construct `OSInfo` values outside
+//! of detection, e.g. in tests or when deserializing a custom format.
+
+use alloc::{boxed::Box, string::String};
+
+use super::{OSInfo, Version};
+
+/// Builder of `OSInfo` values, see `OSInfo::builder`. Fields that aren't set keep the values of
+/// `OSInfo::new_unknown()`.
+///
+/// # Example
+/// ```
+/// use osinfo::{OSInfo, Version};
+/// let info = OSInfo::builder()
+///     .id("ubuntu")
+///     .name("Ubuntu")
+///     .version(Version::Semantic(22, 4, 4, 0))
+///     .codename("jammy")
+///     .lts(true)
+///     .build();
+/// assert_eq!(info.id(), "ubuntu");
+/// assert_eq!(info.codename(), Some("jammy"));
+/// assert!(info.is_lts());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[must_use]
+pub struct OSInfoBuilder {
+    info: OSInfo,
+}
+
+impl OSInfoBuilder {
+    /// Creates a builder of an unknown operating system.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the operating system ID, e.g. "ubuntu" or "windows".
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.info.id = Some(id.into());
+        self
+    }
+
+    /// Sets the operating system name, e.g. "Ubuntu".
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.info.name = Some(name.into());
+        self
+    }
+
+    /// Sets the name displayed to the user, if it differs from the locale-neutral name.
+    pub fn localized_name(mut self, localized_name: impl Into<String>) -> Self {
+        self.info.localized_name = Some(localized_name.into());
+        self
+    }
+
+    /// Sets the version.
+    pub fn version(mut self, version: Version) -> Self {
+        self.info.version = version;
+        self
+    }
+
+    /// Sets the supplemental identifier of the version, e.g. "(a)".
+    pub fn version_extra(mut self, version_extra: impl Into<String>) -> Self {
+        self.info.version_extra = Some(version_extra.into());
+        self
+    }
+
+    /// Sets the variant, e.g. "Server".
+    pub fn variant(mut self, variant: impl Into<String>) -> Self {
+        self.info.variant = Some(variant.into());
+        self
+    }
+
+    /// Sets the edition, e.g. "Professional".
+    pub fn edition(mut self, edition: impl Into<String>) -> Self {
+        self.info.edition = Some(edition.into());
+        self
+    }
+
+    /// Sets the codename, e.g. "jammy" or "23H2".
+    pub fn codename(mut self, codename: impl Into<String>) -> Self {
+        self.info.codename = Some(codename.into());
+        self
+    }
+
+    /// Sets whether the release is a long-term support one.
+    pub fn lts(mut self, lts: bool) -> Self {
+        self.info.lts = lts;
+        self
+    }
+
+    /// Sets the Windows product type (SKU) number, e.g. `0x30` for `PRODUCT_PROFESSIONAL`.
+    pub fn product_sku(mut self, product_sku: u32) -> Self {
+        self.info.product_sku = Some(product_sku);
+        self
+    }
+
+    /// Sets the environment the operating system runs in, see `OSInfo::host`.
+    pub fn host(mut self, host: OSInfo) -> Self {
+        self.info.host = Some(Box::new(host));
+        self
+    }
+
+    /// Returns the `OSInfo` value.
+    pub fn build(self) -> OSInfo {
+        self.info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn defaults() {
+        assert_eq!(OSInfo::new_unknown(), OSInfoBuilder::new().build());
+    }
+
+    #[test]
+    fn all_fields() {
+        let host = OSInfo::builder().id("windows").build();
+        let info = OSInfo::builder()
+            .id("ubuntu")
+            .name("Ubuntu")
+            .localized_name("Ubuntu")
+            .version(Version::Semantic(22, 4, 4, 0))
+            .version_extra("(a)")
+            .variant("Server")
+            .edition("Minimal")
+            .codename("jammy")
+            .lts(true)
+            .product_sku(0x30)
+            .host(host.clone())
+            .build();
+
+        let expected = OSInfo {
+            id: Some("ubuntu".to_string()),
+            name: Some("Ubuntu".to_string()),
+            version: Version::Semantic(22, 4, 4, 0),
+            version_extra: Some("(a)".to_string()),
+            variant: Some("Server".to_string()),
+            edition: Some("Minimal".to_string()),
+            codename: Some("jammy".to_string()),
+            lts: true,
+            host: Some(Box::new(host)),
+            product_sku: Some(0x30),
+            localized_name: Some("Ubuntu".to_string()),
+            provenance: alloc::vec::Vec::new(),
+        };
+        assert_eq!(expected, info);
+    }
+}
@@ -0,0 +1,389 @@
+//! This module defines the `Bundle` struct, a self-contained JSON file with a detection result and
+//! the raw data it was detected from, see `OSInfo::export_bundle`.
+
+use std::{
+    collections::hash_map::RandomState,
+    fs,
+    hash::{BuildHasher, Hasher},
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Config, OSInfo, linux,
+    windows::{self, MemoryRegistry, RegistryKey, RegistryValue},
+};
+
+/// Version of the bundle format.
+const FORMAT: u32 = 1;
+
+/// Detection result together with the release files and registry values it was detected from,
+/// so that it can be sent from an air-gapped machine and the detection reproduced elsewhere.
+///
+/// The bundle carries a SHA-256 checksum of its contents, verified by `OSInfo::import_bundle`,
+/// so that truncated or edited files are rejected rather than reproduced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bundle {
+    /// Version of the bundle format.
+    pub format: u32,
+    /// Version of the crate that wrote the bundle.
+    pub crate_version: String,
+    /// Detection result on the machine that wrote the bundle.
+    pub info: OSInfo,
+    /// Raw data of the machine that wrote the bundle.
+    pub sources: Vec<BundleSource>,
+    /// Lowercase hexadecimal SHA-256 checksum of the other fields.
+    pub checksum: String,
+}
+
+/// Raw data included in a `Bundle`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BundleSource {
+    /// Text file, e.g. "/etc/os-release".
+    File {
+        /// Absolute path of the file.
+        path: String,
+        /// Content of the file, with invalid UTF-8 replaced as in the detection.
+        content: String,
+    },
+    /// Registry key under `HKEY_LOCAL_MACHINE`, e.g.
+    /// "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion".
+    RegistryKey {
+        /// Path of the key relative to `HKEY_LOCAL_MACHINE`.
+        path: String,
+        /// Names and values of the key.
+        values: Vec<(String, RegistryValue)>,
+    },
+}
+
+/// Files read by the Linux detection besides the release files, relative to the root.
+const EXTRA_FILES: [&str; 2] = ["proc/sys/kernel/osrelease", "run/host/os-release"];
+
+impl Bundle {
+    /// Creates a bundle of `info` with the data it was detected from on this machine: the release
+    /// files and the sources recorded in the provenance of `info` and its hosts, within the
+    /// process-wide size limit (see `set_max_file_size`).
+    pub fn new(info: OSInfo) -> Self {
+        Self::with_config(info, &Config::default())
+    }
+
+    /// Creates a bundle like `new`, capturing the files within the size limit of `config`, e.g.
+    /// the configuration `info` was detected with.
+    pub fn with_config(info: OSInfo, config: &Config) -> Self {
+        let max_size = linux::max_file_size(config);
+        let mut sources = Vec::new();
+        let mut add_file = |path: &Path| {
+            let path = path.display().to_string();
+            if sources.iter().any(|source| matches!(source, BundleSource::File { path: p, .. } if *p == path)) {
+                return;
+            }
+            // Only files within the size limit of the detection are sources. They are decoded
+            // lossily, as the detection does, so that replaying them gives the same result.
+            if let Ok(metadata) = fs::metadata(&path)
+                && metadata.is_file()
+                && metadata.len() <= max_size
+                && let Ok(content) = fs::read(&path)
+            {
+                let content = String::from_utf8_lossy(&content).into_owned();
+                sources.push(BundleSource::File { path, content });
+            }
+        };
+
+        let root = Path::new("/");
+        for path in linux::release_files().chain(EXTRA_FILES) {
+            add_file(&root.join(path));
+        }
+        for layer in core::iter::once(&info).chain(info.hosts()) {
+            for (_, source) in layer.provenance() {
+                if Path::new(source).is_absolute() {
+                    add_file(Path::new(source));
+                }
+            }
+        }
+
+        #[cfg(windows)]
+        for layer in core::iter::once(&info).chain(info.hosts()) {
+            for (_, source) in layer.provenance() {
+                let Some(path) = source.strip_prefix("HKLM\\") else {
+                    continue;
+                };
+                let known = sources.iter().any(|source| matches!(source, BundleSource::RegistryKey { path: p, .. } if p == path));
+                if !known && let Ok(key) = windows::read_live_key(path) {
+                    let values = key.values().map(|(name, value)| (name.to_string(), value.clone())).collect();
+                    sources.push(BundleSource::RegistryKey { path: path.to_string(), values });
+                }
+            }
+        }
+
+        let mut bundle = Self {
+            format: FORMAT,
+            crate_version: String::from(env!("CARGO_PKG_VERSION")),
+            info,
+            sources,
+            checksum: String::new(),
+        };
+        bundle.checksum = bundle.compute_checksum();
+        bundle
+    }
+
+    /// Parses a bundle and verifies its checksum.
+    pub fn from_json_str(json: &str) -> io::Result<Self> {
+        let bundle: Self = serde_json::from_str(json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if bundle.format != FORMAT {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported bundle format {}", bundle.format),
+            ));
+        }
+        if bundle.checksum != bundle.compute_checksum() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "bundle checksum mismatch"));
+        }
+        Ok(bundle)
+    }
+
+    /// Serializes the bundle to a pretty-printed JSON string.
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Bundle is always serializable to JSON")
+    }
+
+    /// Runs the detection again on the data of the bundle, with the default configuration:
+    /// the Linux detection on its files, or the Windows detection on its registry keys. Returns
+    /// `None` if the bundle has no data the detection recognizes.
+    ///
+    /// Bundles written on macOS can't be replayed and always give `None`: the macOS detection is
+    /// only built on macOS, and reads `sysctl` values besides `SystemVersion.plist`.
+    ///
+    /// The provenance of the result names the temporary directory the files were written to.
+    pub fn replay(&self) -> io::Result<Option<OSInfo>> {
+        if self.info.is_macos() {
+            return Ok(None);
+        }
+
+        let files: Vec<(&str, &str)> = self
+            .sources
+            .iter()
+            .filter_map(|source| match source {
+                BundleSource::File { path, content } => Some((path.as_str(), content.as_str())),
+                BundleSource::RegistryKey { .. } => None,
+            })
+            .collect();
+        if !files.is_empty() {
+            let root = create_private_dir()?;
+            // The files were already limited when the bundle was written, possibly to a larger
+            // size than the limit of this process.
+            let config = Config {
+                max_file_size: Some(u64::MAX),
+                ..Config::default()
+            };
+            let result = write_files(&root, &files).map(|()| linux::get_info_from(&root, &config, &mut Vec::new()));
+            let _ = fs::remove_dir_all(&root);
+            return result;
+        }
+
+        let registry = self
+            .sources
+            .iter()
+            .fold(MemoryRegistry::new(), |registry, source| match source {
+                BundleSource::RegistryKey { path, values } => {
+                    registry.with_key(path.as_str(), RegistryKey::new(values.iter().cloned()))
+                }
+                BundleSource::File { .. } => registry,
+            });
+        let has_keys = self.sources.iter().any(|source| matches!(source, BundleSource::RegistryKey { .. }));
+        Ok(has_keys.then(|| windows::get_from_registry(&registry)))
+    }
+
+    fn compute_checksum(&self) -> String {
+        let contents = serde_json::to_string(&(self.format, &self.crate_version, &self.info, &self.sources))
+            .expect("Bundle is always serializable to JSON");
+        crate::sha256::hex_digest(contents.as_bytes())
+    }
+}
+
+/// Creates a new directory with a random name in the temporary directory, only accessible to the
+/// current user on Unix. An existing path is never reused, as another user could have created it
+/// (or a symlink) to redirect the files of the bundle.
+fn create_private_dir() -> io::Result<PathBuf> {
+    static REPLAYS: AtomicUsize = AtomicUsize::new(0);
+
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+
+    for _ in 0..16 {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_usize(REPLAYS.fetch_add(1, Ordering::Relaxed));
+        hasher.write_u32(std::process::id());
+        let root = std::env::temp_dir().join(format!("osinfo-bundle-{:016x}", hasher.finish()));
+        match builder.create(&root) {
+            Ok(()) => return Ok(root),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::AlreadyExists,
+        "unable to create a temporary directory for the bundle",
+    ))
+}
+
+/// Writes the files of a bundle under `root`.
+fn write_files(root: &Path, files: &[(&str, &str)]) -> io::Result<()> {
+    for (path, content) in files {
+        // Paths are kept under `root`, whatever the bundle contains.
+        let relative: std::path::PathBuf = Path::new(path)
+            .components()
+            .filter(|component| matches!(component, std::path::Component::Normal(_)))
+            .collect();
+        let path = root.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Version;
+    use pretty_assertions::assert_eq;
+
+    fn with_sources(sources: Vec<BundleSource>) -> Bundle {
+        let mut bundle = Bundle {
+            format: FORMAT,
+            crate_version: String::from(env!("CARGO_PKG_VERSION")),
+            info: OSInfo::with_id(String::from("ubuntu")),
+            sources,
+            checksum: String::new(),
+        };
+        bundle.checksum = bundle.compute_checksum();
+        bundle
+    }
+
+    #[test]
+    fn round_trip() {
+        let bundle = with_sources(vec![BundleSource::File {
+            path: String::from("/etc/os-release"),
+            content: String::from("NAME=\"Ubuntu\"\nID=ubuntu\nVERSION_ID=\"22.04\"\nVERSION_CODENAME=jammy\n"),
+        }]);
+        let json = bundle.to_json_string();
+        assert_eq!(bundle, Bundle::from_json_str(&json).unwrap());
+
+        let tampered = json.replace("jammy", "noble");
+        assert_eq!(io::ErrorKind::InvalidData, Bundle::from_json_str(&tampered).unwrap_err().kind());
+        assert_eq!(io::ErrorKind::InvalidData, Bundle::from_json_str("{}").unwrap_err().kind());
+    }
+
+    #[test]
+    fn replay_files() {
+        let bundle = with_sources(vec![
+            BundleSource::File {
+                path: String::from("/etc/os-release"),
+                content: String::from("NAME=\"Ubuntu\"\nID=ubuntu\nVERSION_ID=\"22.04\"\nVERSION_CODENAME=jammy\n"),
+            },
+            BundleSource::File {
+                path: String::from("/../../etc/lsb-release"),
+                content: String::from("DISTRIB_ID=Ubuntu\nDISTRIB_RELEASE=22.04\n"),
+            },
+        ]);
+
+        let info = bundle.replay().unwrap().unwrap();
+        assert_eq!("ubuntu", info.id());
+        assert_eq!(&Version::Semantic(22, 4, 0, 0), info.version());
+        assert_eq!(Some("jammy"), info.codename());
+    }
+
+    #[test]
+    fn private_dir() {
+        let first = create_private_dir().unwrap();
+        let second = create_private_dir().unwrap();
+        assert_ne!(first, second);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(0o700, fs::metadata(&first).unwrap().permissions().mode() & 0o777);
+        }
+        fs::remove_dir(first).unwrap();
+        fs::remove_dir(second).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn non_utf8_file() {
+        let root = linux::tests::root_with("bundle-non-utf8", &[]);
+        fs::create_dir_all(&root).unwrap();
+        let path = root.join("os-release");
+        fs::write(&path, b"ID=debian\nPRETTY_NAME=\"Debian \xff\"\n").unwrap();
+        let mut info = OSInfo::with_id(String::from("debian"));
+        info.record_source(&[crate::FieldName::Id], &path.display().to_string());
+
+        let bundle = Bundle::new(info);
+        let content = bundle.sources.iter().find_map(|source| match source {
+            BundleSource::File { path: p, content } if *p == path.display().to_string() => Some(content.as_str()),
+            _ => None,
+        });
+        assert_eq!(Some("ID=debian\nPRETTY_NAME=\"Debian \u{fffd}\"\n"), content);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn replay_registry() {
+        let bundle = with_sources(vec![BundleSource::RegistryKey {
+            path: String::from("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion"),
+            values: vec![
+                (String::from("CurrentBuildNumber"), RegistryValue::String(String::from("22631"))),
+                (String::from("DisplayVersion"), RegistryValue::String(String::from("23H2"))),
+            ],
+        }]);
+
+        let info = bundle.replay().unwrap().unwrap();
+        assert_eq!("windows", info.id());
+        assert_eq!(Some("23H2"), info.codename());
+        assert_eq!(None, with_sources(Vec::new()).replay().unwrap());
+    }
+
+    #[test]
+    fn replay_macos() {
+        let mut bundle = with_sources(vec![BundleSource::File {
+            path: String::from("/System/Library/CoreServices/SystemVersion.plist"),
+            content: String::from("<plist><dict><key>ProductVersion</key><string>14.4</string></dict></plist>"),
+        }]);
+        bundle.info = OSInfo::with_id(String::from("macos"));
+        assert_eq!(None, bundle.replay().unwrap());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn file_size_limit() {
+        let root = linux::tests::root_with("bundle-size-limit", &[("os-release", "ID=debian\n")]);
+        let path = root.join("os-release");
+        let mut info = OSInfo::with_id(String::from("debian"));
+        info.record_source(&[crate::FieldName::Id], &path.display().to_string());
+        let captured = |config: &Config| {
+            Bundle::with_config(info.clone(), config)
+                .sources
+                .iter()
+                .any(|source| matches!(source, BundleSource::File { path: p, .. } if *p == path.display().to_string()))
+        };
+
+        assert!(captured(&Config::default()));
+        assert!(!captured(&Config {
+            max_file_size: Some(4),
+            ..Config::default()
+        }));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn current() {
+        let bundle = Bundle::new(crate::get());
+        assert_eq!(bundle, Bundle::from_json_str(&bundle.to_json_string()).unwrap());
+    }
+}
@@ -0,0 +1,75 @@
+//! This module defines the `Cgroups` struct describing the control group hierarchy of a Linux
+//! process and the resource limits it imposes.
+
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Control groups of the process and the limits they impose, e.g. the memory limit of a
+/// container, which the operating system version alone doesn't reveal.
+///
+/// Limits are inherited: the effective limit is the lowest one of the cgroup of the process and
+/// its ancestors, as far as they are visible from the cgroup namespace of the process.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cgroups {
+    /// Hierarchy in use.
+    pub version: CgroupVersion,
+    /// Path of the cgroup of the process relative to the root of its cgroup namespace, e.g.
+    /// "/system.slice/nginx.service": in the unified hierarchy, or the one of the memory
+    /// controller with cgroup v1.
+    pub path: String,
+    /// Memory limit in bytes, or `None` if the memory isn't limited.
+    pub memory_limit: Option<u64>,
+    /// CPU bandwidth limit, or `None` if the CPU time isn't limited.
+    pub cpu_quota: Option<CpuQuota>,
+}
+
+/// Control group hierarchy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CgroupVersion {
+    /// cgroup v1, a hierarchy per controller.
+    V1,
+    /// cgroup v2, the unified hierarchy.
+    V2,
+    /// cgroup v1 controllers, with the unified hierarchy only used to track processes (systemd's
+    /// "hybrid" mode).
+    Hybrid,
+}
+
+/// CPU bandwidth limit: at most `quota_us` microseconds of CPU time per `period_us`
+/// microseconds, across all CPUs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CpuQuota {
+    /// CPU time allowed per period, in microseconds.
+    pub quota_us: u64,
+    /// Length of the period, in microseconds.
+    pub period_us: u64,
+}
+
+impl CpuQuota {
+    /// Returns the number of CPUs the quota amounts to, e.g. 0.5 for 50ms per 100ms.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::CpuQuota;
+    /// let quota = CpuQuota { quota_us: 150_000, period_us: 100_000 };
+    /// assert_eq!(quota.cpus(), 1.5);
+    /// ```
+    pub fn cpus(&self) -> f64 {
+        self.quota_us as f64 / self.period_us as f64
+    }
+}
+
+impl Display for CgroupVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::V1 => "cgroup v1",
+            Self::V2 => "cgroup v2",
+            Self::Hybrid => "hybrid",
+        })
+    }
+}
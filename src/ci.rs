@@ -0,0 +1,264 @@
+//! This module defines the `CiEnvironment` struct describing the continuous integration service
+//! and runner image a process runs on.
+
+use std::{
+    fmt::{self, Display, Formatter},
+    fs,
+    path::Path,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Description of the image of GitHub-hosted Linux runners, relative to the root.
+const IMAGE_DATA_PATH: &str = "imagegeneration/imagedata.json";
+
+/// Continuous integration environment and runner image.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CiEnvironment {
+    /// Continuous integration service.
+    pub provider: CiProvider,
+    /// Label of the runner image, e.g. "ubuntu-22.04" or "windows-2022" on GitHub Actions and
+    /// Azure Pipelines, or the Docker image of the job on GitLab, e.g. "rust:1.77".
+    pub image: Option<String>,
+    /// Version of the runner image, e.g. "20240310.1.0".
+    pub image_version: Option<String>,
+    /// Version of the runner (GitHub Actions, GitLab) or agent (Azure Pipelines), e.g.
+    /// "2.317.0".
+    pub runner_version: Option<String>,
+    /// Whether the runner is managed by the user rather than hosted by the service, if known.
+    pub self_hosted: Option<bool>,
+}
+
+/// Continuous integration service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum CiProvider {
+    /// GitHub Actions.
+    GitHubActions,
+    /// GitLab CI/CD.
+    GitLab,
+    /// Azure Pipelines.
+    AzurePipelines,
+}
+
+#[cfg_attr(not(feature = "detect"), allow(dead_code))]
+impl CiEnvironment {
+    /// Detects the continuous integration environment given a lookup of environment variables.
+    /// The files of GitHub-hosted runners are read under `root`, and the runner versions
+    /// installed under `$HOME/runners`.
+    pub(crate) fn detect(var: impl Fn(&str) -> Option<String>, root: &Path) -> Option<Self> {
+        let var = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+
+        if var("GITHUB_ACTIONS").as_deref() == Some("true") {
+            let image_data = fs::read_to_string(root.join(IMAGE_DATA_PATH)).unwrap_or_default();
+            return Some(Self {
+                provider: CiProvider::GitHubActions,
+                image: var("ImageOS")
+                    .map(|image_os| image_label(&image_os))
+                    .or_else(|| image_data_value(&image_data, "Image")),
+                image_version: var("ImageVersion").or_else(|| image_data_value(&image_data, "Version")),
+                runner_version: var("HOME").and_then(|home| installed_runner(&Path::new(&home).join("runners"))),
+                self_hosted: var("RUNNER_ENVIRONMENT").map(|environment| environment == "self-hosted"),
+            });
+        }
+
+        if var("GITLAB_CI").is_some() {
+            return Some(Self {
+                provider: CiProvider::GitLab,
+                image: var("CI_JOB_IMAGE"),
+                image_version: None,
+                runner_version: var("CI_RUNNER_VERSION"),
+                self_hosted: None,
+            });
+        }
+
+        if var("TF_BUILD").is_some_and(|value| value.eq_ignore_ascii_case("true")) {
+            // Microsoft-hosted agents run the same images as GitHub-hosted runners.
+            let image = var("ImageOS").map(|image_os| image_label(&image_os));
+            return Some(Self {
+                provider: CiProvider::AzurePipelines,
+                self_hosted: Some(image.is_none()),
+                image,
+                image_version: var("ImageVersion"),
+                runner_version: var("AGENT_VERSION"),
+            });
+        }
+
+        None
+    }
+
+    /// Returns a label describing the image and the runner, e.g. "ubuntu-22.04 runner 2.317.0",
+    /// for build matrices.
+    pub fn label(&self) -> String {
+        let mut label = match &self.image {
+            Some(image) => image.clone(),
+            None => self.provider.to_string(),
+        };
+        if let Some(version) = &self.runner_version {
+            label.push_str(" runner ");
+            label.push_str(version);
+        }
+        label
+    }
+}
+
+impl Display for CiEnvironment {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.label())
+    }
+}
+
+impl Display for CiProvider {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::GitHubActions => "GitHub Actions",
+            Self::GitLab => "GitLab CI/CD",
+            Self::AzurePipelines => "Azure Pipelines",
+        })
+    }
+}
+
+/// Converts the `ImageOS` variable of hosted runners to the label used in workflows, e.g.
+/// "ubuntu-22.04" for "ubuntu22", "windows-2022" for "win22" or "macos-14" for "macos14".
+fn image_label(image_os: &str) -> String {
+    if let Some(year) = image_os.strip_prefix("ubuntu") {
+        format!("ubuntu-{year}.04")
+    } else if let Some(year) = image_os.strip_prefix("win") {
+        format!("windows-20{year}")
+    } else if let Some(version) = image_os.strip_prefix("macos") {
+        format!("macos-{version}")
+    } else {
+        image_os.to_string()
+    }
+}
+
+/// Finds a "Key: value" line in the details of the `imagedata.json` file of GitHub-hosted
+/// runners, whose JSON strings escape line feeds.
+fn image_data_value(image_data: &str, key: &str) -> Option<String> {
+    let prefix = format!("{key}: ");
+    image_data
+        .split("\\n")
+        .flat_map(|line| line.split('"'))
+        .find_map(|part| part.strip_prefix(&prefix))
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Returns the newest runner version installed in `dir`, whose subdirectories are named after
+/// the versions, e.g. "2.317.0".
+fn installed_runner(dir: &Path) -> Option<String> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter_map(|name| {
+            let components: Option<Vec<u32>> = name.split('.').map(|n| n.parse().ok()).collect();
+            Some((components?, name))
+        })
+        .max()
+        .map(|(_, name)| name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn env(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        |name| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+    }
+
+    #[test]
+    fn github_actions() {
+        let vars = env(&[
+            ("GITHUB_ACTIONS", "true"),
+            ("ImageOS", "ubuntu22"),
+            ("ImageVersion", "20240310.1.0"),
+            ("RUNNER_ENVIRONMENT", "github-hosted"),
+        ]);
+        let expected = CiEnvironment {
+            provider: CiProvider::GitHubActions,
+            image: Some(String::from("ubuntu-22.04")),
+            image_version: Some(String::from("20240310.1.0")),
+            runner_version: None,
+            self_hosted: Some(false),
+        };
+        let ci = CiEnvironment::detect(vars, Path::new("/nonexistent"));
+        assert_eq!(Some(expected), ci);
+        assert_eq!("ubuntu-22.04", ci.unwrap().label());
+    }
+
+    #[test]
+    fn gitlab_and_azure() {
+        let ci = CiEnvironment::detect(
+            env(&[("GITLAB_CI", "true"), ("CI_JOB_IMAGE", "rust:1.77"), ("CI_RUNNER_VERSION", "16.9.1")]),
+            Path::new("/nonexistent"),
+        )
+        .unwrap();
+        assert_eq!(CiProvider::GitLab, ci.provider);
+        assert_eq!("rust:1.77 runner 16.9.1", ci.to_string());
+
+        let ci = CiEnvironment::detect(
+            env(&[("TF_BUILD", "True"), ("ImageOS", "win22"), ("AGENT_VERSION", "3.236.1")]),
+            Path::new("/nonexistent"),
+        )
+        .unwrap();
+        assert_eq!(CiProvider::AzurePipelines, ci.provider);
+        assert_eq!(Some(false), ci.self_hosted);
+        assert_eq!("windows-2022 runner 3.236.1", ci.label());
+
+        let ci = CiEnvironment::detect(env(&[("TF_BUILD", "True")]), Path::new("/nonexistent")).unwrap();
+        assert_eq!(Some(true), ci.self_hosted);
+        assert_eq!("Azure Pipelines", ci.label());
+
+        assert_eq!(None, CiEnvironment::detect(env(&[("CI", "true")]), Path::new("/nonexistent")));
+    }
+
+    #[test]
+    fn image_labels() {
+        let data = [
+            ("ubuntu24", "ubuntu-24.04"),
+            ("win19", "windows-2019"),
+            ("macos14", "macos-14"),
+            ("custom", "custom"),
+        ];
+
+        for (image_os, expected) in data {
+            assert_eq!(expected, image_label(image_os));
+        }
+    }
+
+    #[cfg(feature = "detect")]
+    #[test]
+    fn runner_files() {
+        use crate::linux::tests::root_with;
+
+        let root = root_with(
+            "ci-github",
+            &[
+                (
+                    IMAGE_DATA_PATH,
+                    r#"[{"group":"Operating System","detail":"Ubuntu\n22.04.4\nLTS"},{"group":"Runner Image","detail":"Image: ubuntu-22.04\nVersion: 20240310.1.0\nIncluded Software: https://github.com/actions/runner-images"}]"#,
+                ),
+                ("home/runner/runners/2.316.1/bin/Runner.Worker", ""),
+                ("home/runner/runners/2.317.0/bin/Runner.Worker", ""),
+                ("home/runner/runners/current/bin/Runner.Worker", ""),
+            ],
+        );
+        let home = root.join("home/runner").display().to_string();
+        let var = |name: &str| match name {
+            "GITHUB_ACTIONS" => Some(String::from("true")),
+            "HOME" => Some(home.clone()),
+            _ => None,
+        };
+
+        let ci = CiEnvironment::detect(var, &root).unwrap();
+        assert_eq!(Some("ubuntu-22.04"), ci.image.as_deref());
+        assert_eq!(Some("20240310.1.0"), ci.image_version.as_deref());
+        assert_eq!(None, ci.self_hosted);
+        assert_eq!("ubuntu-22.04 runner 2.317.0", ci.label());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
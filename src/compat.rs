@@ -0,0 +1,252 @@
+//! Conversions between this crate's types and the types of the `os_info` crate, to migrate call
+//! sites incrementally. Requires the `os_info-compat` feature.
+//!
+//! `os_info` has no notion of variants, LTS releases or host chains, and `osinfo` doesn't track
+//! the bitness and architecture, so those are lost in the conversions.
+
+use crate::logging::warn;
+
+use crate::{OSInfo, OsKind, Version};
+
+use os_info::Type;
+
+/// `os_info` types and the operating system IDs they correspond to. When several IDs map to the
+/// same type, the first one is used for the conversion from `os_info`.
+pub(crate) static TYPES: &[(Type, &str)] = &[
+    (Type::AIX, "aix"),
+    (Type::AlmaLinux, "almalinux"),
+    (Type::Alpaquita, "alpaquita"),
+    (Type::Alpine, "alpine"),
+    (Type::ALTLinux, "altlinux"),
+    (Type::Amazon, "amzn"),
+    (Type::Android, "android"),
+    (Type::AOSC, "aosc"),
+    (Type::Arch, "arch"),
+    (Type::Artix, "artix"),
+    (Type::Bazzite, "bazzite"),
+    (Type::Bluefin, "bluefin"),
+    (Type::CachyOS, "cachyos"),
+    (Type::CentOS, "centos"),
+    (Type::Cygwin, "cygwin"),
+    (Type::Debian, "debian"),
+    (Type::DragonFly, "dragonfly"),
+    (Type::Elementary, "elementary"),
+    (Type::Emscripten, "emscripten"),
+    (Type::EndeavourOS, "endeavouros"),
+    (Type::Fedora, "fedora"),
+    (Type::FreeBSD, "freebsd"),
+    (Type::Garuda, "garuda"),
+    (Type::Gentoo, "gentoo"),
+    (Type::HardenedBSD, "hardenedbsd"),
+    (Type::Hurd, "hurd"),
+    (Type::Illumos, "illumos"),
+    (Type::InstantOS, "instantos"),
+    (Type::Ios, "ios"),
+    (Type::Kali, "kali"),
+    (Type::KDENeon, "neon"),
+    (Type::Linux, "linux"),
+    (Type::Mabox, "mabox"),
+    (Type::Macos, "macos"),
+    (Type::Manjaro, "manjaro"),
+    (Type::Mariner, "mariner"),
+    (Type::MidnightBSD, "midnightbsd"),
+    (Type::Mint, "linuxmint"),
+    (Type::NetBSD, "netbsd"),
+    (Type::NixOS, "nixos"),
+    (Type::Nobara, "nobara"),
+    (Type::OpenBSD, "openbsd"),
+    (Type::OpenCloudOS, "opencloudos"),
+    (Type::openEuler, "openeuler"),
+    (Type::openSUSE, "opensuse"),
+    (Type::openSUSE, "opensuse-leap"),
+    (Type::openSUSE, "opensuse-tumbleweed"),
+    (Type::OracleLinux, "ol"),
+    (Type::PikaOS, "pika"),
+    (Type::Pop, "pop"),
+    (Type::Raspbian, "raspbian"),
+    (Type::Redhat, "redhat"),
+    (Type::RedHatEnterprise, "rhel"),
+    (Type::Redox, "redox"),
+    (Type::RockyLinux, "rocky"),
+    (Type::Solus, "solus"),
+    (Type::SUSE, "sles"),
+    (Type::Ubuntu, "ubuntu"),
+    (Type::Ultramarine, "ultramarine"),
+    (Type::Uos, "uos"),
+    (Type::Void, "void"),
+    (Type::Windows, "windows"),
+    (Type::Zorin, "zorin"),
+    (Type::Unknown, "Unknown"),
+];
+
+/// Returns the `os_info` type of the operating system with the given ID.
+fn os_type(id: &str) -> Type {
+    let id = id.trim();
+    match TYPES.iter().find(|(_, type_id)| type_id.eq_ignore_ascii_case(id)) {
+        Some(&(os_type, _)) => os_type,
+        None if OsKind::of(id) == OsKind::Linux => Type::Linux,
+        None => Type::Unknown,
+    }
+}
+
+/// Returns the operating system ID of the given `os_info` type.
+fn id(os_type: Type) -> String {
+    match TYPES.iter().find(|&&(type_, _)| type_ == os_type) {
+        Some((_, id)) => String::from(*id),
+        // `Type` is non-exhaustive, so newer `os_info` releases may have types missing above.
+        None => format!("{os_type:?}").to_ascii_lowercase(),
+    }
+}
+
+/// Converts the version, dropping the `release` component `os_info` doesn't have.
+impl From<Version> for os_info::Version {
+    fn from(version: Version) -> Self {
+        match version {
+            Version::Unknown => Self::Unknown,
+            Version::Semantic(major, minor, build, _) => Self::Semantic(major.into(), minor.into(), build.into()),
+            Version::Rolling(date) => Self::Rolling(date),
+            Version::Custom(version) => Self::Custom(version),
+        }
+    }
+}
+
+/// Converts the version. Semantic versions with components that don't fit in `u32` become
+/// `Version::Custom`.
+impl From<os_info::Version> for Version {
+    fn from(version: os_info::Version) -> Self {
+        match version {
+            os_info::Version::Semantic(major, minor, patch) => {
+                match (u32::try_from(major), u32::try_from(minor), u32::try_from(patch)) {
+                    (Ok(major), Ok(minor), Ok(patch)) => Self::Semantic(major, minor, patch, 0),
+                    _ => Self::Custom(format!("{major}.{minor}.{patch}")),
+                }
+            }
+            os_info::Version::Rolling(date) => Self::Rolling(date),
+            os_info::Version::Custom(version) => Self::Custom(version),
+            os_info::Version::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Converts the information. The name is the display name of the `os_info` type, and the ID the
+/// corresponding os-release ID (e.g. `Type::RedHatEnterprise` becomes `rhel`).
+///
+/// # Example
+/// ```
+/// use osinfo::OSInfo;
+/// let info = OSInfo::from(os_info::Info::with_type(os_info::Type::Ubuntu));
+/// assert_eq!(info.id(), "ubuntu");
+/// assert_eq!(info.name(), "Ubuntu");
+/// ```
+impl From<os_info::Info> for OSInfo {
+    fn from(info: os_info::Info) -> Self {
+        let os_type = info.os_type();
+        let mut result = Self {
+            version: info.version().clone().into(),
+            edition: info.edition().map(String::from),
+            codename: info.codename().map(String::from),
+            ..Self::new_unknown()
+        };
+        if os_type != Type::Unknown {
+            result.id = Some(id(os_type));
+            result.name = Some(os_type.to_string());
+        }
+        result
+    }
+}
+
+/// Converts the information. IDs without an `os_info` type become `Type::Linux` for the Linux
+/// distributions in `consts` and `Type::Unknown` otherwise. The bitness is unknown.
+///
+/// # Example
+/// ```
+/// use osinfo::OSInfo;
+/// let info = os_info::Info::from(OSInfo::with_id("rhel".to_string()));
+/// assert_eq!(info.os_type(), os_info::Type::RedHatEnterprise);
+/// ```
+impl From<OSInfo> for os_info::Info {
+    fn from(info: OSInfo) -> Self {
+        let os_type = os_type(info.id.as_deref().unwrap_or_default());
+
+        // `os_info::Info` can only be built field by field through its `Deserialize`
+        // implementation.
+        let value = serde_json::json!({
+            "os_type": os_type,
+            "version": os_info::Version::from(info.version),
+            "edition": info.edition,
+            "codename": info.codename,
+            "bitness": os_info::Bitness::Unknown,
+            "architecture": None::<String>,
+        });
+        serde_json::from_value(value).unwrap_or_else(|e| {
+            warn!("Failed to construct os_info::Info: {}", e);
+            Self::with_type(os_type)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn version() {
+        let data = [
+            (Version::Unknown, os_info::Version::Unknown),
+            (Version::Semantic(22, 4, 3, 0), os_info::Version::Semantic(22, 4, 3)),
+            (Version::Rolling(None), os_info::Version::Rolling(None)),
+            (Version::Custom(String::from("unstable")), os_info::Version::Custom(String::from("unstable"))),
+        ];
+
+        for (version, expected) in data {
+            assert_eq!(expected, os_info::Version::from(version.clone()));
+            assert_eq!(version, Version::from(expected));
+        }
+
+        assert_eq!(
+            Version::Custom(String::from("1.2.4294967296")),
+            Version::from(os_info::Version::Semantic(1, 2, u64::from(u32::MAX) + 1))
+        );
+    }
+
+    #[test]
+    fn os_info_to_osinfo() {
+        let info = OSInfo::from(os_info::Info::with_type(Type::RedHatEnterprise));
+        assert_eq!("rhel", info.id());
+        assert_eq!("Red Hat Enterprise Linux", info.name());
+
+        assert_eq!(OSInfo::new_unknown(), OSInfo::from(os_info::Info::unknown()));
+    }
+
+    #[test]
+    fn round_trip() {
+        let info = OSInfo {
+            version: Version::Semantic(22, 4, 0, 0),
+            edition: Some(String::from("Server")),
+            codename: Some(String::from("jammy")),
+            ..OSInfo::with_id(String::from("ubuntu"))
+        };
+
+        let converted = os_info::Info::from(info);
+        assert_eq!(Type::Ubuntu, converted.os_type());
+        assert_eq!(&os_info::Version::Semantic(22, 4, 0), converted.version());
+        assert_eq!(Some("Server"), converted.edition());
+        assert_eq!(Some("jammy"), converted.codename());
+
+        let info = OSInfo::from(converted);
+        assert_eq!("ubuntu", info.id());
+        assert_eq!(&Version::Semantic(22, 4, 0, 0), info.version());
+        assert_eq!(Some("jammy"), info.codename());
+    }
+
+    #[test]
+    fn unmapped_ids() {
+        assert_eq!(Type::openSUSE, os_type("opensuse-leap"));
+        assert_eq!(Type::Linux, os_type("clear-linux-os"));
+        assert_eq!(Type::Unknown, os_type("kvm"));
+        assert_eq!(Type::Unknown, os_type("sunos"));
+        assert_eq!(Type::Unknown, os_type("solaris"));
+        assert_eq!(Type::Unknown, os_type("haiku"));
+    }
+}
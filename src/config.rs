@@ -0,0 +1,136 @@
+//! This module defines the `Config` struct controlling how `osinfo::get_with` detects the
+//! operating system.
+
+use std::path::PathBuf;
+
+use crate::Matcher;
+
+/// Options for the detection of the operating system, see `osinfo::get_with`.
+///
+/// The default configuration behaves like `osinfo::get()`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::Config;
+///
+/// // Never spawn subprocesses, e.g. in a sandboxed or setuid program.
+/// let config = Config {
+///     run_external_commands: false,
+///     ..Default::default()
+/// };
+/// let info = osinfo::get_with(&config);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Whether `/etc/lsb-release` is consulted on Linux. Some tools rewrite it to describe a
+    /// derivative or the distribution a container was built from. Defaults to `true`.
+    pub follow_lsb_release: bool,
+    /// Whether external commands may be run, e.g. `profiles` on macOS to detect MDM enrollment.
+    /// Defaults to `true`, and has no effect once `osinfo::set_external_commands_enabled`
+    /// disabled them for the whole process.
+    pub run_external_commands: bool,
+    /// Maximum size in bytes of the release files read on Linux, overriding the process-wide
+    /// limit set by `osinfo::set_max_file_size`, or `None` to use that limit.
+    pub max_file_size: Option<u64>,
+    /// Root file system of the Linux distribution to detect instead of the current operating
+    /// system, e.g. a mounted disk image (see `osinfo::get_from_root`).
+    pub custom_root: Option<PathBuf>,
+    /// Release files of distributions this crate doesn't know, consulted after the built-in
+    /// ones on Linux.
+    pub extra_distros: Vec<ExtraDistro>,
+    /// What to do with the host's os-release exposed to a Flatpak sandbox or a Toolbx /
+    /// Distrobox container on Linux. Defaults to `HostOsRelease::Chain`.
+    pub host_os_release: HostOsRelease,
+    /// Whether the release files are read concurrently on Linux, one thread each, which reduces
+    /// the latency of a cold start on slow storage such as network file systems. The result is
+    /// the same as when reading them one after the other. Defaults to `false`.
+    pub parallel: bool,
+}
+
+/// Use of the host's os-release file exposed to a sandbox or container under `/run/host`, i.e.
+/// `/run/host/os-release` in a Flatpak sandbox and `/run/host/etc/os-release` in Toolbx and
+/// Distrobox containers, which mount the host's root file system there.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::{Config, HostOsRelease};
+///
+/// // Report the distribution of the host rather than the one of the Flatpak runtime.
+/// let config = Config {
+///     host_os_release: HostOsRelease::Report,
+///     ..Default::default()
+/// };
+/// let info = osinfo::get_with(&config);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum HostOsRelease {
+    /// Don't read the host's os-release.
+    Ignore,
+    /// Describe the host by its os-release in the host chain (see `OSInfo::host`), while the
+    /// result describes the sandbox runtime or container.
+    #[default]
+    Chain,
+    /// Describe the host by its os-release in the result, as if detection ran on the host. The
+    /// sandbox or container itself isn't described.
+    Report,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            follow_lsb_release: true,
+            run_external_commands: true,
+            max_file_size: None,
+            custom_root: None,
+            extra_distros: Vec::new(),
+            host_os_release: HostOsRelease::default(),
+            parallel: false,
+        }
+    }
+}
+
+/// Release file of a Linux distribution unknown to this crate.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::{Config, ExtraDistro, Matcher};
+///
+/// // /etc/acme-release contains e.g. "Acme Linux release 4.2 (Roadrunner)".
+/// let acme = ExtraDistro {
+///     codename: Some(Matcher::Between { start: '(', end: ')' }),
+///     ..ExtraDistro::new("etc/acme-release", "acme", "Acme Linux", Matcher::PrefixedVersion { prefix: "release" })
+/// };
+/// let config = Config {
+///     extra_distros: vec![acme],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExtraDistro {
+    /// Path of the release file relative to the root, e.g. "etc/acme-release".
+    pub path: String,
+    /// ID of the distribution the release file identifies, e.g. "acme".
+    pub id: String,
+    /// Name of the distribution, e.g. "Acme Linux".
+    pub name: String,
+    /// Finds the version in the release file.
+    pub version: Matcher,
+    /// Finds the codename in the release file.
+    pub codename: Option<Matcher>,
+}
+
+impl ExtraDistro {
+    /// Creates the description of a release file without codename.
+    pub fn new(path: impl Into<String>, id: impl Into<String>, name: impl Into<String>, version: Matcher) -> Self {
+        Self {
+            path: path.into(),
+            id: id.into(),
+            name: name.into(),
+            version,
+            codename: None,
+        }
+    }
+}
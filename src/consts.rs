@@ -0,0 +1,360 @@
+//! Operating system IDs, the values of `OSInfo::id`, to match against without typos in string
+//! literals.
+//!
+//! The IDs the crate assigns itself (Windows, macOS, the layers and hypervisors of host chains,
+//! the kernels of the uname fallback and the distributions detected from legacy release files)
+//! are listed in `ALL`, except that the uname fallback reports kernels it doesn't know by their
+//! lowercase name, e.g. "serenityos". Linux distributions are otherwise reported with the `ID` of their
+//! os-release file, so distributions the crate doesn't know may have other IDs; the ones listed
+//! here are those it recognizes, e.g. in `DistroFamily::of`.
+//!
+//! # Example
+//! ```
+//! use osinfo::{OSInfo, consts};
+//!
+//! let info = OSInfo::builder().id("ubuntu").build();
+//! let package_manager = match info.id() {
+//!     consts::DEBIAN | consts::UBUNTU => "apt",
+//!     consts::FEDORA | consts::RHEL => "dnf",
+//!     consts::MACOS => "brew",
+//!     _ => "unknown",
+//! };
+//! assert_eq!(package_manager, "apt");
+//! assert!(consts::ALL.contains(&info.id()));
+//! ```
+
+// spell-checker:disable
+
+/// Unknown operating system, see `OSInfo::new_unknown`.
+pub const UNKNOWN: &str = "Unknown";
+
+/// Microsoft Windows.
+pub const WINDOWS: &str = "windows";
+/// Windows Subsystem for Linux, in host chains.
+pub const WSL: &str = "wsl";
+/// Apple macOS.
+pub const MACOS: &str = "macos";
+/// Apple iOS.
+pub const IOS: &str = "ios";
+/// Android.
+pub const ANDROID: &str = "android";
+/// Linux, when the distribution is unknown, e.g. the host of a container.
+pub const LINUX: &str = "linux";
+/// ChromeOS, the host of Crostini.
+pub const CHROMEOS: &str = "chromeos";
+
+/// Google Compute Engine.
+pub const GCE: &str = "gce";
+/// Microsoft Hyper-V.
+pub const HYPERV: &str = "hyperv";
+/// KVM.
+pub const KVM: &str = "kvm";
+/// AWS Nitro.
+pub const NITRO: &str = "nitro";
+/// Parallels.
+pub const PARALLELS: &str = "parallels";
+/// QEMU.
+pub const QEMU: &str = "qemu";
+/// Oracle VirtualBox.
+pub const VIRTUALBOX: &str = "virtualbox";
+/// VMware.
+pub const VMWARE: &str = "vmware";
+/// Xen.
+pub const XEN: &str = "xen";
+
+/// IBM AIX.
+pub const AIX: &str = "aix";
+/// Cygwin.
+pub const CYGWIN: &str = "cygwin";
+/// DragonFly BSD.
+pub const DRAGONFLY: &str = "dragonfly";
+/// Emscripten.
+pub const EMSCRIPTEN: &str = "emscripten";
+/// FreeBSD.
+pub const FREEBSD: &str = "freebsd";
+/// HardenedBSD.
+pub const HARDENEDBSD: &str = "hardenedbsd";
+/// Haiku.
+pub const HAIKU: &str = "haiku";
+/// GNU Hurd.
+pub const HURD: &str = "hurd";
+/// illumos.
+pub const ILLUMOS: &str = "illumos";
+/// Debian GNU/kFreeBSD, the GNU userland on the FreeBSD kernel.
+pub const KFREEBSD: &str = "kfreebsd";
+/// MidnightBSD.
+pub const MIDNIGHTBSD: &str = "midnightbsd";
+/// MinGW, the MSYS2 environments building native Windows programs.
+pub const MINGW: &str = "mingw";
+/// MINIX.
+pub const MINIX: &str = "minix";
+/// MSYS2.
+pub const MSYS: &str = "msys";
+/// NetBSD.
+pub const NETBSD: &str = "netbsd";
+/// OpenBSD.
+pub const OPENBSD: &str = "openbsd";
+/// QNX Neutrino.
+pub const QNX: &str = "qnx";
+/// Redox.
+pub const REDOX: &str = "redox";
+/// Oracle Solaris.
+pub const SOLARIS: &str = "solaris";
+/// The kernel name of Solaris and illumos.
+pub const SUNOS: &str = "sunos";
+
+/// Debian.
+pub const DEBIAN: &str = "debian";
+/// Deepin.
+pub const DEEPIN: &str = "deepin";
+/// Devuan.
+pub const DEVUAN: &str = "devuan";
+/// elementary OS.
+pub const ELEMENTARY: &str = "elementary";
+/// Kali Linux.
+pub const KALI: &str = "kali";
+/// Linux Mint.
+pub const LINUXMINT: &str = "linuxmint";
+/// MX Linux.
+pub const MX: &str = "mx";
+/// KDE neon.
+pub const NEON: &str = "neon";
+/// Parrot OS.
+pub const PARROT: &str = "parrot";
+/// Pop!_OS.
+pub const POP: &str = "pop";
+/// Raspberry Pi OS.
+pub const RASPBIAN: &str = "raspbian";
+/// Ubuntu.
+pub const UBUNTU: &str = "ubuntu";
+/// Zorin OS.
+pub const ZORIN: &str = "zorin";
+
+/// AlmaLinux.
+pub const ALMALINUX: &str = "almalinux";
+/// Amazon Linux.
+pub const AMZN: &str = "amzn";
+/// Azure Linux.
+pub const AZURELINUX: &str = "azurelinux";
+/// Bazzite.
+pub const BAZZITE: &str = "bazzite";
+/// Bluefin.
+pub const BLUEFIN: &str = "bluefin";
+/// CentOS.
+pub const CENTOS: &str = "centos";
+/// CloudLinux.
+pub const CLOUDLINUX: &str = "cloudlinux";
+/// EuroLinux.
+pub const EUROLINUX: &str = "eurolinux";
+/// Fedora Linux.
+pub const FEDORA: &str = "fedora";
+/// CBL-Mariner, the former name of Azure Linux.
+pub const MARINER: &str = "mariner";
+/// Nobara.
+pub const NOBARA: &str = "nobara";
+/// Oracle Linux.
+pub const OL: &str = "ol";
+/// Red Hat Linux, the predecessor of Red Hat Enterprise Linux.
+pub const REDHAT: &str = "redhat";
+/// Red Hat Enterprise Linux.
+pub const RHEL: &str = "rhel";
+/// Rocky Linux.
+pub const ROCKY: &str = "rocky";
+/// Scientific Linux.
+pub const SCIENTIFIC: &str = "scientific";
+/// Ultramarine Linux.
+pub const ULTRAMARINE: &str = "ultramarine";
+/// Virtuozzo.
+pub const VIRTUOZZO: &str = "virtuozzo";
+
+/// openSUSE, before Leap and Tumbleweed.
+pub const OPENSUSE: &str = "opensuse";
+/// openSUSE Leap.
+pub const OPENSUSE_LEAP: &str = "opensuse-leap";
+/// openSUSE MicroOS.
+pub const OPENSUSE_MICROOS: &str = "opensuse-microos";
+/// openSUSE Slowroll.
+pub const OPENSUSE_SLOWROLL: &str = "opensuse-slowroll";
+/// openSUSE Tumbleweed.
+pub const OPENSUSE_TUMBLEWEED: &str = "opensuse-tumbleweed";
+/// SUSE Linux Enterprise Micro.
+pub const SLE_MICRO: &str = "sle-micro";
+/// SUSE Linux Enterprise Desktop.
+pub const SLED: &str = "sled";
+/// SUSE Linux Enterprise Server.
+pub const SLES: &str = "sles";
+/// SUSE Linux Enterprise High Performance Computing.
+pub const SLES_HPC: &str = "sles_hpc";
+/// SUSE Linux Enterprise Server for SAP Applications.
+pub const SLES_SAP: &str = "sles_sap";
+
+/// Arch Linux.
+pub const ARCH: &str = "arch";
+/// Arch Linux ARM.
+pub const ARCHARM: &str = "archarm";
+/// Artix Linux.
+pub const ARTIX: &str = "artix";
+/// CachyOS.
+pub const CACHYOS: &str = "cachyos";
+/// EndeavourOS.
+pub const ENDEAVOUROS: &str = "endeavouros";
+/// Garuda Linux.
+pub const GARUDA: &str = "garuda";
+/// Manjaro.
+pub const MANJARO: &str = "manjaro";
+
+/// Alpine Linux.
+pub const ALPINE: &str = "alpine";
+/// postmarketOS.
+pub const POSTMARKETOS: &str = "postmarketos";
+
+/// Calculate Linux.
+pub const CALCULATE: &str = "calculate";
+/// Funtoo.
+pub const FUNTOO: &str = "funtoo";
+/// Gentoo.
+pub const GENTOO: &str = "gentoo";
+
+/// Alpaquita Linux.
+pub const ALPAQUITA: &str = "alpaquita";
+/// ALT Linux.
+pub const ALTLINUX: &str = "altlinux";
+/// AOSC OS.
+pub const AOSC: &str = "aosc";
+/// Bottlerocket.
+pub const BOTTLEROCKET: &str = "bottlerocket";
+/// Clear Linux OS.
+pub const CLEAR_LINUX: &str = "clear-linux-os";
+/// Container-Optimized OS from Google.
+pub const COS: &str = "cos";
+/// instantOS.
+pub const INSTANTOS: &str = "instantos";
+/// Mabox Linux.
+pub const MABOX: &str = "mabox";
+/// Mageia.
+pub const MAGEIA: &str = "mageia";
+/// NixOS.
+pub const NIXOS: &str = "nixos";
+/// OpenCloudOS.
+pub const OPENCLOUDOS: &str = "opencloudos";
+/// openEuler.
+pub const OPENEULER: &str = "openeuler";
+/// PikaOS.
+pub const PIKA: &str = "pika";
+/// Slackware.
+pub const SLACKWARE: &str = "slackware";
+/// Solus.
+pub const SOLUS: &str = "solus";
+/// UnionTech OS.
+pub const UOS: &str = "uos";
+/// Void Linux.
+pub const VOID: &str = "void";
+
+/// Every ID above.
+///
+/// `OSInfo::id` may be outside this set: for Linux distributions the crate doesn't know, and for
+/// kernels without an ID here, reported by the uname fallback as their lowercase name.
+pub const ALL: &[&str] = &[
+    UNKNOWN, WINDOWS, WSL, MACOS, IOS, ANDROID, LINUX, CHROMEOS,
+    GCE, HYPERV, KVM, NITRO, PARALLELS, QEMU, VIRTUALBOX, VMWARE, XEN,
+    AIX, CYGWIN, DRAGONFLY, EMSCRIPTEN, FREEBSD, HAIKU, HARDENEDBSD, HURD, ILLUMOS, KFREEBSD, MIDNIGHTBSD, MINGW, MINIX,
+    MSYS, NETBSD, OPENBSD, QNX, REDOX, SOLARIS, SUNOS,
+    DEBIAN, DEEPIN, DEVUAN, ELEMENTARY, KALI, LINUXMINT, MX, NEON, PARROT, POP, RASPBIAN, UBUNTU, ZORIN,
+    ALMALINUX, AMZN, AZURELINUX, BAZZITE, BLUEFIN, CENTOS, CLOUDLINUX, EUROLINUX, FEDORA, MARINER, NOBARA, OL, REDHAT,
+    RHEL, ROCKY, SCIENTIFIC, ULTRAMARINE, VIRTUOZZO,
+    OPENSUSE, OPENSUSE_LEAP, OPENSUSE_MICROOS, OPENSUSE_SLOWROLL, OPENSUSE_TUMBLEWEED, SLE_MICRO, SLED, SLES, SLES_HPC,
+    SLES_SAP,
+    ARCH, ARCHARM, ARTIX, CACHYOS, ENDEAVOUROS, GARUDA, MANJARO,
+    ALPINE, POSTMARKETOS,
+    CALCULATE, FUNTOO, GENTOO,
+    ALPAQUITA, ALTLINUX, AOSC, BOTTLEROCKET, CLEAR_LINUX, COS, INSTANTOS, MABOX, MAGEIA, NIXOS, OPENCLOUDOS, OPENEULER, PIKA,
+    SLACKWARE, SOLUS, UOS, VOID,
+];
+
+/// The IDs above of Linux and its distributions, see `OsKind::of`.
+pub(crate) const LINUX_IDS: &[&str] = &[
+    LINUX, CHROMEOS,
+    DEBIAN, DEEPIN, DEVUAN, ELEMENTARY, KALI, LINUXMINT, MX, NEON, PARROT, POP, RASPBIAN, UBUNTU, ZORIN,
+    ALMALINUX, AMZN, AZURELINUX, BAZZITE, BLUEFIN, CENTOS, CLOUDLINUX, EUROLINUX, FEDORA, MARINER, NOBARA, OL, REDHAT,
+    RHEL, ROCKY, SCIENTIFIC, ULTRAMARINE, VIRTUOZZO,
+    OPENSUSE, OPENSUSE_LEAP, OPENSUSE_MICROOS, OPENSUSE_SLOWROLL, OPENSUSE_TUMBLEWEED, SLE_MICRO, SLED, SLES, SLES_HPC,
+    SLES_SAP,
+    ARCH, ARCHARM, ARTIX, CACHYOS, ENDEAVOUROS, GARUDA, MANJARO,
+    ALPINE, POSTMARKETOS,
+    CALCULATE, FUNTOO, GENTOO,
+    ALPAQUITA, ALTLINUX, AOSC, BOTTLEROCKET, CLEAR_LINUX, COS, INSTANTOS, MABOX, MAGEIA, NIXOS, OPENCLOUDOS, OPENEULER, PIKA,
+    SLACKWARE, SOLUS, UOS, VOID,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn unique() {
+        let mut ids: Vec<&str> = ALL.to_vec();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ALL.len(), ids.len());
+    }
+
+    #[test]
+    fn tables() {
+        for id in LINUX_IDS {
+            assert!(ALL.contains(id), "{id}");
+        }
+        #[cfg(feature = "std")]
+        for id in crate::os_info::TARGET_NAMES.iter().map(|&(id, _)| id) {
+            assert!(ALL.contains(&id), "{id}");
+        }
+        #[cfg(feature = "os_info-compat")]
+        for id in crate::compat::TYPES.iter().map(|&(_, id)| id) {
+            assert!(ALL.contains(&id), "{id}");
+        }
+    }
+
+    /// Detects every layer the Linux backend reports from files, and checks that the IDs it
+    /// assigns itself are canonical.
+    #[cfg(feature = "detect")]
+    #[test]
+    fn detected_ids() {
+        use crate::{Config, linux};
+        use std::fs;
+
+        let roots: [&[(&str, &str)]; 8] = [
+            &[("etc/debian_version", "12.5\n")],
+            &[("etc/alpine-release", "3.19.1\n")],
+            &[("etc/redhat-release", "Rocky Linux release 9.3 (Blue Onyx)\n")],
+            &[("etc/system-release", "Amazon Linux release 2 (Karoo)\n")],
+            &[("etc/os-release", "ID=debian\n"), ("proc/sys/kernel/osrelease", "5.15.146.1-microsoft-standard-WSL2\n")],
+            &[("etc/os-release", "ID=debian\n"), ("dev/.cros_milestone", "120\n")],
+            &[("etc/os-release", "ID=debian\n"), (".dockerenv", ""), ("sys/class/dmi/id/sys_vendor", "QEMU\n")],
+            &[("etc/os-release", "ID=fedora\n"), ("sys/class/dmi/id/sys_vendor", "innotek GmbH\n")],
+        ];
+
+        for (index, files) in roots.iter().enumerate() {
+            let root = linux::tests::root_with(&alloc::format!("consts-{index}"), files);
+            let info = linux::get_info_from(&root, &Config::default(), &mut Vec::new()).unwrap();
+            for layer in core::iter::once(&info).chain(info.hosts()) {
+                assert!(ALL.contains(&layer.id()), "{} in {:?}", layer.id(), files);
+            }
+            fs::remove_dir_all(root).unwrap();
+        }
+
+        // The kernels uname reports in practice have an ID here.
+        let sysnames = [
+            "Linux", "FreeBSD", "OpenBSD", "SunOS", "AIX", "GNU", "GNU/kFreeBSD", "Haiku", "Minix", "QNX",
+            "CYGWIN_NT-10.0-19045", "MSYS_NT-10.0", "MINGW64_NT-10.0-19045",
+        ];
+        for sysname in sysnames {
+            let uname = crate::unix::Uname {
+                sysname: String::from(sysname),
+                release: String::from("1.0"),
+            };
+            let info = uname.to_os_info("uname");
+            assert!(ALL.contains(&info.id()), "{} for {sysname}", info.id());
+        }
+    }
+}
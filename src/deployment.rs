@@ -0,0 +1,55 @@
+//! This module defines the `Deployment` struct identifying the image or generation an
+//! image-based operating system (NixOS, Guix System, ostree based distributions, Flatcar) booted.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Identity of the booted deployment of an image-based operating system.
+///
+/// Two machines with the same operating system version may run different deployments, e.g.
+/// NixOS systems built from different configurations, so fleet management tools should compare
+/// the deployment identity rather than the version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Deployment {
+    /// How the operating system is deployed.
+    pub kind: DeploymentKind,
+    /// System profile generation (NixOS and Guix System), e.g. 42.
+    pub generation: Option<u32>,
+    /// Identity of the booted system image: the store path of the system closure (NixOS and Guix
+    /// System), e.g. "/nix/store/9f7r...-nixos-system-host-23.11", or the ostree commit checksum.
+    pub commit: Option<String>,
+    /// Where updates come from: the ostree refspec, e.g.
+    /// "fedora:fedora/39/x86_64/silverblue", or the container image reference of ostree native
+    /// containers.
+    pub origin: Option<String>,
+    /// Update channel, e.g. "stable", "beta" or "alpha" (Flatcar Container Linux).
+    pub channel: Option<String>,
+}
+
+/// How an image-based operating system is deployed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DeploymentKind {
+    /// NixOS system profile generation.
+    Nix,
+    /// Guix System profile generation.
+    Guix,
+    /// ostree (or rpm-ostree / bootc) deployment, e.g. Fedora Silverblue, Kinoite or CoreOS.
+    Ostree,
+    /// Flatcar Container Linux (or the original CoreOS Container Linux) A/B partition image.
+    Flatcar,
+}
+
+impl Deployment {
+    /// Creates a deployment of the given kind with unknown identity.
+    pub fn new(kind: DeploymentKind) -> Self {
+        Self {
+            kind,
+            generation: None,
+            commit: None,
+            origin: None,
+            channel: None,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! This module defines the `Diagnostic` type recording what happened during detection, to help
+//! debugging "Unknown" results reported from machines you don't have access to.
+
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A single event recorded during detection.
+///
+/// A source is a file path (e.g. `/etc/os-release`) or a registry key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum Diagnostic {
+    /// The source doesn't exist.
+    SourceMissing {
+        /// Path of the source.
+        source: String,
+    },
+    /// The source exists but couldn't be read.
+    SourceUnreadable {
+        /// Path of the source.
+        source: String,
+        /// Description of the error.
+        error: String,
+    },
+    /// The source was read and contributed to the result.
+    SourceUsed {
+        /// Path of the source.
+        source: String,
+    },
+    /// The source was read but ignored.
+    SourceIgnored {
+        /// Path of the source.
+        source: String,
+        /// Why the source was ignored.
+        reason: String,
+    },
+    /// The matcher for a field didn't find a value in the source.
+    FieldNotFound {
+        /// Path of the source.
+        source: String,
+        /// Name of the field, e.g. "version".
+        field: String,
+    },
+    /// No source could identify the operating system; the result is `OSInfo::new_unknown()`.
+    NotDetected,
+}
+
+impl Diagnostic {
+    pub(crate) fn source_missing(source: impl Display) -> Self {
+        Self::SourceMissing { source: source.to_string() }
+    }
+
+    pub(crate) fn source_unreadable(source: impl Display, error: impl Display) -> Self {
+        Self::SourceUnreadable {
+            source: source.to_string(),
+            error: error.to_string(),
+        }
+    }
+
+    pub(crate) fn source_used(source: impl Display) -> Self {
+        Self::SourceUsed { source: source.to_string() }
+    }
+
+    pub(crate) fn source_ignored(source: impl Display, reason: impl Into<String>) -> Self {
+        Self::SourceIgnored {
+            source: source.to_string(),
+            reason: reason.into(),
+        }
+    }
+
+    pub(crate) fn field_not_found(source: impl Display, field: &str) -> Self {
+        Self::FieldNotFound {
+            source: source.to_string(),
+            field: field.to_string(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::SourceMissing { source } => write!(f, "{source}: missing"),
+            Self::SourceUnreadable { source, error } => write!(f, "{source}: unreadable ({error})"),
+            Self::SourceUsed { source } => write!(f, "{source}: used"),
+            Self::SourceIgnored { source, reason } => write!(f, "{source}: ignored ({reason})"),
+            Self::FieldNotFound { source, field } => write!(f, "{source}: {field} not found"),
+            Self::NotDetected => f.write_str("operating system not detected"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn display() {
+        let data = [
+            (Diagnostic::source_missing("/etc/os-release"), "/etc/os-release: missing"),
+            (
+                Diagnostic::source_unreadable("/etc/os-release", "permission denied"),
+                "/etc/os-release: unreadable (permission denied)",
+            ),
+            (Diagnostic::source_used("/etc/os-release"), "/etc/os-release: used"),
+            (
+                Diagnostic::source_ignored("/etc/redhat-release", "describes centos"),
+                "/etc/redhat-release: ignored (describes centos)",
+            ),
+            (Diagnostic::field_not_found("/etc/os-release", "version"), "/etc/os-release: version not found"),
+            (Diagnostic::NotDetected, "operating system not detected"),
+        ];
+
+        for (diagnostic, expected) in &data {
+            assert_eq!(*expected, diagnostic.to_string());
+        }
+    }
+}
@@ -0,0 +1,41 @@
+//! This module defines the `DisplayServer` struct describing the graphical environment of the
+//! current session.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Graphical environment of the current session.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DisplayServer {
+    /// Display server the session is connected to.
+    pub kind: DisplayServerKind,
+    /// Compositor or desktop environment, e.g. "GNOME", "KDE", "sway" or "Hyprland".
+    pub compositor: Option<String>,
+    /// Whether a monitor is connected, or `None` if it couldn't be determined. A session may
+    /// have a display server without a monitor, e.g. a virtual machine or a remote session.
+    pub display_attached: Option<bool>,
+}
+
+/// Display server of a session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DisplayServerKind {
+    /// The X Window System.
+    X11,
+    /// A Wayland compositor. X11 applications may also run through XWayland.
+    Wayland,
+    /// The macOS WindowServer.
+    Quartz,
+    /// The Windows Desktop Window Manager.
+    Windows,
+    /// No display server; applications should use a text interface.
+    Headless,
+}
+
+impl DisplayServer {
+    /// Returns whether graphical applications can be shown.
+    pub fn is_graphical(&self) -> bool {
+        self.kind != DisplayServerKind::Headless
+    }
+}
@@ -0,0 +1,165 @@
+//! Runs the external commands detection falls back to, e.g. `ldd` or `sysctl`, so that a wedged
+//! binary or a flood of output can't hang detection.
+
+use std::{
+    io::{self, Read},
+    process::{Child, Command, Output, Stdio},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::logging::trace;
+
+use crate::{DEFAULT_COMMAND_TIMEOUT, DEFAULT_MAX_COMMAND_OUTPUT};
+
+/// Interval at which a running command is checked for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+static TIMEOUT_MS: AtomicU64 = AtomicU64::new(DEFAULT_COMMAND_TIMEOUT.as_millis() as u64);
+static MAX_OUTPUT: AtomicU64 = AtomicU64::new(DEFAULT_MAX_COMMAND_OUTPUT);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn set_timeout(timeout: Duration) {
+    TIMEOUT_MS.store(timeout.as_millis().try_into().unwrap_or(u64::MAX), Ordering::Relaxed);
+}
+
+pub fn set_max_output(bytes: u64) {
+    MAX_OUTPUT.store(bytes, Ordering::Relaxed);
+}
+
+/// Runs `program` with `args` like `Command::output`, without standard input. Fails with
+/// `io::ErrorKind::TimedOut` if the command runs longer than the timeout, with
+/// `io::ErrorKind::InvalidData` if its standard output or error exceeds the size limit (the
+/// command is killed in both cases), and with `io::ErrorKind::Unsupported` if external
+/// commands are disabled.
+pub fn run(program: &str, args: &[&str]) -> io::Result<Output> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return Err(io::Error::new(io::ErrorKind::Unsupported, "external commands are disabled"));
+    }
+    let timeout = Duration::from_millis(TIMEOUT_MS.load(Ordering::Relaxed));
+    let max_output = MAX_OUTPUT.load(Ordering::Relaxed);
+
+    let mut command = Command::new(program);
+    command.args(args);
+    run_with(command, timeout, max_output)
+}
+
+fn run_with(mut command: Command, timeout: Duration, max_output: u64) -> io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // The pipes are drained concurrently, or a command filling one of them would block. Readers
+    // stop after the limit, closing the pipe, so that the command fails writing instead.
+    let read = |pipe: Option<Box<dyn Read + Send>>| {
+        thread::spawn(move || {
+            let mut data = Vec::new();
+            if let Some(pipe) = pipe {
+                let _ = pipe.take(max_output.saturating_add(1)).read_to_end(&mut data);
+            }
+            data
+        })
+    };
+    let stdout = read(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+    let stderr = read(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+
+    let status = wait(&mut child, timeout).inspect_err(|_| {
+        trace!("Killing {:?} after {:?}", command, timeout);
+        let _ = child.kill();
+        let _ = child.wait();
+    })?;
+
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if stdout.len() as u64 > max_output || stderr.len() as u64 > max_output {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("output of {command:?} exceeds {max_output} bytes"),
+        ));
+    }
+
+    Ok(Output { status, stdout, stderr })
+}
+
+/// Waits for `child` to exit, at most `timeout`.
+fn wait(child: &mut Child, timeout: Duration) -> io::Result<std::process::ExitStatus> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            return Err(io::Error::new(io::ErrorKind::TimedOut, format!("command timed out after {timeout:?}")));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::{path::Path, sync::Mutex};
+
+    use crate::{unix, Config};
+
+    /// Serializes the tests running commands through `run`, which some tests disable.
+    static ENABLED_LOCK: Mutex<()> = Mutex::new(());
+
+    fn sh(script: &str) -> Command {
+        let mut command = Command::new("sh");
+        command.args(["-c", script]);
+        command
+    }
+
+    #[test]
+    fn output() {
+        let output = run_with(sh("echo out; echo err >&2; exit 3"), Duration::from_secs(10), 1024).unwrap();
+        assert_eq!(b"out\n".as_slice(), output.stdout);
+        assert_eq!(b"err\n".as_slice(), output.stderr);
+        assert_eq!(Some(3), output.status.code());
+    }
+
+    #[test]
+    fn timeout() {
+        let start = Instant::now();
+        let error = run_with(sh("sleep 10"), Duration::from_millis(50), 1024).unwrap_err();
+        assert_eq!(io::ErrorKind::TimedOut, error.kind());
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn output_limit() {
+        let start = Instant::now();
+        let error = run_with(sh("yes"), Duration::from_secs(10), 4096).unwrap_err();
+        assert_eq!(io::ErrorKind::InvalidData, error.kind());
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        assert!(run_with(sh("printf 1234"), Duration::from_secs(10), 4).is_ok());
+    }
+
+    #[test]
+    fn missing_program() {
+        let _lock = ENABLED_LOCK.lock().unwrap();
+        let error = run("osinfo-no-such-program", &[]).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+    }
+    #[test]
+    fn disabled_overrides_config() {
+        let _lock = ENABLED_LOCK.lock().unwrap();
+        set_enabled(false);
+        let error = run("sh", &["-c", "true"]).unwrap_err();
+        // Without `/proc`, only the `uname` command could describe the system.
+        let info = unix::get_info_from_uname(Path::new("/nonexistent"), &Config::default(), &mut Vec::new());
+        set_enabled(true);
+        assert_eq!(io::ErrorKind::Unsupported, error.kind());
+        assert_eq!(None, info);
+    }
+}
@@ -0,0 +1,112 @@
+//! This module defines the `DistroFamily` enum used to group related operating systems together.
+
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Family of related operating systems, e.g. all Debian derivatives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DistroFamily {
+    /// Debian and its derivatives (Ubuntu, Linux Mint, Raspberry Pi OS, ...).
+    Debian,
+    /// Red Hat Enterprise Linux and related distributions (Fedora, CentOS, Rocky, Amazon Linux, ...).
+    RedHat,
+    /// SUSE Linux Enterprise and openSUSE.
+    Suse,
+    /// Arch Linux and its derivatives (Manjaro, EndeavourOS, ...).
+    Arch,
+    /// Alpine Linux and its derivatives.
+    Alpine,
+    /// Gentoo and its derivatives (Funtoo, Calculate, ...).
+    Gentoo,
+    /// Microsoft Windows.
+    Windows,
+    /// Apple macOS.
+    MacOS,
+    /// Any operating system that doesn't belong to a known family.
+    Other,
+}
+
+impl DistroFamily {
+    /// Returns the family the given operating system ID belongs to.
+    ///
+    /// The ID is compared case-insensitively, ignoring surrounding whitespace.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::DistroFamily;
+    /// assert_eq!(DistroFamily::of("ubuntu"), DistroFamily::Debian);
+    /// assert_eq!(DistroFamily::of("Rocky"), DistroFamily::RedHat);
+    /// assert_eq!(DistroFamily::of("plan9"), DistroFamily::Other);
+    /// ```
+    pub fn of(id: &str) -> Self {
+        match id.trim().to_ascii_lowercase().as_str() {
+            "debian" | "ubuntu" | "linuxmint" | "raspbian" | "pop" | "elementary" | "kali"
+            | "zorin" | "devuan" | "deepin" | "mx" | "neon" | "parrot" => Self::Debian,
+            "rhel" | "centos" | "fedora" | "rocky" | "almalinux" | "ol" | "amzn" | "scientific"
+            | "eurolinux" | "cloudlinux" | "virtuozzo" | "mariner" | "azurelinux" | "nobara" => Self::RedHat,
+            "sles" | "sles_sap" | "sles_hpc" | "sled" | "sle-micro" | "opensuse" | "opensuse-leap"
+            | "opensuse-tumbleweed" | "opensuse-microos" | "opensuse-slowroll" => Self::Suse,
+            "arch" | "archarm" | "manjaro" | "endeavouros" | "garuda" | "artix" => Self::Arch,
+            "alpine" | "postmarketos" => Self::Alpine,
+            "gentoo" | "funtoo" | "calculate" => Self::Gentoo,
+            "windows" => Self::Windows,
+            "macos" => Self::MacOS,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl Display for DistroFamily {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Self::Debian => "Debian",
+            Self::RedHat => "Red Hat",
+            Self::Suse => "SUSE",
+            Self::Arch => "Arch",
+            Self::Alpine => "Alpine",
+            Self::Gentoo => "Gentoo",
+            Self::Windows => "Windows",
+            Self::MacOS => "macOS",
+            Self::Other => "Other",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn of() {
+        let data = [
+            ("debian", DistroFamily::Debian),
+            ("ubuntu", DistroFamily::Debian),
+            (" Ubuntu ", DistroFamily::Debian),
+            ("centos", DistroFamily::RedHat),
+            ("amzn", DistroFamily::RedHat),
+            ("opensuse-leap", DistroFamily::Suse),
+            ("manjaro", DistroFamily::Arch),
+            ("alpine", DistroFamily::Alpine),
+            ("gentoo", DistroFamily::Gentoo),
+            ("windows", DistroFamily::Windows),
+            ("macos", DistroFamily::MacOS),
+            ("", DistroFamily::Other),
+            ("Unknown", DistroFamily::Other),
+        ];
+
+        for (id, expected) in &data {
+            assert_eq!(*expected, DistroFamily::of(id));
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("Red Hat", DistroFamily::RedHat.to_string());
+        assert_eq!("macOS", DistroFamily::MacOS.to_string());
+    }
+}
@@ -0,0 +1,115 @@
+//! This module defines the `FieldName` enum naming the fields of `OSInfo`, e.g. to record which
+//! source each field was detected from, and the `FieldChange` struct describing how a field
+//! differs between two `OSInfo` values.
+
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A field of `OSInfo`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum FieldName {
+    /// The operating system ID, see `OSInfo::id`.
+    Id,
+    /// The name, see `OSInfo::name`.
+    Name,
+    /// The version, see `OSInfo::version`.
+    Version,
+    /// The variant, see `OSInfo::variant`.
+    Variant,
+    /// The edition, see `OSInfo::edition`.
+    Edition,
+    /// The codename, see `OSInfo::codename`.
+    Codename,
+    /// Whether the release is a long-term support one, see `OSInfo::is_lts`.
+    Lts,
+    /// The Windows product SKU, see `OSInfo::product_sku`.
+    ProductSku,
+    /// The name as displayed to the user, see `OSInfo::localized_name`.
+    LocalizedName,
+}
+
+impl FieldName {
+    /// All the fields, in declaration order.
+    pub const ALL: [FieldName; 9] = [
+        Self::Id,
+        Self::Name,
+        Self::Version,
+        Self::Variant,
+        Self::Edition,
+        Self::Codename,
+        Self::Lts,
+        Self::ProductSku,
+        Self::LocalizedName,
+    ];
+
+    /// Returns the lowercase name of the field, e.g. "version" or "product_sku".
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Id => "id",
+            Self::Name => "name",
+            Self::Version => "version",
+            Self::Variant => "variant",
+            Self::Edition => "edition",
+            Self::Codename => "codename",
+            Self::Lts => "lts",
+            Self::ProductSku => "product_sku",
+            Self::LocalizedName => "localized_name",
+        }
+    }
+}
+
+impl Display for FieldName {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A field that differs between two `OSInfo` values, see `OSInfo::diff`.
+///
+/// The values are formatted like `OSInfo::field_value`, e.g. "22.4.0.0" for the version, and
+/// are `None` if the field isn't set.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FieldChange {
+    /// The field that changed.
+    pub field: FieldName,
+    /// The previous value.
+    pub old: Option<String>,
+    /// The new value.
+    pub new: Option<String>,
+}
+
+impl Display for FieldChange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match (&self.old, &self.new) {
+            (Some(old), Some(new)) => write!(f, "{} changed from {old} to {new}", self.field),
+            (None, Some(new)) => write!(f, "{} set to {new}", self.field),
+            (Some(old), None) => write!(f, "{} {old} removed", self.field),
+            (None, None) => write!(f, "{} unchanged", self.field),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn change_display() {
+        let change = |old: Option<&str>, new: Option<&str>| FieldChange {
+            field: FieldName::Codename,
+            old: old.map(String::from),
+            new: new.map(String::from),
+        };
+        assert_eq!("codename changed from jammy to noble", change(Some("jammy"), Some("noble")).to_string());
+        assert_eq!("codename set to noble", change(None, Some("noble")).to_string());
+        assert_eq!("codename jammy removed", change(Some("jammy"), None).to_string());
+    }
+}
@@ -0,0 +1,29 @@
+//! This module defines the `Firmware` struct describing the system firmware the operating system
+//! was booted by.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// System firmware information.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Firmware {
+    /// Type of the firmware the system booted via, or `None` if it couldn't be determined.
+    pub firmware_type: Option<FirmwareType>,
+    /// Firmware vendor, e.g. "American Megatrends Inc." or "Apple".
+    pub vendor: Option<String>,
+    /// Firmware version as reported by the vendor, e.g. "1.17.0" or "10151.101.3".
+    pub version: Option<String>,
+}
+
+/// Type of the system firmware.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum FirmwareType {
+    /// Unified Extensible Firmware Interface (including Intel Macs).
+    Uefi,
+    /// Legacy BIOS, or UEFI in its compatibility (CSM) mode.
+    Bios,
+    /// Apple's iBoot (Apple Silicon Macs).
+    Iboot,
+}
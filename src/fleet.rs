@@ -0,0 +1,289 @@
+//! Helpers for aggregating many `OSInfo` records, e.g. the inventory of a fleet of machines.
+//!
+//! All helpers normalize records the same way (see `Release::of`), so services computing the
+//! common aggregations don't have to re-derive the normalization rules.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{DistroFamily, OSInfo, Version, consts};
+
+/// A normalized operating system release, used as a grouping key.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Release {
+    /// Lowercase operating system ID.
+    pub id: String,
+    /// Release version. See `Release::of` for the normalization rules.
+    pub version: Version,
+}
+
+impl Release {
+    /// Constructs a `Release` from the given ID and version, applying the normalization rules.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{fleet::Release, Version};
+    /// let release = Release::new("Ubuntu", Version::Semantic(22, 4, 3, 0));
+    /// assert_eq!(release.id, "ubuntu");
+    /// assert_eq!(release.version, Version::Semantic(22, 4, 0, 0));
+    /// ```
+    pub fn new(id: &str, version: Version) -> Self {
+        let id = id.trim().to_ascii_lowercase();
+        let version = match version {
+            Version::Semantic(major, minor, build, _) => match release_components(&id, major) {
+                1 => Version::Semantic(major, 0, 0, 0),
+                2 => Version::Semantic(major, minor, 0, 0),
+                _ => Version::Semantic(major, minor, build, 0),
+            },
+            Version::Rolling(_) => Version::Rolling(None),
+            version => version,
+        };
+
+        Self { id, version }
+    }
+
+    /// Returns the release the given `OSInfo` belongs to.
+    ///
+    /// - the ID is trimmed and lowercased.
+    /// - semantic versions are truncated to the components that identify a release in the
+    ///   family of the ID, so point releases and builds of the same release are grouped
+    ///   together: `major.minor.build` on Windows (10.0.19045 is Windows 10 22H2, 10.0.22631
+    ///   Windows 11 23H2), `major` for Debian (12.5 is Debian 12), Raspberry Pi OS and Devuan,
+    ///   the Red Hat family (9.3 is RHEL 9) and macOS 11 and later, and `major.minor` otherwise
+    ///   (Ubuntu 22.04, Linux Mint 21.3, Kali 2024.4, openSUSE Leap 15.5, macOS 10.15).
+    /// - all snapshots of a rolling release are considered the same release.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{fleet::Release, OSInfo, Version};
+    /// let release = Release::of(&OSInfo::with_id("debian".to_string()));
+    /// assert_eq!(release.id, "debian");
+    /// assert_eq!(release.version, Version::Unknown);
+    /// ```
+    pub fn of(info: &OSInfo) -> Self {
+        Self::new(info.id(), info.version().clone())
+    }
+}
+
+/// Returns the number of leading version components that identify a release of the operating
+/// system with the given (lowercase) ID, see `Release::of`.
+fn release_components(id: &str, major: u32) -> usize {
+    match DistroFamily::of(id) {
+        DistroFamily::Windows => 3,
+        // Other derivatives of Debian release minor versions too, e.g. Linux Mint 21.2 and 21.3.
+        DistroFamily::Debian if matches!(id, consts::DEBIAN | consts::RASPBIAN | consts::DEVUAN) => 1,
+        DistroFamily::RedHat => 1,
+        DistroFamily::MacOS if major >= 11 => 1,
+        _ => 2,
+    }
+}
+
+/// A database of releases that reached their end of life. Releases are normalized like
+/// `Release::of`, so that an entry for Debian 12 matches Debian 12.5.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EolDb {
+    releases: BTreeSet<Release>,
+}
+
+impl EolDb {
+    /// Constructs an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the release with the given ID and version as end of life.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{fleet::{EolDb, Release}, Version};
+    /// let mut db = EolDb::new();
+    /// db.insert("ubuntu", Version::Semantic(18, 4, 0, 0));
+    /// db.insert("debian", Version::Semantic(10, 0, 0, 0));
+    /// assert!(db.is_eol(&Release::new("ubuntu", Version::Semantic(18, 4, 6, 0))));
+    /// assert!(db.is_eol(&Release::new("debian", Version::Semantic(10, 13, 0, 0))));
+    /// ```
+    pub fn insert(&mut self, id: &str, version: Version) {
+        self.releases.insert(Release::new(id, version));
+    }
+
+    /// Returns `true` if the given release reached its end of life.
+    pub fn is_eol(&self, release: &Release) -> bool {
+        self.releases.contains(release)
+    }
+}
+
+impl<'a> FromIterator<(&'a str, Version)> for EolDb {
+    fn from_iter<T: IntoIterator<Item = (&'a str, Version)>>(iter: T) -> Self {
+        let mut db = Self::new();
+        for (id, version) in iter {
+            db.insert(id, version);
+        }
+        db
+    }
+}
+
+/// Groups the given records by their normalized release.
+///
+/// # Example
+/// ```
+/// use osinfo::{fleet, OSInfo};
+/// let infos = [OSInfo::with_id("ubuntu".to_string()), OSInfo::with_id("Ubuntu".to_string())];
+/// let groups = fleet::group_by_release(&infos);
+/// assert_eq!(groups.len(), 1);
+/// ```
+pub fn group_by_release<'a, I>(infos: I) -> BTreeMap<Release, Vec<&'a OSInfo>>
+where
+    I: IntoIterator<Item = &'a OSInfo>,
+{
+    let mut groups: BTreeMap<Release, Vec<&'a OSInfo>> = BTreeMap::new();
+    for info in infos {
+        groups.entry(Release::of(info)).or_default().push(info);
+    }
+    groups
+}
+
+/// Counts the given records per operating system family.
+///
+/// # Example
+/// ```
+/// use osinfo::{fleet, DistroFamily, OSInfo};
+/// let infos = [OSInfo::with_id("ubuntu".to_string()), OSInfo::with_id("debian".to_string())];
+/// let counts = fleet::count_by_family(&infos);
+/// assert_eq!(counts[&DistroFamily::Debian], 2);
+/// ```
+pub fn count_by_family<'a, I>(infos: I) -> BTreeMap<DistroFamily, usize>
+where
+    I: IntoIterator<Item = &'a OSInfo>,
+{
+    let mut counts = BTreeMap::new();
+    for info in infos {
+        *counts.entry(info.family()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Returns the records whose release reached its end of life according to `db`.
+///
+/// # Example
+/// ```
+/// use osinfo::{fleet::{self, EolDb}, OSInfo, Version};
+/// let db: EolDb = [("unknown", Version::Unknown)].into_iter().collect();
+/// let infos = [OSInfo::new_unknown()];
+/// assert_eq!(fleet::outdated(&infos, &db).len(), 1);
+/// ```
+pub fn outdated<'a, I>(infos: I, db: &EolDb) -> Vec<&'a OSInfo>
+where
+    I: IntoIterator<Item = &'a OSInfo>,
+{
+    infos
+        .into_iter()
+        .filter(|info| db.is_eol(&Release::of(info)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn info(id: &str, version: Version) -> OSInfo {
+        OSInfo {
+            version,
+            ..OSInfo::with_id(id.to_string())
+        }
+    }
+
+    #[test]
+    fn release_normalization() {
+        let data = [
+            (info("Ubuntu ", Version::Semantic(22, 4, 4, 0)), Release::new("ubuntu", Version::Semantic(22, 4, 0, 0))),
+            (info("arch", Version::Rolling(Some("20240101".to_owned()))), Release::new("arch", Version::Rolling(None))),
+            (info("nixos", Version::Custom("unstable".to_owned())), Release::new("nixos", Version::Custom("unstable".to_owned()))),
+            (info("debian", Version::Semantic(12, 5, 0, 0)), Release::new("debian", Version::Semantic(12, 0, 0, 0))),
+            (info("raspbian", Version::Semantic(12, 1, 0, 0)), Release::new("raspbian", Version::Semantic(12, 0, 0, 0))),
+            (info("linuxmint", Version::Semantic(21, 3, 0, 0)), Release::new("linuxmint", Version::Semantic(21, 3, 0, 0))),
+            (info("kali", Version::Semantic(2024, 4, 0, 0)), Release::new("kali", Version::Semantic(2024, 4, 0, 0))),
+            (info("elementary", Version::Semantic(7, 1, 0, 0)), Release::new("elementary", Version::Semantic(7, 1, 0, 0))),
+            (info("rocky", Version::Semantic(9, 3, 0, 0)), Release::new("rocky", Version::Semantic(9, 0, 0, 0))),
+            (info("sles", Version::Semantic(15, 5, 0, 0)), Release::new("sles", Version::Semantic(15, 5, 0, 0))),
+            (info("macos", Version::Semantic(14, 4, 1, 0)), Release::new("macos", Version::Semantic(14, 0, 0, 0))),
+            (info("macos", Version::Semantic(10, 15, 7, 0)), Release::new("macos", Version::Semantic(10, 15, 0, 0))),
+            (
+                info("windows", Version::Semantic(10, 0, 22631, 3296)),
+                Release::new("windows", Version::Semantic(10, 0, 22631, 0)),
+            ),
+        ];
+
+        for (info, expected) in &data {
+            assert_eq!(expected, &Release::of(info));
+        }
+    }
+
+    #[test]
+    fn group_by_release() {
+        let infos = [
+            info("ubuntu", Version::Semantic(22, 4, 1, 0)),
+            info("ubuntu", Version::Semantic(22, 4, 4, 0)),
+            info("ubuntu", Version::Semantic(24, 4, 0, 0)),
+            info("debian", Version::Semantic(12, 0, 0, 0)),
+            info("debian", Version::Semantic(12, 5, 0, 0)),
+            // Windows 10 22H2 and Windows 11 23H2 share the 10.0 version.
+            info("windows", Version::Semantic(10, 0, 19045, 4170)),
+            info("windows", Version::Semantic(10, 0, 22631, 3296)),
+            info("windows", Version::Semantic(10, 0, 22631, 3447)),
+        ];
+
+        let groups = super::group_by_release(&infos);
+        assert_eq!(5, groups.len());
+        assert_eq!(2, groups[&Release::new("ubuntu", Version::Semantic(22, 4, 0, 0))].len());
+        assert_eq!(2, groups[&Release::new("debian", Version::Semantic(12, 0, 0, 0))].len());
+        assert_eq!(1, groups[&Release::new("windows", Version::Semantic(10, 0, 19045, 0))].len());
+        assert_eq!(2, groups[&Release::new("windows", Version::Semantic(10, 0, 22631, 0))].len());
+    }
+
+    #[test]
+    fn count_by_family() {
+        let infos = [
+            info("ubuntu", Version::Unknown),
+            info("debian", Version::Unknown),
+            info("rhel", Version::Unknown),
+            info("windows", Version::Unknown),
+        ];
+
+        let counts = super::count_by_family(&infos);
+        assert_eq!(Some(&2), counts.get(&DistroFamily::Debian));
+        assert_eq!(Some(&1), counts.get(&DistroFamily::RedHat));
+        assert_eq!(Some(&1), counts.get(&DistroFamily::Windows));
+        assert_eq!(None, counts.get(&DistroFamily::Arch));
+    }
+
+    #[test]
+    fn outdated() {
+        let db: EolDb = [
+            ("ubuntu", Version::Semantic(18, 4, 0, 0)),
+            ("centos", Version::Semantic(7, 0, 0, 0)),
+            ("debian", Version::Semantic(10, 0, 0, 0)),
+            ("windows", Version::Semantic(10, 0, 19045, 0)),
+        ]
+        .into_iter()
+        .collect();
+
+        let infos = [
+            info("ubuntu", Version::Semantic(18, 4, 6, 0)),
+            info("ubuntu", Version::Semantic(22, 4, 0, 0)),
+            info("centos", Version::Semantic(7, 0, 2009, 0)),
+            info("centos", Version::Semantic(7, 9, 2009, 0)),
+            info("debian", Version::Semantic(10, 13, 0, 0)),
+            info("debian", Version::Semantic(12, 5, 0, 0)),
+            info("windows", Version::Semantic(10, 0, 19045, 4170)),
+            info("windows", Version::Semantic(10, 0, 22631, 3296)),
+        ];
+
+        // Point releases match the entry of their release.
+        let outdated = super::outdated(&infos, &db);
+        assert_eq!(vec![&infos[0], &infos[2], &infos[3], &infos[4], &infos[6]], outdated);
+    }
+}
@@ -0,0 +1,262 @@
+//! Process-wide, lazily detected `OSInfo`, for daemons that query the operating system
+//! information repeatedly and may keep running across OS upgrades.
+
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use crate::logging::info;
+
+use crate::OSInfo;
+
+type Callback = Arc<dyn Fn(&OSInfo, &OSInfo) + Send + Sync>;
+
+pub(crate) static GLOBAL: Global = Global::new();
+
+/// Returns the process-wide operating system information, detecting it on first use.
+///
+/// The returned value only changes when `refresh` detects a new id or version.
+///
+/// # Examples
+///
+/// ```
+/// let info = osinfo::global();
+/// assert_eq!(info, osinfo::global());
+/// ```
+pub fn global() -> &'static OSInfo {
+    GLOBAL.get(crate::get)
+}
+
+/// Registers a callback invoked with the previous and the new information whenever `refresh`
+/// detects that the operating system id or version changed, e.g. after a Windows feature update
+/// or an Ubuntu `do-release-upgrade`.
+///
+/// Callbacks are invoked after the new value is stored, without holding any lock, so they may
+/// call `global`, `refresh` or `subscribe_refresh` themselves.
+///
+/// # Examples
+///
+/// ```
+/// osinfo::subscribe_refresh(|previous, current| {
+///     println!("OS upgraded from {} to {}", previous.version(), current.version());
+/// });
+/// ```
+pub fn subscribe_refresh<F>(callback: F)
+where
+    F: Fn(&OSInfo, &OSInfo) + Send + Sync + 'static,
+{
+    GLOBAL.subscribe(Arc::new(callback));
+}
+
+/// Detects the operating system information again, updating the value returned by `global` and
+/// notifying the subscribers if the id or version changed, including the `version_extra` (e.g. a
+/// macOS Rapid Security Response).
+///
+/// Returns `true` if a change was detected. Concurrent refreshes are serialized, so that each
+/// change is reported once, against the value it replaced. Every detected change keeps the new
+/// value alive for the rest of the process, so that references returned by `global` stay valid;
+/// as OS upgrades are rare, this is negligible.
+///
+/// # Examples
+///
+/// ```
+/// if osinfo::refresh() {
+///     println!("OS changed to {}", osinfo::global());
+/// }
+/// ```
+pub fn refresh() -> bool {
+    GLOBAL.refresh(crate::get)
+}
+
+/// Returns `true` if `current` is a different operating system or version than `previous`,
+/// including the `version_extra`, e.g. a macOS Rapid Security Response.
+fn is_change(previous: &OSInfo, current: &OSInfo) -> bool {
+    !previous.same_release(current)
+}
+
+/// State behind `global`, `subscribe_refresh`, `refresh` and `watch`.
+pub(crate) struct Global {
+    current: RwLock<Option<&'static OSInfo>>,
+    subscribers: Mutex<Vec<Callback>>,
+    /// Held from reading the current value to storing the new one.
+    refreshing: Mutex<()>,
+}
+
+impl Global {
+    pub(crate) const fn new() -> Self {
+        Self {
+            current: RwLock::new(None),
+            subscribers: Mutex::new(Vec::new()),
+            refreshing: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn get(&self, detect: impl FnOnce() -> OSInfo) -> &'static OSInfo {
+        if let Some(info) = *self.current.read().unwrap_or_else(PoisonError::into_inner) {
+            return info;
+        }
+
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        current.get_or_insert_with(|| Box::leak(Box::new(detect())))
+    }
+
+    fn subscribe(&self, callback: Callback) {
+        self.subscribers.lock().unwrap_or_else(PoisonError::into_inner).push(callback);
+    }
+
+    fn refresh(&self, detect: impl FnOnce() -> OSInfo) -> bool {
+        self.refresh_change(detect).is_some()
+    }
+
+    /// Like `refresh`, but returns the previous and the new value if they changed.
+    pub(crate) fn refresh_change(&self, detect: impl FnOnce() -> OSInfo) -> Option<(&'static OSInfo, &'static OSInfo)> {
+        let (previous, current) = self.update(detect)?;
+
+        let subscribers = self.subscribers.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        for callback in subscribers {
+            callback(previous, current);
+        }
+        Some((previous, current))
+    }
+
+    /// Detects the information and stores it if it changed, returning the previous and the new
+    /// value. A first detection is only stored.
+    fn update(&self, detect: impl FnOnce() -> OSInfo) -> Option<(&'static OSInfo, &'static OSInfo)> {
+        let _refreshing = self.refreshing.lock().unwrap_or_else(PoisonError::into_inner);
+        let detected = detect();
+
+        let mut current = self.current.write().unwrap_or_else(PoisonError::into_inner);
+        let Some(previous) = *current else {
+            *current = Some(Box::leak(Box::new(detected)));
+            return None;
+        };
+        if !is_change(previous, &detected) {
+            return None;
+        }
+
+        info!(
+            "Operating system changed from {} {} to {} {}",
+            previous.id(),
+            previous.version(),
+            detected.id(),
+            detected.version()
+        );
+        let detected: &'static OSInfo = Box::leak(Box::new(detected));
+        *current = Some(detected);
+        Some((previous, detected))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Version;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn with_version(major: u32) -> OSInfo {
+        OSInfo::builder().id("ubuntu").version(Version::Semantic(major, 4, 0, 0)).build()
+    }
+
+    #[test]
+    fn refresh_notifies_on_change() {
+        let global = Global::new();
+        let notified = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&notified);
+        global.subscribe(Arc::new(move |previous: &OSInfo, current: &OSInfo| {
+            assert_ne!(previous, current);
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        let initial = global.get(|| with_version(22)).clone();
+        assert!(!global.refresh(|| initial.clone()));
+        assert_eq!(0, notified.load(Ordering::SeqCst));
+
+        // Fields other than id and version don't count as a change.
+        assert!(!global.refresh(|| OSInfo {
+            codename: Some(String::from("other")),
+            ..initial.clone()
+        }));
+        assert_eq!(0, notified.load(Ordering::SeqCst));
+
+        assert!(global.refresh(|| with_version(24)));
+        assert_eq!(1, notified.load(Ordering::SeqCst));
+        assert_eq!(&with_version(24), global.get(|| unreachable!()));
+    }
+
+    #[test]
+    fn version_extra_change() {
+        let global = Global::new();
+        let ventura = || OSInfo::builder().id("macos").version(Version::Semantic(13, 3, 1, 0));
+
+        global.get(|| ventura().build());
+        assert!(global.refresh(|| ventura().version_extra("(a)").build()));
+        assert_eq!(Some("(a)"), global.get(|| unreachable!()).version_extra());
+    }
+
+    #[test]
+    fn first_refresh() {
+        let global = Global::new();
+        assert!(!global.refresh(|| with_version(22)));
+        assert_eq!(&with_version(22), global.get(|| unreachable!()));
+    }
+
+    #[test]
+    fn reentrant_callbacks() {
+        static GLOBAL: Global = Global::new();
+        static NOTIFIED: AtomicUsize = AtomicUsize::new(0);
+
+        GLOBAL.get(|| with_version(22));
+        GLOBAL.subscribe(Arc::new(|_: &OSInfo, current: &OSInfo| {
+            // Neither deadlocks, and the value is already updated.
+            assert_eq!(current, GLOBAL.get(|| unreachable!()));
+            assert!(!GLOBAL.refresh(|| current.clone()));
+            GLOBAL.subscribe(Arc::new(|_: &OSInfo, _: &OSInfo| {
+                NOTIFIED.fetch_add(1, Ordering::SeqCst);
+            }));
+        }));
+
+        assert!(GLOBAL.refresh(|| with_version(24)));
+        assert_eq!(0, NOTIFIED.load(Ordering::SeqCst));
+        assert!(GLOBAL.refresh(|| with_version(26)));
+        assert_eq!(1, NOTIFIED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn panicking_callback() {
+        let global = Global::new();
+        global.get(|| with_version(22));
+        global.subscribe(Arc::new(|_: &OSInfo, _: &OSInfo| panic!("callback failed")));
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| global.refresh(|| with_version(24))));
+        assert!(result.is_err());
+        // The subscribers and the refresh aren't poisoned.
+        assert!(!global.subscribers.is_poisoned());
+        assert!(!global.refreshing.is_poisoned());
+        assert_eq!(&with_version(24), global.get(|| unreachable!()));
+    }
+
+    #[test]
+    fn concurrent_refreshes() {
+        let global = Arc::new(Global::new());
+        global.get(|| with_version(22));
+        let notifications = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&notifications);
+        global.subscribe(Arc::new(move |previous: &OSInfo, current: &OSInfo| {
+            received.lock().unwrap().push((previous.version().clone(), current.version().clone()));
+        }));
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let global = Arc::clone(&global);
+                std::thread::spawn(move || global.refresh(|| with_version(24)))
+            })
+            .collect();
+        let changed = threads.into_iter().map(|thread| thread.join().unwrap()).filter(|&changed| changed).count();
+
+        // Only one refresh saw the change, against the value it replaced.
+        assert_eq!(1, changed);
+        assert_eq!(
+            vec![(Version::Semantic(22, 4, 0, 0), Version::Semantic(24, 4, 0, 0))],
+            *notifications.lock().unwrap()
+        );
+    }
+}
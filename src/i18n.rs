@@ -0,0 +1,114 @@
+//! Translations of operating system names, see `OSInfo::display_name_in`.
+//!
+//! Only the parts of names that are translated by the vendor are covered: Linux distributions,
+//! macOS and Windows Server keep their names in every language, and so do Windows client
+//! editions in most languages (e.g. "Windows 11 Pro" in German or Japanese).
+
+// spell-checker:disable
+
+use alloc::{format, string::String};
+
+/// Windows client editions, as they end the locale-neutral name, and their translations by
+/// language: the primary language subtag, or "zh-Hans" / "zh-Hant" for Chinese.
+static WINDOWS_EDITIONS: [(&str, &str, &str); 36] = [
+    ("Home", "fr", "Famille"),
+    ("Home", "ru", "Домашняя"),
+    ("Home", "zh-Hans", "家庭版"),
+    ("Home", "zh-Hant", "家用版"),
+    ("Home Single Language", "fr", "Famille Unilingue"),
+    ("Home Single Language", "ru", "Домашняя для одного языка"),
+    ("Home Single Language", "zh-Hans", "家庭单语言版"),
+    ("Home Single Language", "zh-Hant", "家用單一語言版"),
+    ("Home China", "zh-Hans", "家庭中文版"),
+    ("Home China", "zh-Hant", "家用中文版"),
+    ("Pro", "fr", "Professionnel"),
+    ("Pro", "zh-Hans", "专业版"),
+    ("Pro", "zh-Hant", "專業版"),
+    ("Pro for Workstations", "fr", "Professionnel pour les Stations de travail"),
+    ("Pro for Workstations", "ru", "Pro для рабочих станций"),
+    ("Pro for Workstations", "zh-Hans", "专业工作站版"),
+    ("Pro for Workstations", "zh-Hant", "專業工作站版"),
+    ("Pro Education", "fr", "Professionnel Éducation"),
+    ("Pro Education", "ru", "Pro для образовательных учреждений"),
+    ("Pro Education", "zh-Hans", "专业教育版"),
+    ("Pro Education", "zh-Hant", "專業教育版"),
+    ("Education", "fr", "Éducation"),
+    ("Education", "ru", "для образовательных учреждений"),
+    ("Education", "zh-Hans", "教育版"),
+    ("Education", "zh-Hant", "教育版"),
+    ("Enterprise", "fr", "Entreprise"),
+    ("Enterprise", "ru", "Корпоративная"),
+    ("Enterprise", "zh-Hans", "企业版"),
+    ("Enterprise", "zh-Hant", "企業版"),
+    ("Enterprise LTSC", "fr", "Entreprise LTSC"),
+    ("Enterprise LTSC", "ru", "Корпоративная LTSC"),
+    ("Enterprise LTSC", "zh-Hans", "企业版 LTSC"),
+    ("Enterprise LTSC", "zh-Hant", "企業版 LTSC"),
+    ("Enterprise multi-session", "fr", "Entreprise multisession"),
+    ("Enterprise multi-session", "zh-Hans", "企业版多会话"),
+    ("Enterprise multi-session", "zh-Hant", "企業版多工作階段"),
+];
+
+/// Returns the translation of the locale-neutral `name` in the language of the BCP 47 tag
+/// `language` (e.g. "fr", "fr-CA" or "zh_TW"), or `None` if it isn't translated.
+pub(crate) fn translate(name: &str, language: &str) -> Option<String> {
+    let language = language_key(language);
+    let product = name.strip_prefix("Windows ")?;
+
+    // The longest edition wins, e.g. "Pro Education" over "Education".
+    WINDOWS_EDITIONS
+        .iter()
+        .filter(|&&(edition, lang, _)| {
+            lang.eq_ignore_ascii_case(language)
+                && product.len() > edition.len()
+                && product.ends_with(edition)
+                && product[..product.len() - edition.len()].ends_with(' ')
+        })
+        .max_by_key(|&&(edition, ..)| edition.len())
+        .map(|&(edition, _, translation)| format!("Windows {}{translation}", &product[..product.len() - edition.len()]))
+}
+
+/// Returns the key of the language tag in the tables: the primary subtag, except for Chinese,
+/// whose script is derived from the region if not given.
+fn language_key(tag: &str) -> &str {
+    let tag = tag.trim();
+    let primary = tag.split(['-', '_']).next().unwrap_or_default();
+    if !primary.eq_ignore_ascii_case("zh") {
+        return primary;
+    }
+
+    let traditional = tag
+        .split(['-', '_'])
+        .skip(1)
+        .any(|subtag| ["hant", "tw", "hk", "mo"].iter().any(|t| subtag.eq_ignore_ascii_case(t)));
+    if traditional { "zh-Hant" } else { "zh-Hans" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn windows_editions() {
+        let data = [
+            ("Windows 11 Home", "fr", Some("Windows 11 Famille")),
+            ("Windows 11 Home Single Language", "fr-CA", Some("Windows 11 Famille Unilingue")),
+            ("Windows 10 Pro Education", "fr_FR", Some("Windows 10 Professionnel Éducation")),
+            ("Windows 10 Enterprise LTSC", "ru", Some("Windows 10 Корпоративная LTSC")),
+            ("Windows 11 Pro", "zh-CN", Some("Windows 11 专业版")),
+            ("Windows 11 Pro", "FR", Some("Windows 11 Professionnel")),
+            ("Windows 11 Pro", "zh-TW", Some("Windows 11 專業版")),
+            ("Windows 11 Pro", "zh-Hant-HK", Some("Windows 11 專業版")),
+            ("Windows 11 Pro", "de", None),
+            ("Windows 11 Pro", "ru", None),
+            ("Windows Server 2022 Datacenter", "fr", None),
+            ("Windows 11 IoT Enterprise", "fr", Some("Windows 11 IoT Entreprise")),
+            ("Ubuntu", "fr", None),
+        ];
+
+        for (name, language, expected) in data {
+            assert_eq!(expected.map(String::from), translate(name, language), "{name} in {language}");
+        }
+    }
+}
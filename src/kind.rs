@@ -0,0 +1,139 @@
+//! This module defines the `OsKind` enum, the coarsest classification of operating systems.
+
+use core::fmt::{self, Display, Formatter};
+
+use crate::consts;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Kind of operating system, i.e. the platform backend that detects it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum OsKind {
+    /// A Linux distribution.
+    Linux,
+    /// Microsoft Windows, including the Windows Subsystem for Linux layer.
+    Windows,
+    /// Apple macOS.
+    MacOS,
+    /// Another operating system, e.g. a BSD, Solaris, AIX, iOS or Android, or an ID the crate
+    /// doesn't know.
+    Other,
+    /// Unknown operating system, or a layer that isn't an operating system (e.g. a hypervisor in
+    /// a host chain).
+    Unknown,
+}
+
+impl OsKind {
+    /// Returns the kind of the operating system the crate was compiled for.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OsKind;
+    /// if cfg!(target_os = "linux") {
+    ///     assert_eq!(OsKind::current(), OsKind::Linux);
+    /// }
+    /// ```
+    pub const fn current() -> Self {
+        if cfg!(target_os = "linux") {
+            Self::Linux
+        } else if cfg!(windows) {
+            Self::Windows
+        } else if cfg!(target_os = "macos") {
+            Self::MacOS
+        } else if cfg!(unix) {
+            Self::Other
+        } else {
+            Self::Unknown
+        }
+    }
+
+    /// Returns the kind of the operating system with the given ID.
+    ///
+    /// The ID is compared case-insensitively, ignoring surrounding whitespace. Only "linux" and
+    /// the Linux distributions in `consts` are `Linux`; every other ID, including the BSDs, iOS,
+    /// Android and distributions the crate doesn't know, is `Other`.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OsKind;
+    /// assert_eq!(OsKind::of("ubuntu"), OsKind::Linux);
+    /// assert_eq!(OsKind::of("windows"), OsKind::Windows);
+    /// assert_eq!(OsKind::of("freebsd"), OsKind::Other);
+    /// assert_eq!(OsKind::of("haiku"), OsKind::Other);
+    /// assert_eq!(OsKind::of("Unknown"), OsKind::Unknown);
+    /// ```
+    pub fn of(id: &str) -> Self {
+        match id.trim().to_ascii_lowercase().as_str() {
+            "windows" | "wsl" => Self::Windows,
+            "macos" => Self::MacOS,
+            "" | "unknown" | "kvm" | "qemu" | "vmware" | "virtualbox" | "hyperv" | "xen" | "nitro"
+            | "gce" | "parallels" => Self::Unknown,
+            id if consts::LINUX_IDS.contains(&id) => Self::Linux,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl Display for OsKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match *self {
+            Self::Linux => "Linux",
+            Self::Windows => "Windows",
+            Self::MacOS => "macOS",
+            Self::Other => "Other",
+            Self::Unknown => "Unknown",
+        };
+        f.write_str(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn of() {
+        let data = [
+            ("ubuntu", OsKind::Linux),
+            ("Fedora", OsKind::Linux),
+            ("chromeos", OsKind::Linux),
+            ("clear-linux-os", OsKind::Linux),
+            ("not-a-distro", OsKind::Other),
+            ("haiku", OsKind::Other),
+            ("fuchsia", OsKind::Other),
+            ("wasi", OsKind::Other),
+            (" Windows ", OsKind::Windows),
+            ("wsl", OsKind::Windows),
+            ("macos", OsKind::MacOS),
+            ("freebsd", OsKind::Other),
+            ("OpenBSD", OsKind::Other),
+            ("netbsd", OsKind::Other),
+            ("dragonfly", OsKind::Other),
+            ("solaris", OsKind::Other),
+            ("sunos", OsKind::Other),
+            ("illumos", OsKind::Other),
+            ("aix", OsKind::Other),
+            ("ios", OsKind::Other),
+            ("android", OsKind::Other),
+            ("redox", OsKind::Other),
+            ("hurd", OsKind::Other),
+            ("cygwin", OsKind::Other),
+            ("kvm", OsKind::Unknown),
+            ("Unknown", OsKind::Unknown),
+            ("", OsKind::Unknown),
+        ];
+
+        for (id, expected) in &data {
+            assert_eq!(*expected, OsKind::of(id));
+        }
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!("macOS", OsKind::MacOS.to_string());
+    }
+}
@@ -1,25 +1,119 @@
-#[cfg(target_os = "linux")]
-#[path = "linux/mod.rs"]
-mod osimp;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
-#[cfg(target_os = "macos")]
-#[path = "macos/mod.rs"]
-mod osimp;
+extern crate alloc;
 
-#[cfg(windows)]
-#[path = "windows/mod.rs"]
-mod osimp;
+// Linux detection only reads files, so it is available on every platform to inspect a mounted
+// root file system (see `get_from_root`).
+#[cfg(feature = "detect")]
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+mod linux;
 
+#[cfg(all(feature = "detect", target_os = "linux"))]
+use linux as osimp;
 
+// The uname fallback is shared by all Unix systems, and the backend of those without their own.
+#[cfg(feature = "detect")]
+#[cfg_attr(not(all(unix, not(any(target_os = "linux", target_os = "macos")))), allow(dead_code))]
+mod unix;
+
+#[cfg(all(feature = "detect", unix, not(any(target_os = "linux", target_os = "macos"))))]
+use unix as osimp;
+
+#[cfg(all(feature = "detect", target_os = "macos"))]
+pub mod macos;
+
+#[cfg(all(feature = "detect", target_os = "macos"))]
+use macos as osimp;
+
+// Windows detection reads the registry through a provider, so it is available on every platform
+// to inspect an offline hive (see `windows::get_from_hive`).
+#[cfg(feature = "detect")]
+#[cfg_attr(not(windows), allow(dead_code))]
+pub mod windows;
+
+#[cfg(all(feature = "detect", windows))]
+use windows as osimp;
+
+
+// The data model only needs `alloc`; everything else requires the `std` feature, and the
+// platform backends the `detect` feature.
 mod os_info;
+mod builder;
 mod version;
 mod matcher;
+mod family;
+mod kind;
+mod field;
+pub mod lookup;
+pub mod consts;
+mod sha256;
+#[cfg(feature = "i18n")]
+mod i18n;
+#[cfg(feature = "std")]
+mod logging;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "detect"), allow(dead_code))]
+mod report;
+#[cfg(feature = "std")]
+#[cfg_attr(not(feature = "detect"), allow(dead_code))]
+mod diagnostic;
+#[cfg(feature = "std")]
+mod security;
+#[cfg(feature = "std")]
+mod firmware;
+#[cfg(feature = "std")]
+mod deployment;
+#[cfg(feature = "std")]
+mod libc;
+#[cfg(feature = "std")]
+mod session;
+#[cfg(feature = "std")]
+mod network;
+#[cfg(feature = "std")]
+mod display;
+#[cfg(feature = "std")]
+mod ci;
+#[cfg(feature = "std")]
+mod sandbox;
+#[cfg(feature = "std")]
+mod cgroups;
+#[cfg(feature = "detect")]
+mod global;
+#[cfg(feature = "detect")]
+mod watch;
+#[cfg(feature = "detect")]
+mod exec;
+#[cfg(feature = "detect")]
+mod config;
+#[cfg(feature = "std")]
+pub mod fleet;
+#[cfg(feature = "std")]
+pub mod boot;
+#[cfg(feature = "os_info-compat")]
+mod compat;
+#[cfg(all(feature = "json", feature = "detect"))]
+mod bundle;
+
+pub use crate::{os_info::OSInfo, builder::OSInfoBuilder, version::{Version, VersionParseError, RollingDate}, matcher::{Matcher, CombinedMatcher}, family::DistroFamily, kind::OsKind, field::{FieldName, FieldChange}};
+
+#[cfg(feature = "std")]
+pub use crate::{
+    report::{Report, Inconsistency}, diagnostic::Diagnostic, security::{SecurityProfile, SecurityProcessor, DiskEncryption},
+    firmware::{Firmware, FirmwareType}, deployment::{Deployment, DeploymentKind},
+    libc::{LibcInfo, LibcKind}, session::{Session, SessionKind},
+    network::NetworkEnv, display::{DisplayServer, DisplayServerKind}, ci::{CiEnvironment, CiProvider},
+    sandbox::{Sandbox, SandboxKind}, cgroups::{Cgroups, CgroupVersion, CpuQuota},
+};
 
-pub use crate::{os_info::OSInfo, version::Version, matcher::Matcher};
+#[cfg(all(feature = "json", feature = "detect"))]
+pub use crate::bundle::{Bundle, BundleSource};
+
+#[cfg(feature = "detect")]
+pub use crate::{global::{global, subscribe_refresh, refresh}, watch::{watch, Watcher, WatchEvent}, config::{Config, ExtraDistro, HostOsRelease}};
 
 /// Returns information about the current operating system (id, name, version, variant, edition, codename).
 /// 
-/// - codename on Windows will be display version string e.g 22H2, 23H1 etc and on Linux it will be the codename of the distribution.
+/// - codename on Windows will be display version string e.g 22H2, 23H1 etc (prefixed with the server release on Windows Server, e.g. Server 2022 21H2) and on Linux it will be the codename of the distribution.
 ///
 /// - variant will be server / client 
 ///
@@ -34,13 +128,583 @@ pub use crate::{os_info::OSInfo, version::Version, matcher::Matcher};
 /// println!("OS information: {info}");
 ///
 /// // Print information separately:
-/// println!("ID: {}", info.get_id());
-/// println!("Name: {}", info.get_name());
-/// println!("Version: {}", info.get_version());
-/// println!("Variant: {}", info.get_variant());
-/// println!("Edition: {}", info.get_edition());
-/// println!("Codename: {}", info.get_codename());
+/// println!("ID: {}", info.id());
+/// println!("Name: {}", info.name());
+/// println!("Version: {}", info.version());
+/// println!("Variant: {}", info.variant().unwrap_or_default());
+/// println!("Edition: {}", info.edition().unwrap_or_default());
+/// println!("Codename: {}", info.codename().unwrap_or_default());
 /// ```
+#[cfg(feature = "detect")]
 pub fn get() -> OSInfo {
-    osimp::get_info()
+    osimp::get_info(&Config::default(), &mut Vec::new())
+}
+
+/// Returns information about the current operating system, detected according to `config`,
+/// e.g. without running external commands or from a mounted root file system.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::Config;
+///
+/// let config = Config {
+///     follow_lsb_release: false,
+///     run_external_commands: false,
+///     ..Default::default()
+/// };
+/// let info = osinfo::get_with(&config);
+/// println!("OS information: {info}");
+/// ```
+#[cfg(feature = "detect")]
+pub fn get_with(config: &Config) -> OSInfo {
+    match config.custom_root {
+        Some(ref root) => linux::get_info_under(root, config, &mut Vec::new()),
+        None => osimp::get_info(config, &mut Vec::new()),
+    }
+}
+
+/// Returns information about the Linux distribution installed under `root`, e.g. a mounted disk
+/// image or a container root file system, or `None` if it couldn't be identified.
+///
+/// Only the release files under `root` are consulted, so this works on every platform.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if let Some(info) = osinfo::get_from_root("/mnt/image") {
+///     println!("Image OS information: {info}");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn get_from_root(root: impl AsRef<std::path::Path>) -> Option<OSInfo> {
+    linux::get_info_from(root.as_ref(), &Config::default(), &mut Vec::new())
+}
+
+/// Default maximum size in bytes of the release files read on Linux, see `set_max_file_size`.
+#[cfg(feature = "detect")]
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 64 * 1024;
+
+/// Sets the maximum size in bytes of the release files read on Linux (`/etc/os-release` and the
+/// like, also by `get_from_root`). Larger files are ignored rather than read into memory, which
+/// guards against corrupt or hostile root file systems. Defaults to `DEFAULT_MAX_FILE_SIZE`.
+///
+/// This is the process-wide default: a `Config::max_file_size` passed to `get_with` takes
+/// precedence over it.
+///
+/// Release files must also be regular files; FIFOs and devices are ignored, and at most 8
+/// symbolic links are followed.
+///
+/// # Examples
+///
+/// ```
+/// osinfo::set_max_file_size(16 * 1024);
+/// let info = osinfo::get();
+/// # osinfo::set_max_file_size(osinfo::DEFAULT_MAX_FILE_SIZE);
+/// ```
+#[cfg(feature = "detect")]
+pub fn set_max_file_size(bytes: u64) {
+    linux::set_max_file_size(bytes);
+}
+
+/// Default timeout of the external commands run during detection, see `set_command_timeout`.
+#[cfg(feature = "detect")]
+pub const DEFAULT_COMMAND_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Default maximum size in bytes of the output of the external commands run during detection,
+/// see `set_max_command_output`.
+#[cfg(feature = "detect")]
+pub const DEFAULT_MAX_COMMAND_OUTPUT: u64 = 1024 * 1024;
+
+/// Sets the timeout of the external commands some detections fall back to, e.g. `ldd` on Linux
+/// or `sysctl` on macOS. Commands running longer are killed and the detection continues without
+/// them. Defaults to `DEFAULT_COMMAND_TIMEOUT`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// osinfo::set_command_timeout(Duration::from_millis(500));
+/// let libc = osinfo::libc();
+/// # osinfo::set_command_timeout(osinfo::DEFAULT_COMMAND_TIMEOUT);
+/// ```
+#[cfg(feature = "detect")]
+pub fn set_command_timeout(timeout: std::time::Duration) {
+    exec::set_timeout(timeout);
+}
+
+/// Sets the maximum size in bytes of the standard output and error of the external commands
+/// run during detection. Commands printing more are killed and their output is ignored.
+/// Defaults to `DEFAULT_MAX_COMMAND_OUTPUT`.
+///
+/// # Examples
+///
+/// ```
+/// osinfo::set_max_command_output(64 * 1024);
+/// let network = osinfo::network_env();
+/// # osinfo::set_max_command_output(osinfo::DEFAULT_MAX_COMMAND_OUTPUT);
+/// ```
+#[cfg(feature = "detect")]
+pub fn set_max_command_output(bytes: u64) {
+    exec::set_max_output(bytes);
+}
+
+/// Sets whether detection may run external commands at all, e.g. to forbid subprocesses in a
+/// sandboxed or setuid program. Unlike `Config::run_external_commands`, this applies to every
+/// function of the crate, which then return less information. Defaults to `true`.
+///
+/// Commands only run if both allow them: disabling them here wins over a `Config` with
+/// `run_external_commands: true`, while `run_external_commands: false` only applies to the
+/// `get_with` call it is passed to.
+///
+/// # Examples
+///
+/// ```
+/// osinfo::set_external_commands_enabled(false);
+/// let info = osinfo::get();
+/// # osinfo::set_external_commands_enabled(true);
+/// ```
+#[cfg(feature = "detect")]
+pub fn set_external_commands_enabled(enabled: bool) {
+    exec::set_enabled(enabled);
+}
+
+/// Returns information about the current operating system, together with the diagnostics
+/// recorded during detection: which sources (files, registry keys) were tried, why each one
+/// failed or was ignored, and which fields couldn't be found.
+///
+/// Use this to debug "Unknown" results reported from machines you don't have access to.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// let (info, diagnostics) = osinfo::get_with_diagnostics();
+/// println!("OS information: {info}");
+/// for diagnostic in &diagnostics {
+///     println!("{diagnostic}");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn get_with_diagnostics() -> (OSInfo, Vec<Diagnostic>) {
+    let mut diagnostics = Vec::new();
+    let info = osimp::get_info(&Config::default(), &mut diagnostics);
+    (info, diagnostics)
+}
+
+/// Returns information about the current operating system without blocking the async executor.
+///
+/// Detection reads files (which may live on slow network mounts) or the registry, so it is
+/// offloaded to tokio's blocking thread pool. Requires the `tokio` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn main() {
+/// let info = osinfo::get_async().await;
+/// println!("OS information: {info}");
+/// # }
+/// ```
+#[cfg(feature = "tokio")]
+pub async fn get_async() -> OSInfo {
+    match tokio::task::spawn_blocking(get).await {
+        Ok(info) => info,
+        Err(e) => {
+            crate::logging::error!("OS detection task failed: {}", e);
+            OSInfo::new_unknown()
+        }
+    }
+}
+
+/// Returns the operating system information together with the raw facts it was cross-checked
+/// against, such as the running kernel version.
+///
+/// When the sources disagree beyond the expected bounds (e.g. a RHEL 9 userland on a 3.10 kernel,
+/// which indicates a container on an old host), the conflicting values are reported in
+/// `Report::inconsistencies`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// let report = osinfo::report();
+/// for inconsistency in &report.inconsistencies {
+///     println!("Inconsistent detection: {inconsistency:?}");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn report() -> Report {
+    Report::new(get(), osimp::get_kernel_version())
+}
+
+/// Returns the security profile of the system, such as the presence and version of a TPM
+/// (or the Secure Enclave on macOS) and the encryption of the system volume (BitLocker,
+/// FileVault or LUKS).
+///
+/// All checks are read-only and don't require elevated privileges.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// let profile = osinfo::security_profile();
+/// println!("Security processor: {:?}", profile.security_processor);
+/// println!("Disk encryption: {:?}", profile.disk_encryption);
+/// ```
+#[cfg(feature = "detect")]
+pub fn security_profile() -> SecurityProfile {
+    osimp::get_security_profile()
+}
+
+/// Returns the type (UEFI or legacy BIOS), vendor and version of the system firmware.
+///
+/// The information comes from sysfs (`/sys/firmware/efi` and the DMI tables) on Linux,
+/// `GetFirmwareType` and the registry on Windows, and the system firmware (boot ROM) version on
+/// macOS.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// let firmware = osinfo::firmware();
+/// println!("Firmware: {:?} {:?} {:?}", firmware.firmware_type, firmware.vendor, firmware.version);
+/// ```
+#[cfg(feature = "detect")]
+pub fn firmware() -> Firmware {
+    osimp::get_firmware()
+}
+
+/// Returns whether the operating system runs in FIPS 140 mode, i.e. only FIPS-approved
+/// cryptographic algorithms may be used.
+///
+/// This reads `/proc/sys/crypto/fips_enabled` on Linux and the `FipsAlgorithmPolicy` policy on
+/// Windows. Returns `None` if it couldn't be determined, and on macOS, which has no system-wide
+/// FIPS mode.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if osinfo::fips_enabled() == Some(true) {
+///     println!("Using FIPS-approved algorithms only");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn fips_enabled() -> Option<bool> {
+    osimp::fips_enabled()
+}
+
+/// Returns the identity of the booted deployment of image-based operating systems: the system
+/// profile generation on NixOS and Guix System, the ostree commit and refspec on Fedora
+/// Silverblue / Kinoite, Fedora CoreOS and other ostree based distributions, and the update
+/// channel on Flatcar Container Linux.
+///
+/// Returns `None` for traditionally installed (package-managed) systems, and on Windows and
+/// macOS.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if let Some(deployment) = osinfo::deployment() {
+///     println!("{:?} deployment {:?}", deployment.kind, deployment.commit);
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn deployment() -> Option<Deployment> {
+    osimp::get_deployment()
+}
+
+/// Returns the Flatpak or Snap sandbox the process runs in, or `None` if it isn't sandboxed.
+///
+/// Inside a sandbox, `get()` describes the runtime the application was built against (e.g. the
+/// Freedesktop SDK) rather than the host. The host operating system is read from the os-release
+/// file the sandbox exposes: `/run/host/os-release` for Flatpak and
+/// `/var/lib/snapd/hostfs/etc/os-release` for strictly confined snaps.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if let Some(sandbox) = osinfo::sandbox() {
+///     let host = sandbox.host.map(|host| host.to_string()).unwrap_or_default();
+///     println!("{} app {:?} on {host}", sandbox.kind, sandbox.app_id);
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn sandbox() -> Option<Sandbox> {
+    osimp::get_sandbox(&Config::default())
+}
+
+/// Returns the Flatpak or Snap sandbox the process runs in like `sandbox`, reading the host
+/// os-release within the `max_file_size` of `config`. The host is left out if its
+/// `host_os_release` is `HostOsRelease::Ignore`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::{Config, HostOsRelease};
+///
+/// let config = Config {
+///     host_os_release: HostOsRelease::Ignore,
+///     ..Default::default()
+/// };
+/// if let Some(sandbox) = osinfo::sandbox_with(&config) {
+///     assert_eq!(sandbox.host, None);
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn sandbox_with(config: &Config) -> Option<Sandbox> {
+    osimp::get_sandbox(config)
+}
+
+/// Returns the control groups of the process and the memory and CPU limits they impose, or `None`
+/// if they can't be read, e.g. on operating systems other than Linux.
+///
+/// The limits apply to the process wherever they are set, e.g. by the container runtime or by
+/// systemd for a service, so a report of the environment can tell that the process only has
+/// 512 MiB of memory on the machine it detected.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if let Some(cgroups) = osinfo::cgroups() {
+///     let memory = cgroups.memory_limit.map(|limit| format!("{} MiB", limit >> 20));
+///     let cpus = cgroups.cpu_quota.map(|quota| quota.cpus());
+///     println!("{} {}: memory {memory:?}, CPUs {cpus:?}", cgroups.version, cgroups.path);
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn cgroups() -> Option<Cgroups> {
+    osimp::get_cgroups()
+}
+
+/// Returns the C standard library of the operating system and its version, e.g. to pick the
+/// glibc or musl build of a binary artifact.
+///
+/// On Linux the version of glibc comes from `gnu_get_libc_version` when the calling process is
+/// linked against it, and otherwise from `ldd --version` (or the installed dynamic loaders).
+/// Windows uses the Microsoft C runtime; returns `None` on macOS, whose C library is part of
+/// libSystem.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::LibcKind;
+///
+/// if let Some(libc) = osinfo::libc() {
+///     let target = if libc.kind == LibcKind::Musl { "x86_64-unknown-linux-musl" } else { "x86_64-unknown-linux-gnu" };
+///     println!("Downloading the {target} build (libc {})", libc.version);
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn libc() -> Option<LibcInfo> {
+    osimp::get_libc()
+}
+
+/// Returns whether the operating system must be rebooted to complete the installation of
+/// updates, e.g. for patch compliance dashboards. Returns `None` if it couldn't be determined.
+///
+/// - Linux: `/run/reboot-required` on Debian and Ubuntu, a running kernel whose modules were
+///   removed by an update, or `needs-restarting -r` on Red Hat based distributions.
+/// - Windows: the `RebootRequired` / `RebootPending` keys of Windows Update and Component Based
+///   Servicing, and pending file rename operations.
+/// - macOS: available updates requiring a restart according to `softwareupdate --list`, which
+///   may contact the update server.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if osinfo::pending_reboot() == Some(true) {
+///     println!("Reboot required to complete the installation of updates");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn pending_reboot() -> Option<bool> {
+    osimp::pending_reboot()
+}
+
+/// Returns the language the operating system was installed in as a language tag, e.g. "de-DE".
+///
+/// On Windows this is the install language from the `Nls\Language` registry key (or the system
+/// default UI language), which doesn't change when language packs are added or users switch
+/// their display language. Returns `None` on Linux and macOS, which don't record it.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if let Some(language) = osinfo::install_language() {
+///     println!("Installed in {language}");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn install_language() -> Option<String> {
+    osimp::install_language()
+}
+
+/// Returns the system-wide locale as a language tag, e.g. "en-US", independent of the locale of
+/// the current user.
+///
+/// This is the system locale ("language for non-Unicode programs") on Windows and `LANG` in
+/// `/etc/locale.conf` or `/etc/default/locale` on Linux. Returns `None` on macOS, where the
+/// locale is a per-user preference.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// println!("System locale: {:?}", osinfo::system_locale());
+/// ```
+#[cfg(feature = "detect")]
+pub fn system_locale() -> Option<String> {
+    osimp::system_locale()
+}
+
+/// Returns the session context of the current process: whether it runs in an interactive
+/// terminal, over SSH, as a service or in a desktop session, e.g. to decide whether to prompt
+/// for elevation or to use colored output.
+///
+/// SSH sessions are recognized by the environment variables OpenSSH sets. Otherwise:
+///
+/// - Linux: `XDG_SESSION_TYPE`, the display server variables and systemd's `INVOCATION_ID`.
+/// - Windows: session 0 for services and the session name for Remote Desktop.
+/// - macOS: the launchd session reported by `launchctl managername`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::SessionKind;
+///
+/// let session = osinfo::session();
+/// if session.interactive && session.kind != SessionKind::Service {
+///     println!("Running in a {:?} session", session.kind);
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn session() -> Session {
+    Session::detect(osimp::get_session_kind)
+}
+
+/// Returns whether the process runs with administrative privileges: as root (effective user ID
+/// 0) on Linux and macOS, or with an elevated token on Windows.
+///
+/// On Windows, administrators run unelevated unless started with "Run as administrator", so
+/// installers should check this rather than the group membership of the user.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if !osinfo::is_elevated() {
+///     eprintln!("Please run the installer as administrator");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn is_elevated() -> bool {
+    osimp::is_elevated()
+}
+
+/// Returns the name of the user the process runs as (the effective user on Linux and macOS), or
+/// `None` if it couldn't be determined.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// println!("Running as {}", osinfo::current_user().unwrap_or_default());
+/// ```
+#[cfg(feature = "detect")]
+pub fn current_user() -> Option<String> {
+    osimp::current_user()
+}
+
+/// Returns the proxy configuration of the system, e.g. for downloaders.
+///
+/// The `http_proxy`, `https_proxy`, `all_proxy` and `no_proxy` environment variables take
+/// precedence. Otherwise:
+///
+/// - Linux: the GNOME proxy settings read with `gsettings`.
+/// - Windows: the WinINET settings of the current user, then the machine-wide WinHTTP settings.
+/// - macOS: the SystemConfiguration settings reported by `scutil --proxy`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// let network = osinfo::network_env();
+/// if let Some(proxy) = network.https_proxy {
+///     println!("Downloading through {proxy}");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn network_env() -> NetworkEnv {
+    osimp::network_env()
+}
+
+/// Returns the display server of the current session (X11, Wayland or headless on Linux), its
+/// compositor and whether a monitor is connected, e.g. to choose between a graphical and a text
+/// interface at startup.
+///
+/// On Linux, the display server and compositor are detected from the environment of the session
+/// (`WAYLAND_DISPLAY`, `DISPLAY`, `XDG_CURRENT_DESKTOP`) and the monitors from the DRM connectors
+/// in `/sys/class/drm`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// let display = osinfo::display_server();
+/// if display.is_graphical() {
+///     println!("Starting the GUI on {:?}", display.kind);
+/// } else {
+///     println!("Starting the TUI");
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn display_server() -> DisplayServer {
+    osimp::get_display_server()
+}
+
+/// Returns the continuous integration service the process runs on (GitHub Actions, GitLab CI/CD
+/// or Azure Pipelines) with its runner image and version, or `None` outside of CI, e.g. to label
+/// build matrix results.
+///
+/// The services are recognized by the environment variables they set. The image of
+/// GitHub-hosted runners also comes from `/imagegeneration/imagedata.json`, and their runner
+/// version from the versions installed in `$HOME/runners`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo;
+///
+/// if let Some(ci) = osinfo::ci_environment() {
+///     println!("Testing {} on {}", osinfo::get(), ci.label());
+/// }
+/// ```
+#[cfg(feature = "detect")]
+pub fn ci_environment() -> Option<CiEnvironment> {
+    CiEnvironment::detect(|name| std::env::var(name).ok(), std::path::Path::new("/"))
 }
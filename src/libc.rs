@@ -0,0 +1,32 @@
+//! This module defines the `LibcInfo` struct describing the C standard library of the operating
+//! system, which determines the binaries that can run on it.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Version;
+
+/// C standard library of the operating system.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LibcInfo {
+    /// Implementation of the C standard library.
+    pub kind: LibcKind,
+    /// Version of the C standard library, e.g. 2.39 for glibc or 1.2.4 for musl. Binaries built
+    /// against glibc only run on the same or a newer version.
+    pub version: Version,
+}
+
+/// Implementation of the C standard library.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LibcKind {
+    /// The GNU C Library, used by most Linux distributions.
+    Glibc,
+    /// musl, used by Alpine Linux, Void Linux (musl) and other lightweight distributions.
+    Musl,
+    /// Bionic, Android's C library.
+    Bionic,
+    /// The Microsoft C runtime (the Universal CRT on Windows 10 and later).
+    Msvcrt,
+}
@@ -0,0 +1,124 @@
+// spell-checker:ignore armbian, raspbian
+
+use std::{fs, path::Path};
+
+use crate::logging::trace;
+
+use crate::{matcher::Matcher, FieldName, OSInfo};
+
+/// Detects Raspberry Pi OS and other single-board computer distributions (e.g. Armbian), which
+/// identify themselves as plain Debian / Ubuntu in os-release.
+///
+/// The variant is set to "embedded" and the edition to the board model.
+pub fn detect(info: &mut OSInfo, root: &Path) {
+    let model = fs::read_to_string(root.join("proc/device-tree/model"))
+        .ok()
+        .map(|model| model.trim_end_matches('\0').trim().to_string())
+        .filter(|model| !model.is_empty());
+
+    let armbian = fs::read_to_string(root.join("etc/armbian-release")).ok();
+    let raspberry_pi_os = info.id.as_deref() == Some("raspbian") || root.join("etc/rpi-issue").exists();
+
+    let name = if raspberry_pi_os {
+        Some(String::from("Raspberry Pi OS"))
+    } else if armbian.is_some() {
+        Some(String::from("Armbian"))
+    } else if model.as_deref().is_some_and(|model| model.starts_with("Raspberry Pi")) {
+        // Another distribution running on a Raspberry Pi, keep its name.
+        info.name.clone()
+    } else {
+        return;
+    };
+
+    trace!("Detected single-board computer {:?} running {:?}", model, name);
+
+    let source = |path: &str| root.join(path).display().to_string();
+    if name != info.name {
+        // Raspberry Pi OS may also be identified by its os-release ID.
+        let name_source = if raspberry_pi_os && !root.join("etc/rpi-issue").exists() {
+            info.source_of(FieldName::Id).map(String::from).unwrap_or_default()
+        } else if raspberry_pi_os {
+            source("etc/rpi-issue")
+        } else {
+            source("etc/armbian-release")
+        };
+        info.name = name;
+        info.record_source(&[FieldName::Name], &name_source);
+    }
+
+    let board_source = source(if model.is_some() { "proc/device-tree/model" } else { "etc/armbian-release" });
+    let board = armbian.and_then(|release| Matcher::KeyValue { key: "BOARD_NAME" }.find(&release));
+    info.variant = Some(String::from("embedded"));
+    info.edition = model.or(board);
+    info.record_source(&[FieldName::Variant, FieldName::Edition], &board_source);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    fn debian() -> OSInfo {
+        OSInfo {
+            name: Some(String::from("Debian GNU/Linux")),
+            variant: Some(String::from("client")),
+            ..OSInfo::with_id(String::from("debian"))
+        }
+    }
+
+    #[test]
+    fn raspberry_pi_os() {
+        let root = root_with("rpi", &[
+            ("proc/device-tree/model", "Raspberry Pi 4 Model B Rev 1.4\0"),
+            ("etc/rpi-issue", "Raspberry Pi reference 2023-12-05\n"),
+        ]);
+
+        let mut info = debian();
+        detect(&mut info, &root);
+        assert_eq!("debian", info.id());
+        assert_eq!("Raspberry Pi OS", info.name());
+        assert_eq!(Some("embedded"), info.variant());
+        assert_eq!(Some("Raspberry Pi 4 Model B Rev 1.4"), info.edition());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn armbian() {
+        let root = root_with("armbian", &[
+            ("etc/armbian-release", "BOARD=orangepi5\nBOARD_NAME=\"Orange Pi 5\"\nVERSION=24.2.1\n"),
+        ]);
+
+        let mut info = debian();
+        detect(&mut info, &root);
+        assert_eq!("Armbian", info.name());
+        assert_eq!(Some("embedded"), info.variant());
+        assert_eq!(Some("Orange Pi 5"), info.edition());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn ubuntu_on_raspberry_pi() {
+        let root = root_with("ubuntu-rpi", &[("proc/device-tree/model", "Raspberry Pi 5 Model B Rev 1.0\0")]);
+
+        let mut info = OSInfo {
+            name: Some(String::from("Ubuntu")),
+            ..OSInfo::with_id(String::from("ubuntu"))
+        };
+        detect(&mut info, &root);
+        assert_eq!("Ubuntu", info.name());
+        assert_eq!(Some("embedded"), info.variant());
+        assert_eq!(Some("Raspberry Pi 5 Model B Rev 1.0"), info.edition());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn other_arm_board() {
+        let root = root_with("arm-server", &[("proc/device-tree/model", "Ampere Altra\0")]);
+
+        let mut info = debian();
+        detect(&mut info, &root);
+        assert_eq!(debian(), info);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,99 @@
+use std::{fs, path::Path};
+
+use crate::logging::trace;
+
+use crate::boot::{BootStatus, KernelTaint, Lockdown};
+
+/// `SecureBoot` variable of the EFI global variable namespace.
+const SECURE_BOOT_VAR: &str = "sys/firmware/efi/efivars/SecureBoot-8be4df61-93ca-11d2-aa0d-00e098032b8c";
+
+pub fn get_boot_status(root: &Path) -> BootStatus {
+    BootStatus {
+        secure_boot: secure_boot(root),
+        lockdown: lockdown(root),
+        taint: taint(root),
+    }
+}
+
+/// Reads the Secure Boot state from efivarfs, like `mokutil --sb-state` does.
+fn secure_boot(root: &Path) -> Option<bool> {
+    if !root.join("sys/firmware/efi").exists() {
+        trace!("Booted via legacy BIOS");
+        return Some(false);
+    }
+
+    // The variable starts with its 4 attribute bytes, followed by the 1 byte value.
+    match fs::read(root.join(SECURE_BOOT_VAR)) {
+        Ok(data) => data.get(4).map(|&value| value == 1),
+        Err(e) => {
+            trace!("Unable to read the SecureBoot EFI variable: {:?}", e);
+            None
+        }
+    }
+}
+
+/// Parses the kernel lockdown mode, e.g. "none [integrity] confidentiality".
+fn lockdown(root: &Path) -> Option<Lockdown> {
+    let modes = fs::read_to_string(root.join("sys/kernel/security/lockdown")).ok()?;
+    let mode = modes.split_whitespace().find(|mode| mode.starts_with('['))?;
+    match mode.trim_matches(|c| c == '[' || c == ']') {
+        "none" => Some(Lockdown::Disabled),
+        "integrity" => Some(Lockdown::Integrity),
+        "confidentiality" => Some(Lockdown::Confidentiality),
+        _ => None,
+    }
+}
+
+fn taint(root: &Path) -> Option<KernelTaint> {
+    let tainted = fs::read_to_string(root.join("proc/sys/kernel/tainted")).ok()?;
+    tainted.trim().parse().ok().map(KernelTaint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn uefi_secure_boot() {
+        let root = root_with("boot-uefi", &[
+            (SECURE_BOOT_VAR, "\x06\0\0\0\x01"),
+            ("sys/kernel/security/lockdown", "none [integrity] confidentiality\n"),
+            ("proc/sys/kernel/tainted", "12289\n"),
+        ]);
+
+        let expected = BootStatus {
+            secure_boot: Some(true),
+            lockdown: Some(Lockdown::Integrity),
+            taint: Some(KernelTaint(0x3001)),
+        };
+        assert_eq!(expected, get_boot_status(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn uefi_without_secure_boot() {
+        let root = root_with("boot-uefi-off", &[
+            (SECURE_BOOT_VAR, "\x06\0\0\0\0"),
+            ("sys/kernel/security/lockdown", "[none] integrity confidentiality\n"),
+            ("proc/sys/kernel/tainted", "0\n"),
+        ]);
+
+        let expected = BootStatus {
+            secure_boot: Some(false),
+            lockdown: Some(Lockdown::Disabled),
+            taint: Some(KernelTaint(0)),
+        };
+        assert_eq!(expected, get_boot_status(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn legacy_bios() {
+        let root = root_with("boot-bios", &[("proc/sys/kernel/tainted", "0\n")]);
+        assert_eq!(Some(false), secure_boot(&root));
+        assert_eq!(None, lockdown(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,224 @@
+// spell-checker:ignore cpuacct, cfs
+
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::logging::trace;
+
+use crate::{CgroupVersion, Cgroups, CpuQuota};
+
+/// Control groups of the calling process, relative to the root.
+const PROC_CGROUP_PATH: &str = "proc/self/cgroup";
+
+/// Mount point of the cgroup hierarchies, relative to the root.
+const CGROUP_PATH: &str = "sys/fs/cgroup";
+
+/// cgroup v1 reports an unlimited memory limit as the largest page aligned counter value, e.g.
+/// 9223372036854771712 with 4 KiB pages; anything above this is treated as unlimited.
+const V1_UNLIMITED: u64 = 1 << 62;
+
+/// Reads the control groups of the process and the memory and CPU limits they impose.
+pub fn get_cgroups(root: &Path) -> Option<Cgroups> {
+    let proc_cgroup = fs::read_to_string(root.join(PROC_CGROUP_PATH)).ok()?;
+
+    // Lines are "hierarchy-ID:controller-list:cgroup-path", the unified hierarchy being "0::".
+    let mut unified = None;
+    let mut controllers = Vec::new();
+    for line in proc_cgroup.lines() {
+        let mut parts = line.splitn(3, ':');
+        let (Some(id), Some(list), Some(path)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        if id == "0" && list.is_empty() {
+            unified = Some(path);
+        } else if !list.is_empty() {
+            controllers.push((list, path));
+        }
+    }
+
+    let base = root.join(CGROUP_PATH);
+    let cgroups = match (unified, controllers.is_empty()) {
+        (Some(path), true) => {
+            let dirs = ancestors(&base, path);
+            Cgroups {
+                version: CgroupVersion::V2,
+                path: path.to_string(),
+                memory_limit: lowest(&dirs, |dir| v2_memory_limit(&read(&dir.join("memory.max"))?)),
+                cpu_quota: lowest_quota(&dirs, |dir| v2_cpu_quota(&read(&dir.join("cpu.max"))?)),
+            }
+        }
+        (_, false) => {
+            let controller = |name: &str| {
+                controllers
+                    .iter()
+                    .find(|(list, _)| list.split(',').any(|controller| controller == name))
+                    .map(|&(list, path)| (base.join(list), path))
+            };
+            let memory = controller("memory");
+            let path = memory.as_ref().map_or(controllers[0].1, |&(_, path)| path);
+            let memory_dirs = memory.map(|(mount, path)| ancestors(&mount, path)).unwrap_or_default();
+            let cpu_dirs = controller("cpu").map(|(mount, path)| ancestors(&mount, path)).unwrap_or_default();
+            Cgroups {
+                version: if unified.is_some() { CgroupVersion::Hybrid } else { CgroupVersion::V1 },
+                path: path.to_string(),
+                memory_limit: lowest(&memory_dirs, |dir| {
+                    read(&dir.join("memory.limit_in_bytes"))?.parse().ok().filter(|&limit| limit < V1_UNLIMITED)
+                }),
+                cpu_quota: lowest_quota(&cpu_dirs, |dir| {
+                    // The quota is -1 if unlimited.
+                    let quota_us = read(&dir.join("cpu.cfs_quota_us"))?.parse().ok()?;
+                    let period_us = read(&dir.join("cpu.cfs_period_us"))?.parse().ok()?;
+                    (period_us > 0).then_some(CpuQuota { quota_us, period_us })
+                }),
+            }
+        }
+        (None, true) => return None,
+    };
+
+    trace!("Found {:?}", cgroups);
+    Some(cgroups)
+}
+
+/// Returns the directories of the cgroup at `path` and its ancestors under the mount point of
+/// its hierarchy, skipping the ones that aren't visible, e.g. the host part of the path in a
+/// container without a cgroup namespace, whose own cgroup is mounted as the root.
+fn ancestors(mount: &Path, path: &str) -> Vec<PathBuf> {
+    // Only the names are kept: the path of a cgroup outside of the namespace of the process
+    // starts with "/..", which would climb out of the mount point.
+    let path: PathBuf = Path::new(path)
+        .components()
+        .filter(|component| matches!(component, Component::Normal(_)))
+        .collect();
+    path.ancestors()
+        .map(|ancestor| mount.join(ancestor))
+        .filter(|dir| dir.is_dir())
+        .collect()
+}
+
+fn read(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|content| content.trim().to_string())
+}
+
+/// Returns the lowest limit found in `dirs`.
+fn lowest(dirs: &[PathBuf], limit: impl Fn(&Path) -> Option<u64>) -> Option<u64> {
+    dirs.iter().filter_map(|dir| limit(dir)).min()
+}
+
+/// Returns the quota allowing the least CPU time found in `dirs`.
+fn lowest_quota(dirs: &[PathBuf], quota: impl Fn(&Path) -> Option<CpuQuota>) -> Option<CpuQuota> {
+    dirs.iter()
+        .filter_map(|dir| quota(dir))
+        .min_by_key(|quota| quota.quota_us as u128 * 1_000_000_000 / quota.period_us as u128)
+}
+
+/// Parses `memory.max`, "max" or the limit in bytes.
+fn v2_memory_limit(memory_max: &str) -> Option<u64> {
+    memory_max.parse().ok()
+}
+
+/// Parses `cpu.max`, the quota ("max" if unlimited) and the period, e.g. "50000 100000".
+fn v2_cpu_quota(cpu_max: &str) -> Option<CpuQuota> {
+    let (quota_us, period_us) = cpu_max.split_once(' ')?;
+    let quota_us = quota_us.parse().ok()?;
+    let period_us = period_us.trim().parse().ok().filter(|&period_us| period_us > 0)?;
+    Some(CpuQuota { quota_us, period_us })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn unified() {
+        let root = root_with("cgroups-v2", &[
+            (PROC_CGROUP_PATH, "0::/system.slice/app.service\n"),
+            ("sys/fs/cgroup/system.slice/memory.max", "1073741824\n"),
+            ("sys/fs/cgroup/system.slice/cpu.max", "max 100000\n"),
+            ("sys/fs/cgroup/system.slice/app.service/memory.max", "max\n"),
+            ("sys/fs/cgroup/system.slice/app.service/cpu.max", "50000 100000\n"),
+        ]);
+
+        let cgroups = get_cgroups(&root).unwrap();
+        assert_eq!(CgroupVersion::V2, cgroups.version);
+        assert_eq!("/system.slice/app.service", cgroups.path);
+        // The slice limits the memory of the service.
+        assert_eq!(Some(1 << 30), cgroups.memory_limit);
+        assert_eq!(Some(CpuQuota { quota_us: 50000, period_us: 100000 }), cgroups.cpu_quota);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn unified_namespace() {
+        let root = root_with("cgroups-v2-namespace", &[
+            (PROC_CGROUP_PATH, "0::/\n"),
+            ("sys/fs/cgroup/memory.max", "536870912\n"),
+            ("sys/fs/cgroup/cpu.max", "200000 100000\n"),
+        ]);
+
+        let cgroups = get_cgroups(&root).unwrap();
+        assert_eq!("/", cgroups.path);
+        assert_eq!(Some(512 << 20), cgroups.memory_limit);
+        assert_eq!(2.0, cgroups.cpu_quota.unwrap().cpus());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn outside_namespace() {
+        let root = root_with("cgroups-v2-outside", &[
+            (PROC_CGROUP_PATH, "0::/../..\n"),
+            ("sys/fs/cgroup/memory.max", "536870912\n"),
+            ("sys/memory.max", "1024\n"),
+            ("memory.max", "1024\n"),
+        ]);
+
+        let cgroups = get_cgroups(&root).unwrap();
+        assert_eq!("/../..", cgroups.path);
+        assert_eq!(Some(512 << 20), cgroups.memory_limit);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn v1_container() {
+        let root = root_with("cgroups-v1", &[
+            (
+                PROC_CGROUP_PATH,
+                "12:pids:/docker/4f2a\n5:cpu,cpuacct:/docker/4f2a\n4:memory:/docker/4f2a\n1:name=systemd:/docker/4f2a\n",
+            ),
+            ("sys/fs/cgroup/memory/memory.limit_in_bytes", "268435456\n"),
+            ("sys/fs/cgroup/cpu,cpuacct/cpu.cfs_quota_us", "-1\n"),
+            ("sys/fs/cgroup/cpu,cpuacct/cpu.cfs_period_us", "100000\n"),
+        ]);
+
+        let cgroups = get_cgroups(&root).unwrap();
+        assert_eq!(CgroupVersion::V1, cgroups.version);
+        assert_eq!("/docker/4f2a", cgroups.path);
+        assert_eq!(Some(256 << 20), cgroups.memory_limit);
+        assert_eq!(None, cgroups.cpu_quota);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn hybrid_unlimited() {
+        let root = root_with("cgroups-hybrid", &[
+            (PROC_CGROUP_PATH, "4:memory:/user.slice\n0::/user.slice/session-2.scope\n"),
+            ("sys/fs/cgroup/memory/user.slice/memory.limit_in_bytes", "9223372036854771712\n"),
+        ]);
+
+        let cgroups = get_cgroups(&root).unwrap();
+        assert_eq!(CgroupVersion::Hybrid, cgroups.version);
+        assert_eq!("/user.slice", cgroups.path);
+        assert_eq!(None, cgroups.memory_limit);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn missing() {
+        let root = root_with("cgroups-none", &[("etc/os-release", "ID=debian\n")]);
+        assert_eq!(None, get_cgroups(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+// spell-checker:ignore bottlerocket
+
+use crate::{consts, matcher::Matcher, Version};
+
+/// Returns the version of cloud distributions whose `VERSION_ID` is only part of the version, or
+/// `None` for other distributions.
+///
+/// Google's Container-Optimized OS has the milestone in `VERSION_ID` (e.g. "109") and the build
+/// in `BUILD_ID` (e.g. "17800.66.78"), which are combined into 109.17800.66.78.
+pub fn version(id: &str, release: &str) -> Option<Version> {
+    if id != consts::COS {
+        return None;
+    }
+
+    let milestone = Matcher::KeyValue { key: "VERSION_ID" }.find(release)?.parse().ok()?;
+    let build = Matcher::KeyValue { key: "BUILD_ID" }.find(release).unwrap_or_default();
+    let mut build = build.split('.').map(|part| part.parse().unwrap_or_default());
+    Some(Version::Semantic(
+        milestone,
+        build.next().unwrap_or_default(),
+        build.next().unwrap_or_default(),
+        build.next().unwrap_or_default(),
+    ))
+}
+
+/// Returns whether the parentheses in `VERSION` contain the codename. Bottlerocket puts its
+/// variant there instead, e.g. "1.19.2 (aws-k8s-1.29)".
+pub fn has_codename_in_version(id: &str) -> bool {
+    id != consts::BOTTLEROCKET
+}
+
+/// Returns the ID of Amazon Linux from `/etc/system-release`, e.g. "Amazon Linux release 2
+/// (Karoo)" or "Amazon Linux AMI release 2018.03". On other distributions the file is usually a
+/// symbolic link to `/etc/redhat-release`, which is handled separately.
+pub fn system_release_id(release: &str) -> Option<String> {
+    release.starts_with("Amazon Linux").then(|| String::from(consts::AMZN))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn cos() {
+        let release = "NAME=\"Container-Optimized OS\"\nID=cos\nVERSION=109\nVERSION_ID=109\nBUILD_ID=17800.66.78\n";
+        assert_eq!(Some(Version::Semantic(109, 17800, 66, 78)), version("cos", release));
+        assert_eq!(Some(Version::Semantic(113, 0, 0, 0)), version("cos", "ID=cos\nVERSION_ID=113\n"));
+        assert_eq!(None, version("ubuntu", release));
+    }
+
+    #[test]
+    fn system_release() {
+        assert_eq!(Some(String::from("amzn")), system_release_id("Amazon Linux release 2 (Karoo)\n"));
+        assert_eq!(None, system_release_id("Red Hat Enterprise Linux release 9.3 (Plow)\n"));
+    }
+}
@@ -0,0 +1,186 @@
+use std::{fs, path::Path};
+
+use crate::logging::trace;
+
+use crate::{Deployment, DeploymentKind};
+
+pub fn get_deployment(root: &Path) -> Option<Deployment> {
+    if let Some(deployment) = profile(root, "nix/var/nix/profiles/system", DeploymentKind::Nix) {
+        return Some(deployment);
+    }
+    if let Some(deployment) = profile(root, "var/guix/profiles/system", DeploymentKind::Guix) {
+        return Some(deployment);
+    }
+    if root.join("run/ostree-booted").exists() {
+        return Some(ostree(root));
+    }
+    flatcar(root)
+}
+
+/// Reads the generation of a NixOS or Guix System profile, a symbolic link to e.g.
+/// "system-42-link", and the store path of the running system.
+fn profile(root: &Path, profile: &str, kind: DeploymentKind) -> Option<Deployment> {
+    let link = fs::read_link(root.join(profile)).ok()?;
+    trace!("Found {:?} system profile {:?}", kind, link);
+    let generation = link
+        .file_name()
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("system-"))
+        .and_then(|name| name.strip_suffix("-link"))
+        .and_then(|generation| generation.parse().ok());
+
+    // The profile may point to a newer generation than the booted one until the next reboot, but
+    // `/run/current-system` is switched on activation.
+    let commit = fs::read_link(root.join("run/current-system"))
+        .ok()
+        .and_then(|path| path.to_str().map(String::from));
+
+    Some(Deployment {
+        generation,
+        commit,
+        ..Deployment::new(kind)
+    })
+}
+
+/// Reads the booted ostree deployment, selected by the `ostree=` kernel argument, e.g.
+/// "ostree=/ostree/boot.1/fedora/<boot checksum>/0". It is a symbolic link to the deployment
+/// directory "/ostree/deploy/fedora/deploy/<commit checksum>.<serial>", next to which ostree
+/// stores the origin file with the refspec the deployment was pulled from.
+fn ostree(root: &Path) -> Deployment {
+    let mut deployment = Deployment::new(DeploymentKind::Ostree);
+
+    let cmdline = fs::read_to_string(root.join("proc/cmdline")).unwrap_or_default();
+    let Some(boot_link) = cmdline.split_whitespace().find_map(|arg| arg.strip_prefix("ostree=")) else {
+        return deployment;
+    };
+    let Ok(path) = fs::canonicalize(root.join(boot_link.trim_start_matches('/'))) else {
+        trace!("Failed to resolve ostree deployment {:?}", boot_link);
+        return deployment;
+    };
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return deployment;
+    };
+
+    deployment.commit = name.split('.').next().map(String::from);
+    if let Ok(origin) = fs::read_to_string(path.with_file_name(format!("{name}.origin"))) {
+        deployment.origin = origin.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            matches!(key.trim(), "refspec" | "container-image-reference").then(|| value.trim().to_string())
+        });
+    }
+    deployment
+}
+
+/// Reads the update channel of Flatcar Container Linux (or CoreOS Container Linux). The
+/// configuration in `/etc` overrides the image defaults in `/usr/share`.
+fn flatcar(root: &Path) -> Option<Deployment> {
+    if !["usr/share/flatcar", "usr/share/coreos"].iter().any(|dir| root.join(dir).join("update.conf").exists()) {
+        return None;
+    }
+
+    let files = [
+        "etc/flatcar/update.conf",
+        "usr/share/flatcar/update.conf",
+        "etc/coreos/update.conf",
+        "usr/share/coreos/update.conf",
+    ];
+
+    let channel = files.iter().find_map(|file| {
+        let content = fs::read_to_string(root.join(file)).ok()?;
+        content.lines().find_map(|line| {
+            let value = line.trim().strip_prefix("GROUP=")?.trim().trim_matches('"');
+            (!value.is_empty()).then(|| value.to_string())
+        })
+    });
+
+    Some(Deployment {
+        channel,
+        ..Deployment::new(DeploymentKind::Flatcar)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(unix)]
+    #[test]
+    fn nixos() {
+        use std::os::unix::fs::symlink;
+
+        let root = root_with("deployment-nixos", &[("nix/var/nix/profiles/system-42-link/nixos-version", "23.11")]);
+        symlink("system-42-link", root.join("nix/var/nix/profiles/system")).unwrap();
+        fs::create_dir_all(root.join("run")).unwrap();
+        symlink("/nix/store/9f7rvw2m-nixos-system-host-23.11", root.join("run/current-system")).unwrap();
+
+        let expected = Deployment {
+            generation: Some(42),
+            commit: Some(String::from("/nix/store/9f7rvw2m-nixos-system-host-23.11")),
+            ..Deployment::new(DeploymentKind::Nix)
+        };
+        assert_eq!(Some(expected), get_deployment(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn silverblue() {
+        use std::os::unix::fs::symlink;
+
+        let commit = "3c5a1d8e7f0b";
+        let root = root_with("deployment-ostree", &[
+            ("run/ostree-booted", ""),
+            ("proc/cmdline", "BOOT_IMAGE=(hd0,gpt2)/vmlinuz ostree=/ostree/boot.1/fedora/b07c/0 rhgb quiet\n"),
+            (
+                &format!("ostree/deploy/fedora/deploy/{commit}.0.origin"),
+                "[origin]\nrefspec=fedora:fedora/39/x86_64/silverblue\n",
+            ),
+            (&format!("ostree/deploy/fedora/deploy/{commit}.0/etc/os-release"), "ID=fedora\n"),
+        ]);
+        fs::create_dir_all(root.join("ostree/boot.1/fedora/b07c")).unwrap();
+        symlink(
+            format!("../../../deploy/fedora/deploy/{commit}.0"),
+            root.join("ostree/boot.1/fedora/b07c/0"),
+        )
+        .unwrap();
+
+        let expected = Deployment {
+            commit: Some(String::from(commit)),
+            origin: Some(String::from("fedora:fedora/39/x86_64/silverblue")),
+            ..Deployment::new(DeploymentKind::Ostree)
+        };
+        assert_eq!(Some(expected), get_deployment(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn ostree_without_cmdline() {
+        let root = root_with("deployment-ostree-unknown", &[("run/ostree-booted", "")]);
+        assert_eq!(Some(Deployment::new(DeploymentKind::Ostree)), get_deployment(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn flatcar_channel() {
+        let root = root_with("deployment-flatcar", &[
+            ("usr/share/flatcar/update.conf", "GROUP=stable\nSERVER=https://public.update.flatcar-linux.net/v1/update/\n"),
+            ("etc/flatcar/update.conf", "GROUP=beta\n"),
+        ]);
+
+        let expected = Deployment {
+            channel: Some(String::from("beta")),
+            ..Deployment::new(DeploymentKind::Flatcar)
+        };
+        assert_eq!(Some(expected), get_deployment(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn traditional() {
+        let root = root_with("deployment-none", &[("etc/os-release", "ID=debian\n")]);
+        assert_eq!(None, get_deployment(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,104 @@
+use std::{env, fs, path::Path};
+
+use crate::{DisplayServer, DisplayServerKind};
+
+pub fn get_display_server(root: &Path) -> DisplayServer {
+    DisplayServer {
+        display_attached: display_attached(root),
+        ..display_server(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+    }
+}
+
+/// Detects the display server and compositor from the environment of the session.
+fn display_server(var: impl Fn(&str) -> Option<String>) -> DisplayServer {
+    let kind = if var("WAYLAND_DISPLAY").is_some() {
+        DisplayServerKind::Wayland
+    } else if var("DISPLAY").is_some() {
+        DisplayServerKind::X11
+    } else {
+        DisplayServerKind::Headless
+    };
+
+    // Standalone compositors often don't set XDG_CURRENT_DESKTOP, but their IPC socket.
+    let compositor = if kind == DisplayServerKind::Headless {
+        None
+    } else if var("SWAYSOCK").is_some() {
+        Some(String::from("sway"))
+    } else if var("HYPRLAND_INSTANCE_SIGNATURE").is_some() {
+        Some(String::from("Hyprland"))
+    } else {
+        // A colon-separated list, e.g. "ubuntu:GNOME"; the last entry is the desktop itself.
+        var("XDG_CURRENT_DESKTOP")
+            .and_then(|desktops| desktops.split(':').next_back().map(String::from))
+            .or_else(|| var("DESKTOP_SESSION"))
+    };
+
+    DisplayServer {
+        kind,
+        compositor,
+        display_attached: None,
+    }
+}
+
+/// Returns whether a monitor is connected to a DRM connector, e.g.
+/// `/sys/class/drm/card0-HDMI-A-1/status` containing "connected", or `None` without DRM.
+fn display_attached(root: &Path) -> Option<bool> {
+    let connectors = fs::read_dir(root.join("sys/class/drm")).ok()?;
+    let mut statuses = connectors
+        .flatten()
+        .filter_map(|connector| fs::read_to_string(connector.path().join("status")).ok())
+        .peekable();
+    statuses.peek()?;
+    Some(statuses.any(|status| status.trim() == "connected"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    fn detect(environment: &[(&str, &str)]) -> DisplayServer {
+        display_server(|name| environment.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string()))
+    }
+
+    #[test]
+    fn servers() {
+        let info = detect(&[("WAYLAND_DISPLAY", "wayland-0"), ("DISPLAY", ":0"), ("XDG_CURRENT_DESKTOP", "ubuntu:GNOME")]);
+        assert_eq!(DisplayServerKind::Wayland, info.kind);
+        assert_eq!(Some(String::from("GNOME")), info.compositor);
+
+        let info = detect(&[("WAYLAND_DISPLAY", "wayland-1"), ("SWAYSOCK", "/run/user/1000/sway-ipc.sock")]);
+        assert_eq!(Some(String::from("sway")), info.compositor);
+
+        let info = detect(&[("DISPLAY", ":0"), ("DESKTOP_SESSION", "xfce")]);
+        assert_eq!(DisplayServerKind::X11, info.kind);
+        assert_eq!(Some(String::from("xfce")), info.compositor);
+
+        let info = detect(&[("XDG_CURRENT_DESKTOP", "GNOME")]);
+        assert_eq!(DisplayServerKind::Headless, info.kind);
+        assert_eq!(None, info.compositor);
+    }
+
+    #[test]
+    fn connectors() {
+        let root = root_with(
+            "drm",
+            &[
+                ("sys/class/drm/card0-eDP-1/status", "disconnected\n"),
+                ("sys/class/drm/card0-HDMI-A-1/status", "connected\n"),
+                ("sys/class/drm/version", "drm 1.1.0 20060810\n"),
+            ],
+        );
+        assert_eq!(Some(true), display_attached(&root));
+        fs::remove_dir_all(root).unwrap();
+
+        let root = root_with("drm-disconnected", &[("sys/class/drm/card0-Virtual-1/status", "disconnected\n")]);
+        assert_eq!(Some(false), display_attached(&root));
+        fs::remove_dir_all(root).unwrap();
+
+        let root = root_with("drm-none", &[("etc/os-release", "ID=alpine\n")]);
+        assert_eq!(None, display_attached(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
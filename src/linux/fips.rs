@@ -0,0 +1,32 @@
+use std::{fs, path::Path};
+
+pub fn fips_enabled(root: &Path) -> Option<bool> {
+    match fs::read_to_string(root.join("proc/sys/crypto/fips_enabled")) {
+        Ok(enabled) => Some(enabled.trim() == "1"),
+        // Kernels built without FIPS support can't run in FIPS mode.
+        Err(_) => Some(false),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn fips() {
+        let data = [
+            ("fips-on", Some("1\n"), Some(true)),
+            ("fips-off", Some("0\n"), Some(false)),
+            ("fips-unsupported", None, Some(false)),
+        ];
+
+        for (name, content, expected) in data {
+            let files: Vec<_> = content.map(|content| ("proc/sys/crypto/fips_enabled", content)).into_iter().collect();
+            let root = root_with(name, &files);
+            assert_eq!(expected, fips_enabled(&root));
+            let _ = fs::remove_dir_all(root);
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use std::{fs, path::Path};
+
+use crate::{Firmware, FirmwareType};
+
+pub fn get_firmware(root: &Path) -> Firmware {
+    let read = |path: &str| {
+        fs::read_to_string(root.join(path))
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    // The EFI runtime services are only exposed when the kernel was booted via UEFI.
+    let firmware_type = if root.join("sys/firmware/efi").exists() {
+        FirmwareType::Uefi
+    } else {
+        FirmwareType::Bios
+    };
+
+    Firmware {
+        firmware_type: Some(firmware_type),
+        vendor: read("sys/class/dmi/id/bios_vendor"),
+        version: read("sys/class/dmi/id/bios_version"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn uefi() {
+        let root = root_with("firmware-uefi", &[
+            ("sys/firmware/efi/fw_platform_size", "64\n"),
+            ("sys/class/dmi/id/bios_vendor", "American Megatrends Inc.\n"),
+            ("sys/class/dmi/id/bios_version", "1.17.0\n"),
+        ]);
+
+        let expected = Firmware {
+            firmware_type: Some(FirmwareType::Uefi),
+            vendor: Some(String::from("American Megatrends Inc.")),
+            version: Some(String::from("1.17.0")),
+        };
+        assert_eq!(expected, get_firmware(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn bios_without_dmi() {
+        let root = root_with("firmware-bios", &[("sys/class/dmi/id/bios_version", "\n")]);
+
+        let expected = Firmware {
+            firmware_type: Some(FirmwareType::Bios),
+            ..Default::default()
+        };
+        assert_eq!(expected, get_firmware(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+// spell-checker:ignore funtoo, webrsync
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::logging::trace;
+
+use crate::{DistroFamily, FieldName, OSInfo, Version};
+
+/// Profile selected with `eselect profile`, relative to the root.
+const MAKE_PROFILE_PATH: &str = "etc/portage/make.profile";
+
+/// Locations of the Gentoo ebuild repository, relative to the root, if the profile doesn't
+/// point into it: the current default and the one before 2019.
+const REPOSITORY_PATHS: [&str; 2] = ["var/db/repos/gentoo", "usr/portage"];
+
+/// Timestamp files of a repository snapshot: written by the rsync mirrors (and
+/// `emerge-webrsync`), e.g. "Wed, 20 Mar 2024 00:45:00 +0000", and by the snapshot tarballs,
+/// e.g. "1710895501 Wed Mar 20 00:45:01 2024 UTC".
+const TIMESTAMP_PATHS: [&str; 2] = ["metadata/timestamp.chk", "metadata/timestamp.x"];
+
+const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// Reports the Portage profile and the ebuild repository snapshot of Gentoo and its
+/// derivatives, whose os-release only says that they are rolling releases.
+///
+/// The edition is set to the profile, e.g. "default/linux/amd64/23.0/desktop", and the version
+/// to a rolling release with the snapshot date, e.g. "20240320".
+pub fn detect(info: &mut OSInfo, root: &Path) {
+    if info.family() != DistroFamily::Gentoo {
+        return;
+    }
+
+    let make_profile = root.join(MAKE_PROFILE_PATH);
+    let (profile, repository) = match fs::read_link(&make_profile) {
+        Ok(target) => {
+            let target = target.to_string_lossy().into_owned();
+            match target.split_once("/profiles/") {
+                Some((repository, profile)) => {
+                    // Relative targets are resolved lexically, so that they stay under `root`.
+                    let mut path = PathBuf::from(if repository.starts_with('/') { "" } else { "etc/portage" });
+                    for component in repository.split('/') {
+                        match component {
+                            "" | "." => {}
+                            ".." => {
+                                path.pop();
+                            }
+                            component => path.push(component),
+                        }
+                    }
+                    let repository = root.join(path);
+                    (Some(profile.trim_end_matches('/').to_string()), Some(repository))
+                }
+                None => (Some(target), None),
+            }
+        }
+        // Funtoo's profile is a directory listing the profiles it combines.
+        Err(_) => (fs::read_to_string(make_profile.join("parent")).ok().and_then(|parent| parent_profiles(&parent)), None),
+    };
+
+    trace!("Detected Portage profile {:?} in repository {:?}", profile, repository);
+
+    if let Some(profile) = profile {
+        info.edition = Some(profile);
+        info.record_source(&[FieldName::Edition], &make_profile.display().to_string());
+    }
+
+    let repositories = repository
+        .into_iter()
+        .chain(REPOSITORY_PATHS.iter().map(|path| root.join(path)));
+    if let Some((date, path)) = snapshot(repositories) {
+        info.version = Version::Rolling(Some(date));
+        info.version_extra = None;
+        info.record_source(&[FieldName::Version], &path.display().to_string());
+    }
+}
+
+/// Combines the profiles listed in the `parent` file of a profile directory, e.g.
+/// "funtoo/1.0/linux-gnu/arch/x86-64bit funtoo/1.0/linux-gnu/flavor/desktop".
+fn parent_profiles(parent: &str) -> Option<String> {
+    let profiles: Vec<&str> = parent
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        // Entries may be qualified with their repository, e.g. "gentoo:funtoo/1.0/...".
+        .map(|line| line.split_once(':').map_or(line, |(_, profile)| profile))
+        .collect();
+    (!profiles.is_empty()).then(|| profiles.join(" "))
+}
+
+/// Returns the date of the first repository snapshot found, e.g. "20240320", and the file it
+/// was read from.
+fn snapshot(repositories: impl Iterator<Item = PathBuf>) -> Option<(String, PathBuf)> {
+    repositories
+        .flat_map(|repository| TIMESTAMP_PATHS.map(|path| repository.join(path)))
+        .find_map(|path| {
+            let date = parse_timestamp(&fs::read_to_string(&path).ok()?)?;
+            Some((date, path))
+        })
+}
+
+/// Converts the date of a snapshot timestamp to "YYYYMMDD", the format of the snapshot
+/// tarballs.
+fn parse_timestamp(timestamp: &str) -> Option<String> {
+    let words: Vec<&str> = timestamp.split_whitespace().map(|word| word.trim_end_matches(',')).collect();
+    let month_index = words.iter().position(|word| MONTHS.contains(word))?;
+    let month = MONTHS.iter().position(|month| *month == words[month_index])? + 1;
+
+    // "Wed, 20 Mar 2024 ..." has the day before the month and the year after it, "Wed Mar 20
+    // 00:45:01 2024 UTC" the day after the month and the year after the time.
+    let before = month_index.checked_sub(1).and_then(|index| words.get(index));
+    let (day, year) = match (before, words.get(month_index + 1)) {
+        (Some(day), Some(year)) if day.parse::<u32>().is_ok() => (*day, *year),
+        (_, Some(day)) => (*day, *words.get(month_index + 3)?),
+        _ => return None,
+    };
+    let day: u32 = day.parse().ok()?;
+    let year: u32 = year.parse().ok()?;
+    (1..=31).contains(&day).then(|| format!("{year:04}{month:02}{day:02}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    fn gentoo() -> OSInfo {
+        OSInfo {
+            name: Some(String::from("Gentoo")),
+            version: Version::from_string("2.15"),
+            ..OSInfo::with_id(String::from("gentoo"))
+        }
+    }
+
+    #[test]
+    fn timestamps() {
+        let data = [
+            ("Wed, 20 Mar 2024 00:45:00 +0000\n", Some("20240320")),
+            ("1710895501 Wed Mar 20 00:45:01 2024 UTC\n", Some("20240320")),
+            ("Sun, 05 May 2024 01:30:00 +0000", Some("20240505")),
+            ("1710895501\n", None),
+            ("Mar 20", None),
+            ("", None),
+        ];
+
+        for (timestamp, expected) in data {
+            assert_eq!(expected.map(String::from), parse_timestamp(timestamp), "{timestamp}");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn gentoo_profile_and_snapshot() {
+        use std::os::unix::fs::symlink;
+
+        let root = root_with("gentoo", &[
+            ("var/db/repos/gentoo/profiles/default/linux/amd64/23.0/desktop/parent", ".."),
+            ("var/db/repos/gentoo/metadata/timestamp.chk", "Wed, 20 Mar 2024 00:45:00 +0000\n"),
+        ]);
+        fs::create_dir_all(root.join("etc/portage")).unwrap();
+        symlink(
+            "../../var/db/repos/gentoo/profiles/default/linux/amd64/23.0/desktop",
+            root.join(MAKE_PROFILE_PATH),
+        )
+        .unwrap();
+
+        let mut info = gentoo();
+        detect(&mut info, &root);
+        assert_eq!(Some("default/linux/amd64/23.0/desktop"), info.edition());
+        assert_eq!(&Version::Rolling(Some(String::from("20240320"))), info.version());
+        let timestamp = root.join("var/db/repos/gentoo/metadata/timestamp.chk").display().to_string();
+        assert_eq!(Some(timestamp.as_str()), info.source_of(FieldName::Version));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn custom_repository_location() {
+        use std::os::unix::fs::symlink;
+
+        let root = root_with("gentoo-custom-repo", &[
+            ("srv/gentoo/metadata/timestamp.x", "1710895501 Wed Mar 20 00:45:01 2024 UTC\n"),
+            ("var/db/repos/gentoo/metadata/timestamp.chk", "Mon, 01 Jan 2024 00:00:00 +0000\n"),
+        ]);
+        fs::create_dir_all(root.join("etc/portage")).unwrap();
+        symlink("/srv/gentoo/profiles/default/linux/arm64/23.0/", root.join(MAKE_PROFILE_PATH)).unwrap();
+
+        let mut info = gentoo();
+        detect(&mut info, &root);
+        assert_eq!(Some("default/linux/arm64/23.0"), info.edition());
+        assert_eq!(&Version::Rolling(Some(String::from("20240320"))), info.version());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn funtoo() {
+        let root = root_with("funtoo", &[(
+            "etc/portage/make.profile/parent",
+            "gentoo:funtoo/1.0/linux-gnu/arch/x86-64bit\ngentoo:funtoo/1.0/linux-gnu/build/next\n\ngentoo:funtoo/1.0/linux-gnu/flavor/desktop\n",
+        )]);
+
+        let mut info = OSInfo::with_id(String::from("funtoo"));
+        detect(&mut info, &root);
+        assert_eq!(
+            Some("funtoo/1.0/linux-gnu/arch/x86-64bit funtoo/1.0/linux-gnu/build/next funtoo/1.0/linux-gnu/flavor/desktop"),
+            info.edition()
+        );
+        // Without a snapshot, the version is left alone.
+        assert_eq!(&Version::Unknown, info.version());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn other_distribution() {
+        let root = root_with("gentoo-debian", &[("var/db/repos/gentoo/metadata/timestamp.chk", "Wed, 20 Mar 2024 00:45:00 +0000\n")]);
+
+        let mut info = OSInfo::with_id(String::from("debian"));
+        detect(&mut info, &root);
+        assert_eq!(OSInfo::with_id(String::from("debian")), info);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
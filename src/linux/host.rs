@@ -0,0 +1,337 @@
+// spell-checker:ignore crostini, garcon, innotek
+
+use std::{fs, path::Path};
+
+use crate::logging::trace;
+
+use super::os_release;
+use crate::{Config, FieldName, HostOsRelease, OSInfo, Version, consts, unix::parse_release};
+
+/// Host os-release files exposed under `/run/host`: by Flatpak (always, and with the `host-etc`
+/// or `host-os` permissions), and by Toolbx and Distrobox, which mount the host's root there.
+const HOST_OS_RELEASE_PATHS: [&str; 3] = ["run/host/os-release", "run/host/etc/os-release", "run/host/usr/lib/os-release"];
+
+/// Detects the environment the operating system runs in and records it in `info`. With
+/// `HostOsRelease::Report` in `config`, `info` is replaced by the host's os-release, if exposed.
+///
+/// See `OSInfo::host` for the semantics of the host chain.
+pub fn detect(info: &mut OSInfo, root: &Path, config: &Config) {
+    let host_os_release = config.host_os_release;
+    let kernel_release = fs::read_to_string(root.join("proc/sys/kernel/osrelease")).unwrap_or_default();
+
+    if let Some(wsl) = wsl(&kernel_release) {
+        trace!("Detected {:?}", wsl);
+        info.host = Some(Box::new(wsl));
+        return;
+    }
+
+    if let Some(milestone) = crostini(root) {
+        trace!("Detected Crostini, ChromeOS milestone {:?}", milestone);
+        info.variant = Some(String::from("crostini"));
+        let source = if milestone.is_some() { "dev/.cros_milestone" } else { "opt/google/cros-containers" };
+        info.record_source(&[FieldName::Variant], &root.join(source).display().to_string());
+        info.host = Some(Box::new(OSInfo {
+            id: Some(String::from(consts::CHROMEOS)),
+            name: Some(String::from("ChromeOS")),
+            version: milestone.map(Version::from_string).unwrap_or_default(),
+            ..Default::default()
+        }));
+        return;
+    }
+
+    let hypervisor = hypervisor(root);
+    if host_os_release != HostOsRelease::Ignore
+        && let Some(mut host) = get_host_os_release(root, config)
+    {
+        trace!("Detected host {:?}, hypervisor {:?}", host, hypervisor);
+        host.host = hypervisor.map(Box::new);
+        if host_os_release == HostOsRelease::Report {
+            *info = host;
+        } else {
+            info.host = Some(Box::new(host));
+        }
+        return;
+    }
+
+    if container(root) {
+        trace!("Detected container, hypervisor {:?}", hypervisor);
+        info.host = Some(Box::new(OSInfo {
+            id: Some(String::from(consts::LINUX)),
+            name: Some(String::from("Linux")),
+            version: parse_release(&kernel_release).unwrap_or_default(),
+            host: hypervisor.map(Box::new),
+            ..Default::default()
+        }));
+        return;
+    }
+
+    info.host = hypervisor.map(Box::new);
+}
+
+/// Reads the first of the host os-release files exposed under `/run/host`.
+pub fn get_host_os_release(root: &Path, config: &Config) -> Option<OSInfo> {
+    HOST_OS_RELEASE_PATHS
+        .iter()
+        .find_map(|path| os_release::get_os_data_at(root, path, config, &mut Vec::new()))
+}
+
+/// Detects the Windows Subsystem for Linux from the kernel release, e.g.
+/// "5.15.146.1-microsoft-standard-WSL2" or "4.4.0-19041-Microsoft" for WSL 1.
+fn wsl(kernel_release: &str) -> Option<OSInfo> {
+    let kernel_release = kernel_release.trim();
+    if !kernel_release.to_ascii_lowercase().contains("microsoft") {
+        return None;
+    }
+
+    let (version, windows_version) = if kernel_release.ends_with("Microsoft") {
+        // WSL 1 reports the Windows build number in place of the kernel patch level.
+        let build = kernel_release.split('-').nth(1).and_then(|build| build.parse().ok());
+        (1, build.map(|build| Version::Semantic(10, 0, build, 0)).unwrap_or_default())
+    } else {
+        (2, Version::Unknown)
+    };
+
+    let windows = OSInfo {
+        id: Some(String::from(consts::WINDOWS)),
+        name: Some(String::from("Windows")),
+        version: windows_version,
+        ..Default::default()
+    };
+
+    Some(OSInfo {
+        id: Some(String::from(consts::WSL)),
+        name: Some(String::from("Windows Subsystem for Linux")),
+        version: Version::Semantic(version, 0, 0, 0),
+        host: Some(Box::new(windows)),
+        ..Default::default()
+    })
+}
+
+/// Detects whether the system runs inside ChromeOS's Crostini container (the Termina VM), where
+/// os-release describes the Debian container rather than ChromeOS.
+///
+/// Returns the ChromeOS milestone (e.g. "120") if it is known.
+fn crostini(root: &Path) -> Option<Option<String>> {
+    if let Ok(milestone) = fs::read_to_string(root.join("dev/.cros_milestone")) {
+        let milestone = milestone.trim();
+        return Some((!milestone.is_empty()).then(|| milestone.to_string()));
+    }
+
+    // The garcon daemon bridging the container with ChromeOS lives here.
+    if root.join("opt/google/cros-containers").exists() {
+        return Some(None);
+    }
+
+    None
+}
+
+/// Detects whether the system runs inside a Docker or Podman style container.
+pub fn container(root: &Path) -> bool {
+    root.join(".dockerenv").exists() || root.join("run/.containerenv").exists()
+}
+
+/// Detects the hypervisor the system runs on from the DMI data.
+fn hypervisor(root: &Path) -> Option<OSInfo> {
+    let read = |path: &str| {
+        fs::read_to_string(root.join(path))
+            .map(|value| value.trim().to_string())
+            .unwrap_or_default()
+    };
+    let vendor = read("sys/class/dmi/id/sys_vendor");
+    let product = read("sys/class/dmi/id/product_name");
+
+    let (id, name) = match (vendor.as_str(), product.as_str()) {
+        (_, "KVM") | ("Red Hat", _) => (consts::KVM, "KVM"),
+        ("QEMU", _) => (consts::QEMU, "QEMU"),
+        ("VMware, Inc.", _) => (consts::VMWARE, "VMware"),
+        ("innotek GmbH", _) | (_, "VirtualBox") => (consts::VIRTUALBOX, "VirtualBox"),
+        ("Microsoft Corporation", "Virtual Machine") => (consts::HYPERV, "Hyper-V"),
+        ("Xen", _) => (consts::XEN, "Xen"),
+        ("Amazon EC2", _) => (consts::NITRO, "AWS Nitro"),
+        ("Google", "Google Compute Engine") => (consts::GCE, "Google Compute Engine"),
+        ("Parallels Software International Inc.", _) => (consts::PARALLELS, "Parallels"),
+        _ if read("sys/hypervisor/type") == "xen" => (consts::XEN, "Xen"),
+        _ => return None,
+    };
+
+    Some(OSInfo {
+        id: Some(String::from(id)),
+        name: Some(String::from(name)),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    fn config(host_os_release: HostOsRelease) -> Config {
+        Config {
+            host_os_release,
+            ..Config::default()
+        }
+    }
+
+    fn chain(info: &OSInfo) -> Vec<(String, Version)> {
+        info.hosts().map(|host| (host.id().to_string(), host.version().clone())).collect()
+    }
+
+    #[test]
+    fn wsl2() {
+        let root = root_with("wsl2", &[("proc/sys/kernel/osrelease", "5.15.146.1-microsoft-standard-WSL2\n")]);
+
+        let mut info = OSInfo::with_id(String::from("ubuntu"));
+        detect(&mut info, &root, &config(HostOsRelease::Chain));
+        assert_eq!(
+            vec![
+                (String::from("wsl"), Version::Semantic(2, 0, 0, 0)),
+                (String::from("windows"), Version::Unknown),
+            ],
+            chain(&info)
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn wsl1() {
+        let info = wsl("4.4.0-19041-Microsoft\n").unwrap();
+        assert_eq!(&Version::Semantic(1, 0, 0, 0), info.version());
+        assert_eq!(&Version::Semantic(10, 0, 19041, 0), info.host().unwrap().version());
+    }
+
+    #[test]
+    fn crostini_milestone() {
+        let root = root_with("crostini", &[("dev/.cros_milestone", "120\n")]);
+        assert_eq!(Some(Some(String::from("120"))), crostini(&root));
+
+        let mut info = OSInfo::with_id(String::from("debian"));
+        detect(&mut info, &root, &config(HostOsRelease::Chain));
+        assert_eq!(Some("crostini"), info.variant());
+        assert_eq!(vec![(String::from("chromeos"), Version::Semantic(120, 0, 0, 0))], chain(&info));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn crostini_without_milestone() {
+        let root = root_with("crostini-garcon", &[("opt/google/cros-containers/bin/garcon", "")]);
+        assert_eq!(Some(None), crostini(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn not_crostini() {
+        let root = root_with("not-crostini", &[("dev/.keep", "")]);
+        assert_eq!(None, crostini(&root));
+
+        let mut info = OSInfo::with_id(String::from("debian"));
+        detect(&mut info, &root, &config(HostOsRelease::Chain));
+        assert_eq!(OSInfo::with_id(String::from("debian")), info);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn container_in_vm() {
+        let root = root_with("container", &[
+            (".dockerenv", ""),
+            ("proc/sys/kernel/osrelease", "6.5.0-1014-gcp\n"),
+            ("sys/class/dmi/id/sys_vendor", "Google\n"),
+            ("sys/class/dmi/id/product_name", "Google Compute Engine\n"),
+        ]);
+
+        let mut info = OSInfo::with_id(String::from("alpine"));
+        detect(&mut info, &root, &config(HostOsRelease::Chain));
+        assert_eq!(
+            vec![
+                (String::from("linux"), Version::Semantic(6, 5, 0, 0)),
+                (String::from("gce"), Version::Unknown),
+            ],
+            chain(&info)
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn flatpak_host() {
+        let root = root_with("host-flatpak", &[
+            (".flatpak-info", "[Application]\nname=org.gnome.Maps\n"),
+            ("run/host/os-release", "ID=fedora\nNAME=\"Fedora Linux\"\nVERSION_ID=40\n"),
+            ("sys/class/dmi/id/sys_vendor", "QEMU\n"),
+        ]);
+        let runtime = || OSInfo::with_id(String::from("org.gnome.platform"));
+
+        let mut info = runtime();
+        detect(&mut info, &root, &config(HostOsRelease::Chain));
+        assert_eq!("org.gnome.platform", info.id());
+        assert_eq!(
+            vec![
+                (String::from("fedora"), Version::Semantic(40, 0, 0, 0)),
+                (String::from("qemu"), Version::Unknown),
+            ],
+            chain(&info)
+        );
+        let source = root.join("run/host/os-release").display().to_string();
+        assert_eq!(Some(source.as_str()), info.host().unwrap().source_of(FieldName::Id));
+
+        let mut info = runtime();
+        detect(&mut info, &root, &config(HostOsRelease::Report));
+        assert_eq!("fedora", info.id());
+        assert_eq!("Fedora Linux", info.name());
+        assert_eq!(vec![(String::from("qemu"), Version::Unknown)], chain(&info));
+
+        let mut info = runtime();
+        detect(&mut info, &root, &config(HostOsRelease::Ignore));
+        assert_eq!(vec![(String::from("qemu"), Version::Unknown)], chain(&info));
+
+        // The host os-release is read within the size limit of the configuration.
+        let mut info = runtime();
+        let limited = Config {
+            max_file_size: Some(8),
+            ..config(HostOsRelease::Chain)
+        };
+        detect(&mut info, &root, &limited);
+        assert_eq!(vec![(String::from("qemu"), Version::Unknown)], chain(&info));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn toolbox_host() {
+        let root = root_with("host-toolbox", &[
+            ("run/.containerenv", ""),
+            ("run/host/etc/os-release", "ID=ubuntu\nNAME=\"Ubuntu\"\nVERSION_ID=\"24.04\"\n"),
+        ]);
+
+        let mut info = OSInfo::with_id(String::from("fedora"));
+        detect(&mut info, &root, &config(HostOsRelease::Chain));
+        assert_eq!(vec![(String::from("ubuntu"), Version::Semantic(24, 4, 0, 0))], chain(&info));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn virtual_machine() {
+        let root = root_with("vm", &[
+            ("proc/sys/kernel/osrelease", "6.1.0-18-amd64\n"),
+            ("sys/class/dmi/id/sys_vendor", "QEMU\n"),
+            ("sys/class/dmi/id/product_name", "Standard PC (Q35 + ICH9, 2009)\n"),
+        ]);
+
+        let mut info = OSInfo::with_id(String::from("debian"));
+        detect(&mut info, &root, &config(HostOsRelease::Chain));
+        assert_eq!(vec![(String::from("qemu"), Version::Unknown)], chain(&info));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn bare_metal() {
+        let root = root_with("bare-metal", &[
+            ("proc/sys/kernel/osrelease", "6.1.0-18-amd64\n"),
+            ("sys/class/dmi/id/sys_vendor", "Dell Inc.\n"),
+        ]);
+
+        let mut info = OSInfo::with_id(String::from("debian"));
+        detect(&mut info, &root, &config(HostOsRelease::Chain));
+        assert_eq!(OSInfo::with_id(String::from("debian")), info);
+        fs::remove_dir_all(root).unwrap();
+    }
+}
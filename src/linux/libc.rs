@@ -0,0 +1,152 @@
+#![allow(unsafe_code)]
+
+use std::{fs, path::Path};
+
+use crate::logging::trace;
+
+use crate::{LibcInfo, LibcKind, Version, exec};
+
+pub fn get_libc(root: &Path) -> Option<LibcInfo> {
+    if let Some(version) = running_glibc_version() {
+        return Some(LibcInfo {
+            kind: LibcKind::Glibc,
+            version,
+        });
+    }
+
+    // Statically linked (e.g. musl) binaries can't ask the system library, so ask its loader.
+    match exec::run("ldd", &["--version"]) {
+        Ok(output) => {
+            let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+            // musl's ldd prints its version to stderr and exits with an error.
+            text.push_str(&String::from_utf8_lossy(&output.stderr));
+            if let Some(info) = parse_ldd_version(&text) {
+                return Some(info);
+            }
+        }
+        Err(e) => trace!("Unable to run ldd: {:?}", e),
+    }
+
+    get_libc_from_files(root)
+}
+
+/// Returns the version of glibc the calling process is dynamically linked against, i.e. the
+/// system's glibc.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn running_glibc_version() -> Option<Version> {
+    use std::ffi::{CStr, c_char};
+
+    unsafe extern "C" {
+        fn gnu_get_libc_version() -> *const c_char;
+    }
+
+    // SAFETY: gnu_get_libc_version returns a pointer to a static NUL-terminated string.
+    let version = unsafe { CStr::from_ptr(gnu_get_libc_version()) };
+    version.to_str().ok().map(Version::from_string)
+}
+
+#[cfg(not(all(target_os = "linux", target_env = "gnu")))]
+fn running_glibc_version() -> Option<Version> {
+    None
+}
+
+/// Parses the output of `ldd --version`, e.g. "ldd (Ubuntu GLIBC 2.35-0ubuntu3.6) 2.35" for
+/// glibc or "musl libc (x86_64)\nVersion 1.2.4" for musl.
+fn parse_ldd_version(output: &str) -> Option<LibcInfo> {
+    let first_line = output.lines().next()?.to_ascii_lowercase();
+
+    if first_line.contains("glibc") || first_line.contains("gnu libc") {
+        let version = first_line.split_whitespace().last().map(Version::from_string).unwrap_or_default();
+        return Some(LibcInfo {
+            kind: LibcKind::Glibc,
+            version,
+        });
+    }
+
+    if first_line.starts_with("musl") {
+        let version = output
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("Version "))
+            .map(Version::from_string)
+            .unwrap_or_default();
+        return Some(LibcInfo {
+            kind: LibcKind::Musl,
+            version,
+        });
+    }
+
+    None
+}
+
+/// Detects the C library from the dynamic loaders installed under `root`, without its version.
+fn get_libc_from_files(root: &Path) -> Option<LibcInfo> {
+    let kind = ["lib", "lib64", "usr/lib"].iter().find_map(|dir| {
+        let entries = fs::read_dir(root.join(dir)).ok()?;
+        entries.flatten().find_map(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with("ld-musl-") {
+                Some(LibcKind::Musl)
+            } else if name.starts_with("ld-linux") || name == "libc.so.6" {
+                Some(LibcKind::Glibc)
+            } else {
+                None
+            }
+        })
+    })?;
+
+    Some(LibcInfo {
+        kind,
+        version: Version::Unknown,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ldd_version() {
+        let data = [
+            (
+                "ldd (Ubuntu GLIBC 2.35-0ubuntu3.6) 2.35\nCopyright (C) 2022 Free Software Foundation, Inc.\n",
+                Some((LibcKind::Glibc, Version::Semantic(2, 35, 0, 0))),
+            ),
+            ("ldd (GNU libc) 2.39\n", Some((LibcKind::Glibc, Version::Semantic(2, 39, 0, 0)))),
+            (
+                "musl libc (x86_64)\nVersion 1.2.4\nDynamic Program Loader\nUsage: ldd [options] [--] pathname\n",
+                Some((LibcKind::Musl, Version::Semantic(1, 2, 4, 0))),
+            ),
+            ("ldd: command not found\n", None),
+            ("", None),
+        ];
+
+        for (output, expected) in data {
+            let expected = expected.map(|(kind, version)| LibcInfo { kind, version });
+            assert_eq!(expected, parse_ldd_version(output));
+        }
+    }
+
+    #[test]
+    fn loader_files() {
+        let root = root_with("libc-alpine", &[("lib/ld-musl-x86_64.so.1", ""), ("lib/libz.so.1", "")]);
+        let expected = LibcInfo {
+            kind: LibcKind::Musl,
+            version: Version::Unknown,
+        };
+        assert_eq!(Some(expected), get_libc_from_files(&root));
+        fs::remove_dir_all(root).unwrap();
+
+        let root = root_with("libc-none", &[("etc/os-release", "ID=scratch\n")]);
+        assert_eq!(None, get_libc_from_files(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(all(target_os = "linux", target_env = "gnu"))]
+    #[test]
+    fn running_glibc() {
+        assert!(matches!(running_glibc_version(), Some(Version::Semantic(2, _, _, _))));
+    }
+}
@@ -0,0 +1,53 @@
+use std::{fs, path::Path};
+
+/// Returns the system-wide default locale configured in `/etc/locale.conf` (systemd) or
+/// `/etc/default/locale` (Debian), e.g. "de-DE" for `LANG=de_DE.UTF-8`.
+pub fn system_locale(root: &Path) -> Option<String> {
+    ["etc/locale.conf", "etc/default/locale"].iter().find_map(|file| {
+        let content = fs::read_to_string(root.join(file)).ok()?;
+        content.lines().find_map(|line| {
+            let value = line.trim().strip_prefix("LANG=")?;
+            language_tag(value.trim_matches(|c| c == '"' || c == '\''))
+        })
+    })
+}
+
+/// Converts a POSIX locale name, e.g. "pt_BR.UTF-8" or "sr_RS@latin", to a language tag
+/// ("pt-BR"). The "C" and "POSIX" locales have no language.
+fn language_tag(locale: &str) -> Option<String> {
+    let name = locale.split(['.', '@']).next().unwrap_or_default();
+    match name {
+        "" | "C" | "POSIX" => None,
+        name => Some(name.replace('_', "-")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tags() {
+        let data = [
+            ("de_DE.UTF-8", Some("de-DE")),
+            ("sr_RS@latin", Some("sr-RS")),
+            ("en", Some("en")),
+            ("C.UTF-8", None),
+            ("POSIX", None),
+            ("", None),
+        ];
+
+        for (locale, expected) in data {
+            assert_eq!(expected.map(String::from), language_tag(locale));
+        }
+    }
+
+    #[test]
+    fn debian() {
+        let root = root_with("locale-debian", &[("etc/default/locale", "#  File generated by update-locale\nLANG=\"fr_FR.UTF-8\"\n")]);
+        assert_eq!(Some(String::from("fr-FR")), system_locale(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -1,13 +1,195 @@
+mod board;
+mod boot;
+mod cloud;
+mod deployment;
+mod display;
+mod firmware;
+mod fips;
+mod gentoo;
+mod host;
+mod libc;
+mod locale;
+mod network;
 mod os_release;
+mod reboot;
+mod sandbox;
+mod cgroups;
+mod security;
+mod session;
+mod suse;
+mod user;
 
-use log::trace;
+use crate::logging::trace;
 
-use crate::OSInfo;
+use std::{fs, path::Path};
 
-pub fn get_info() -> OSInfo {
+use crate::{Cgroups, Config, Deployment, Diagnostic, DisplayServer, Firmware, LibcInfo, NetworkEnv, OSInfo, Sandbox, SecurityProfile, SessionKind, Version, boot::BootStatus, unix};
+
+pub fn get_info(config: &Config, diagnostics: &mut Vec<Diagnostic>) -> OSInfo {
     trace!("Linux::get_info is called");
-    let info = os_release::get_os_data();
+    // Without release files, e.g. in a minimal container, the kernel still identifies the system.
+    let info = get_info_from(Path::new("/"), config, diagnostics)
+        .or_else(|| unix::get_info_from_uname(Path::new("/"), config, diagnostics));
     trace!("Returning {:?}", info);
-    info.unwrap_or_default()
+    info.unwrap_or_else(|| {
+        diagnostics.push(Diagnostic::NotDetected);
+        OSInfo::default()
+    })
+}
+
+pub fn set_max_file_size(bytes: u64) {
+    os_release::set_max_file_size(bytes);
+}
+
+/// Returns the size limit of the release files read with `config`.
+#[cfg(feature = "json")]
+pub fn max_file_size(config: &Config) -> u64 {
+    os_release::max_file_size(config)
+}
+
+/// Detects the operating system installed under `root`.
+pub fn get_info_from(root: &Path, config: &Config, diagnostics: &mut Vec<Diagnostic>) -> Option<OSInfo> {
+    let mut info = os_release::get_os_data(root, config, diagnostics)?;
+    board::detect(&mut info, root);
+    gentoo::detect(&mut info, root);
+    host::detect(&mut info, root, config);
+    Some(info)
 }
 
+/// Detects the operating system installed under `root` like `get_info_from`, recording why
+/// `root` couldn't be read, if it couldn't, when nothing is detected.
+pub fn get_info_under(root: &Path, config: &Config, diagnostics: &mut Vec<Diagnostic>) -> OSInfo {
+    trace!("Linux::get_info_under is called");
+    let info = get_info_from(root, config, diagnostics);
+    trace!("Returning {:?}", info);
+    info.unwrap_or_else(|| {
+        if let Err(e) = fs::metadata(root) {
+            diagnostics.push(Diagnostic::source_unreadable(root.display(), e));
+        }
+        diagnostics.push(Diagnostic::NotDetected);
+        OSInfo::default()
+    })
+}
+
+/// Returns the release files the detection reads, relative to the root.
+#[cfg(feature = "json")]
+pub fn release_files() -> impl Iterator<Item = &'static str> {
+    os_release::release_files()
+}
+
+pub fn get_kernel_version() -> Option<Version> {
+    trace!("Linux::get_kernel_version is called");
+    let release = fs::read_to_string("/proc/sys/kernel/osrelease").ok()?;
+    unix::parse_release(&release)
+}
+
+pub fn get_security_profile() -> SecurityProfile {
+    trace!("Linux::get_security_profile is called");
+    SecurityProfile {
+        security_processor: security::get_security_processor(Path::new("/")),
+        disk_encryption: security::get_disk_encryption(Path::new("/")),
+    }
+}
+
+pub fn get_boot_status() -> BootStatus {
+    trace!("Linux::get_boot_status is called");
+    boot::get_boot_status(Path::new("/"))
+}
+
+pub fn get_firmware() -> Firmware {
+    trace!("Linux::get_firmware is called");
+    firmware::get_firmware(Path::new("/"))
+}
+
+pub fn fips_enabled() -> Option<bool> {
+    trace!("Linux::fips_enabled is called");
+    fips::fips_enabled(Path::new("/"))
+}
+
+pub fn get_deployment() -> Option<Deployment> {
+    trace!("Linux::get_deployment is called");
+    deployment::get_deployment(Path::new("/"))
+}
+
+pub fn get_sandbox(config: &Config) -> Option<Sandbox> {
+    trace!("Linux::get_sandbox is called");
+    sandbox::get_sandbox(Path::new("/"), config, |name| std::env::var(name).ok())
+}
+
+pub fn get_cgroups() -> Option<Cgroups> {
+    trace!("Linux::get_cgroups is called");
+    cgroups::get_cgroups(Path::new("/"))
+}
+
+pub fn get_libc() -> Option<LibcInfo> {
+    trace!("Linux::get_libc is called");
+    libc::get_libc(Path::new("/"))
+}
+
+pub fn install_language() -> Option<String> {
+    // Distribution installers only configure the system locale.
+    None
+}
+
+pub fn system_locale() -> Option<String> {
+    trace!("Linux::system_locale is called");
+    locale::system_locale(Path::new("/"))
+}
+
+pub fn pending_reboot() -> Option<bool> {
+    trace!("Linux::pending_reboot is called");
+    reboot::pending_reboot(Path::new("/")).or_else(reboot::needs_restarting)
+}
+
+pub fn get_session_kind() -> SessionKind {
+    trace!("Linux::get_session_kind is called");
+    session::get_session_kind()
+}
+
+pub fn network_env() -> NetworkEnv {
+    trace!("Linux::network_env is called");
+    network::network_env()
+}
+
+pub fn get_display_server() -> DisplayServer {
+    trace!("Linux::get_display_server is called");
+    display::get_display_server(Path::new("/"))
+}
+
+pub fn is_elevated() -> bool {
+    user::effective_uid() == Some(0)
+}
+
+pub fn current_user() -> Option<String> {
+    trace!("Linux::current_user is called");
+    user::current_user(Path::new("/"))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Creates a temporary root directory containing the given files.
+    pub(crate) fn root_with(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("osinfo-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for (path, content) in files {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn missing_root() {
+        let root = std::env::temp_dir().join(format!("osinfo-missing-root-{}", std::process::id()));
+        let mut diagnostics = Vec::new();
+        assert_eq!(OSInfo::default(), get_info_under(&root, &Config::default(), &mut diagnostics));
+        assert!(matches!(
+            &diagnostics[..],
+            [.., Diagnostic::SourceUnreadable { source, .. }, Diagnostic::NotDetected] if *source == root.display().to_string()
+        ));
+    }
+}
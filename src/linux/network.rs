@@ -0,0 +1,101 @@
+use std::env;
+
+use crate::logging::trace;
+
+use crate::{NetworkEnv, exec, network::split_hosts};
+
+/// Returns the proxy configuration from the environment, or from the GNOME proxy settings if no
+/// proxy variables are set.
+pub fn network_env() -> NetworkEnv {
+    let from_env = NetworkEnv::from_env(|name| env::var(name).ok());
+    if !from_env.is_direct() {
+        return from_env;
+    }
+
+    match exec::run("gsettings", &["list-recursively", "org.gnome.system.proxy"]) {
+        Ok(output) if output.status.success() => parse_gsettings(&String::from_utf8_lossy(&output.stdout)),
+        Ok(_) => from_env,
+        Err(e) => {
+            trace!("Unable to run gsettings: {:?}", e);
+            from_env
+        }
+    }
+}
+
+/// Parses the output of `gsettings list-recursively org.gnome.system.proxy`, e.g.
+/// "org.gnome.system.proxy mode 'manual'\norg.gnome.system.proxy.http host 'proxy'\n...".
+fn parse_gsettings(settings: &str) -> NetworkEnv {
+    let value = |schema: &str, key: &str| {
+        settings.lines().find_map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            (parts.next()? == schema && parts.next()? == key).then(|| parts.next().unwrap_or_default().trim().to_string())
+        })
+    };
+    let string = |schema: &str, key: &str| {
+        value(schema, key)
+            .map(|value| value.trim_matches('\'').to_string())
+            .filter(|value| !value.is_empty())
+    };
+    let proxy = |schema: &str| {
+        let host = string(schema, "host")?;
+        let port = value(schema, "port").unwrap_or_default();
+        Some(match port.as_str() {
+            "" | "0" => format!("http://{host}"),
+            port => format!("http://{host}:{port}"),
+        })
+    };
+
+    match string("org.gnome.system.proxy", "mode").as_deref() {
+        Some("manual") => NetworkEnv {
+            http_proxy: proxy("org.gnome.system.proxy.http"),
+            https_proxy: proxy("org.gnome.system.proxy.https"),
+            // A GVariant string array, e.g. "['localhost', '127.0.0.0/8', '::1']".
+            no_proxy: value("org.gnome.system.proxy", "ignore-hosts")
+                .map(|hosts| split_hosts(&hosts.replace(['[', ']', '\''], ""), ','))
+                .unwrap_or_default(),
+            auto_config_url: None,
+        },
+        Some("auto") => NetworkEnv {
+            auto_config_url: string("org.gnome.system.proxy", "autoconfig-url"),
+            ..Default::default()
+        },
+        _ => NetworkEnv::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn manual() {
+        let settings = "org.gnome.system.proxy autoconfig-url ''\n\
+            org.gnome.system.proxy ignore-hosts ['localhost', '127.0.0.0/8', '::1']\n\
+            org.gnome.system.proxy mode 'manual'\n\
+            org.gnome.system.proxy.http host 'proxy.example.com'\n\
+            org.gnome.system.proxy.http port 3128\n\
+            org.gnome.system.proxy.https host 'proxy.example.com'\n\
+            org.gnome.system.proxy.https port 0\n";
+        let expected = NetworkEnv {
+            http_proxy: Some(String::from("http://proxy.example.com:3128")),
+            https_proxy: Some(String::from("http://proxy.example.com")),
+            no_proxy: vec![String::from("localhost"), String::from("127.0.0.0/8"), String::from("::1")],
+            auto_config_url: None,
+        };
+        assert_eq!(expected, parse_gsettings(settings));
+    }
+
+    #[test]
+    fn auto() {
+        let settings = "org.gnome.system.proxy autoconfig-url 'http://wpad/wpad.dat'\n\
+            org.gnome.system.proxy mode 'auto'\n\
+            org.gnome.system.proxy.http host 'proxy.example.com'\n";
+        let expected = NetworkEnv {
+            auto_config_url: Some(String::from("http://wpad/wpad.dat")),
+            ..Default::default()
+        };
+        assert_eq!(expected, parse_gsettings(settings));
+        assert_eq!(NetworkEnv::default(), parse_gsettings("org.gnome.system.proxy mode 'none'\n"));
+    }
+}
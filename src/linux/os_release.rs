@@ -1,64 +1,353 @@
 // spell-checker:ignore sles, AOSCOS
 
-use std::{fmt, fs::File, io::Read, path::Path};
+use std::{
+    ffi::OsString,
+    fmt,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+};
 
-use log::{trace, warn};
+use crate::logging::{trace, warn};
 
-use crate::{matcher::Matcher, OSInfo, Version};
+use super::{cloud, suse};
+use crate::{matcher::Matcher, consts, Config, Diagnostic, ExtraDistro, FieldName, OSInfo, Version, DEFAULT_MAX_FILE_SIZE};
 
-pub fn get_os_data() -> Option<OSInfo> {
-    retrieve(&DISTRIBUTIONS, "/")
+/// Maximum number of symbolic links followed to reach a release file.
+const MAX_SYMLINKS: usize = 8;
+
+/// Maximum size of a release file in bytes; larger files are ignored.
+static MAX_FILE_SIZE: AtomicU64 = AtomicU64::new(DEFAULT_MAX_FILE_SIZE);
+
+pub fn set_max_file_size(bytes: u64) {
+    MAX_FILE_SIZE.store(bytes, Ordering::Relaxed);
 }
 
-fn retrieve(distributions: &[ReleaseInfo], root: &str) -> Option<OSInfo> {
-    for release_info in distributions {
-        let path = Path::new(root).join(release_info.path);
-        
-        if !path.exists() {
-            trace!("Path '{}' doesn't exist", release_info.path);
-            continue;
+/// Returns the size limit of the release files: `Config::max_file_size` if set, the process-wide
+/// limit otherwise.
+pub fn max_file_size(config: &Config) -> u64 {
+    config.max_file_size.unwrap_or_else(|| MAX_FILE_SIZE.load(Ordering::Relaxed))
+}
+
+pub fn get_os_data(root: &Path, config: &Config, diagnostics: &mut Vec<Diagnostic>) -> Option<OSInfo> {
+    retrieve(&DISTRIBUTIONS, root, config, diagnostics)
+}
+
+/// Reads the os-release file at `path`, relative to `root`, e.g. the host's os-release exposed
+/// to a sandbox at "run/host/os-release", within the limits of `config`.
+pub fn get_os_data_at(root: &Path, path: &str, config: &Config, diagnostics: &mut Vec<Diagnostic>) -> Option<OSInfo> {
+    // Only the file at `path` is read, not the extra distributions.
+    let config = Config {
+        max_file_size: config.max_file_size,
+        parallel: config.parallel,
+        ..Config::default()
+    };
+    retrieve(&[ReleaseInfo { path, ..OS_RELEASE }], root, &config, diagnostics)
+}
+
+/// Consults all the release files and merges their fields by priority: the first release file
+/// (in `distributions` order) providing a field wins, except for the version, which later files
+/// may refine (e.g. "8.9" from `/etc/redhat-release` for "8" from os-release).
+///
+/// Release files describing a different distribution than the first one found are ignored, as
+/// is `/etc/lsb-release` if the configuration says so. The extra distributions of the
+/// configuration come last.
+fn retrieve(distributions: &[ReleaseInfo], root: &Path, config: &Config, diagnostics: &mut Vec<Diagnostic>) -> Option<OSInfo> {
+    let max_size = max_file_size(config);
+    let release_infos: Vec<&ReleaseInfo> = distributions
+        .iter()
+        .filter(|release_info| config.follow_lsb_release || release_info.path != "etc/lsb-release")
+        .collect();
+    let paths: Vec<PathBuf> = release_infos
+        .iter()
+        .map(|release_info| root.join(release_info.path))
+        .chain(config.extra_distros.iter().map(|distro| root.join(&distro.path)))
+        .collect();
+    let mut files = read_files(root, &paths, max_size, config.parallel).into_iter();
+    let mut result: Option<OSInfo> = None;
+
+    for (release_info, path) in release_infos.into_iter().zip(&paths) {
+        let (file_content, read_diagnostics) = files.next().unwrap_or_default();
+        diagnostics.extend(read_diagnostics);
+        if let Some(file_content) = file_content {
+            add_source(&mut result, path, parse(release_info, &file_content), diagnostics);
         }
+    }
 
-        let mut file = match File::open(&path) {
-            Ok(val) => val,
-            Err(e) => {
-                warn!("Unable to open {:?} file: {:?}", &path, e);
-                continue;
-            }
-        };
+    for (distro, path) in config.extra_distros.iter().zip(&paths[paths.len() - config.extra_distros.len()..]) {
+        let (file_content, read_diagnostics) = files.next().unwrap_or_default();
+        diagnostics.extend(read_diagnostics);
+        if let Some(file_content) = file_content {
+            add_source(&mut result, path, Some(parse_extra(distro, &file_content)), diagnostics);
+        }
+    }
 
-        let mut file_content = String::new();
-        if let Err(e) = file.read_to_string(&mut file_content) {
-            warn!("Unable to read {:?} file: {:?}", &path, e);
-            continue;
+    // Failed to determine os info
+    let mut result = result?;
+    result.variant.get_or_insert_with(|| String::from("client"));
+    Some(result)
+}
+
+/// Reads the release files at `paths` like `read_file`, concurrently if `parallel` is set, e.g.
+/// on network file systems where each access waits for a round trip. Returns the contents and
+/// the diagnostics of each file, in the order of `paths`.
+fn read_files(root: &Path, paths: &[PathBuf], max_size: u64, parallel: bool) -> Vec<(Option<String>, Vec<Diagnostic>)> {
+    let read = |path: &PathBuf| {
+        let mut diagnostics = Vec::new();
+        (read_file(root, path, max_size, &mut diagnostics), diagnostics)
+    };
+    if !parallel {
+        return paths.iter().map(read).collect();
+    }
+
+    thread::scope(|scope| {
+        let handles: Vec<_> = paths.iter().map(|path| scope.spawn(move || read(path))).collect();
+        handles.into_iter().zip(paths).map(|(handle, path)| joined(path, handle.join())).collect()
+    })
+}
+
+/// Returns what the reader thread of the release file at `path` read, or no content and a
+/// diagnostic naming the file if the thread panicked.
+fn joined(path: &Path, result: thread::Result<(Option<String>, Vec<Diagnostic>)>) -> (Option<String>, Vec<Diagnostic>) {
+    result.unwrap_or_else(|_| {
+        warn!("Reading {} panicked", path.display());
+        (None, vec![Diagnostic::source_unreadable(path.display(), "the reader thread panicked")])
+    })
+}
+
+/// Returns the paths of the release files, relative to the root, in priority order.
+#[cfg(feature = "json")]
+pub fn release_files() -> impl Iterator<Item = &'static str> {
+    DISTRIBUTIONS.iter().map(|release_info| release_info.path)
+}
+
+/// Merges the information parsed from the release file at `path` into `result`.
+fn add_source(result: &mut Option<OSInfo>, path: &Path, info: Option<OSInfo>, diagnostics: &mut Vec<Diagnostic>) {
+    // If id is indeterminate, the release file can't be attributed.
+    let Some(mut info) = info else {
+        diagnostics.push(Diagnostic::field_not_found(path.display(), "id"));
+        return;
+    };
+    if info.version == Version::Unknown {
+        diagnostics.push(Diagnostic::field_not_found(path.display(), "version"));
+    }
+
+    match result {
+        None => {
+            diagnostics.push(Diagnostic::source_used(path.display()));
+            info.record_source(&FieldName::ALL, &path.display().to_string());
+            *result = Some(info);
+        }
+        Some(result) if result.id == info.id => {
+            diagnostics.push(Diagnostic::source_used(path.display()));
+            let fields = merge(result, info);
+            result.record_source(&fields, &path.display().to_string());
+        }
+        // Raspberry Pi OS keeps the Debian point release in `/etc/debian_version`.
+        Some(result)
+            if result.id() == consts::RASPBIAN
+                && info.id() == consts::DEBIAN
+                && is_refinement(&result.version, &info.version) =>
+        {
+            diagnostics.push(Diagnostic::source_used(path.display()));
+            result.version = info.version;
+            result.record_source(&[FieldName::Version], &path.display().to_string());
+        }
+        Some(result) => {
+            trace!("Ignoring '{}' describing {:?} instead of {:?}", path.display(), info.id, result.id);
+            let reason = format!("describes {} instead of {}", info.id(), result.id());
+            diagnostics.push(Diagnostic::source_ignored(path.display(), reason));
+        }
+    }
+}
+
+/// Parses the release file of an extra distribution.
+fn parse_extra(distro: &ExtraDistro, file_content: &str) -> OSInfo {
+    OSInfo {
+        id: Some(distro.id.clone()),
+        name: Some(distro.name.clone()),
+        version: distro.version.find(file_content).map(Version::from_string).unwrap_or_default(),
+        codename: distro.codename.as_ref().and_then(|codename| codename.find(file_content)),
+        ..Default::default()
+    }
+}
+
+/// Parses the release file contents, returning `None` if the os id is indeterminate.
+fn parse(release_info: &ReleaseInfo, file_content: &str) -> Option<OSInfo> {
+    Some(OSInfo {
+        id: Some((release_info.id)(file_content)?),
+        name: (release_info.name)(file_content),
+        variant: (release_info.variant)(file_content),
+        version: (release_info.version)(file_content).unwrap_or(Version::Unknown),
+        codename: (release_info.codename)(file_content),
+        lts: (release_info.lts)(file_content),
+        //bitness: Bitness::Unknown,
+        ..Default::default()
+    })
+}
+
+/// Fills the fields missing from `primary` with the ones from the lower priority `secondary`.
+///
+/// Returns the fields taken from `secondary`.
+fn merge(primary: &mut OSInfo, secondary: OSInfo) -> Vec<FieldName> {
+    let mut fields = Vec::new();
+    if primary.version == Version::Unknown || is_refinement(&primary.version, &secondary.version) {
+        primary.version = secondary.version;
+        fields.push(FieldName::Version);
+    }
+
+    let mut fill = |field: &mut Option<String>, value: Option<String>, name: FieldName| {
+        if field.is_none() && value.is_some() {
+            *field = value;
+            fields.push(name);
         }
+    };
+    fill(&mut primary.name, secondary.name, FieldName::Name);
+    fill(&mut primary.variant, secondary.variant, FieldName::Variant);
+    fill(&mut primary.edition, secondary.edition, FieldName::Edition);
+    fill(&mut primary.codename, secondary.codename, FieldName::Codename);
+
+    if !primary.lts && secondary.lts {
+        primary.lts = true;
+        fields.push(FieldName::Lts);
+    }
+    fields
+}
 
-        let id = (release_info.id)(&file_content);
-        let name = (release_info.name)(&file_content);
-        let variant = (release_info.variant)(&file_content);
-        let version = (release_info.version)(&file_content);
-        let codename = (release_info.codename)(&file_content);
-        // If id is indeterminate, try the next release_info
-        if id.is_none() {
+/// Reads the release file at `path`, which must resolve to a regular file inside `root` no
+/// larger than `max_size` bytes.
+fn read_file(root: &Path, path: &Path, max_size: u64, diagnostics: &mut Vec<Diagnostic>) -> Option<String> {
+    let path = match resolve_symlinks(root, path) {
+        Ok(resolved) => resolved,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            trace!("Path '{}' doesn't exist", path.display());
+            diagnostics.push(Diagnostic::source_missing(path.display()));
+            return None;
+        }
+        Err(e) => {
+            warn!("Unable to resolve {:?}: {:?}", path, e);
+            diagnostics.push(Diagnostic::source_unreadable(path.display(), e));
+            return None;
+        }
+    };
+
+    let file = match File::open(&path) {
+        Ok(val) => val,
+        Err(e) => {
+            warn!("Unable to open {:?} file: {:?}", path, e);
+            diagnostics.push(Diagnostic::source_unreadable(path.display(), e));
+            return None;
+        }
+    };
+
+    // Reading a FIFO or a device would block or never end.
+    if !file.metadata().is_ok_and(|metadata| metadata.is_file()) {
+        trace!("Ignoring {:?}, which is not a regular file", path);
+        diagnostics.push(Diagnostic::source_ignored(path.display(), "not a regular file"));
+        return None;
+    }
+
+    let mut file_content = Vec::new();
+    if let Err(e) = file.take(max_size.saturating_add(1)).read_to_end(&mut file_content) {
+        warn!("Unable to read {:?} file: {:?}", path, e);
+        diagnostics.push(Diagnostic::source_unreadable(path.display(), e));
+        return None;
+    }
+    if file_content.len() as u64 > max_size {
+        trace!("Ignoring {:?}, which is larger than {} bytes", path, max_size);
+        diagnostics.push(Diagnostic::source_ignored(path.display(), format!("larger than {max_size} bytes")));
+        return None;
+    }
+
+    // Some vendors ship release files in legacy encodings (e.g. Latin-1 names); the keys are
+    // ASCII, so replacing the invalid bytes keeps the file usable.
+    match String::from_utf8(file_content) {
+        Ok(file_content) => Some(file_content),
+        Err(e) => {
+            trace!("{:?} is not valid UTF-8: {}", path, e.utf8_error());
+            Some(String::from_utf8_lossy(e.as_bytes()).into_owned())
+        }
+    }
+}
+
+/// Follows the symbolic links at `path` and in its parent directories, e.g. `/etc/os-release` ->
+/// `../usr/lib/os-release`, as if `root` were the root directory: absolute link targets are
+/// resolved inside `root` rather than on the running system, and `..` never leads above it. At
+/// most `MAX_SYMLINKS` links are followed.
+fn resolve_symlinks(root: &Path, path: &Path) -> io::Result<PathBuf> {
+    // Names left to resolve, in reverse order, `None` standing for `..`.
+    fn names(path: &Path) -> impl DoubleEndedIterator<Item = Option<OsString>> + '_ {
+        path.components().filter_map(|component| match component {
+            Component::Normal(name) => Some(Some(name.to_os_string())),
+            Component::ParentDir => Some(None),
+            Component::Prefix(_) | Component::RootDir | Component::CurDir => None,
+        })
+    }
+
+    let mut pending: Vec<Option<OsString>> = names(path.strip_prefix(root).unwrap_or(path)).rev().collect();
+    let mut resolved = PathBuf::new();
+    let mut links = 0;
+    while let Some(name) = pending.pop() {
+        let Some(name) = name else {
+            resolved.pop();
+            continue;
+        };
+
+        let candidate = root.join(&resolved).join(&name);
+        if !fs::symlink_metadata(&candidate)?.file_type().is_symlink() {
+            resolved.push(name);
             continue;
         }
 
-        //let version = (release_info.version)(&file_content);
+        links += 1;
+        if links > MAX_SYMLINKS {
+            return Err(io::Error::other("too many levels of symbolic links"));
+        }
+        let target = fs::read_link(&candidate)?;
+        if target.has_root() {
+            resolved.clear();
+        }
+        pending.extend(names(&target).rev());
+    }
+    Ok(root.join(resolved))
+}
 
-        return Some(OSInfo {
-            // Unwrap is OK here because of the `id.is_none()` check above.
-            id: id,
-            name: name,
-            variant: variant,
-            version: version.unwrap_or(Version::Unknown),
-            codename: codename,
-            //bitness: Bitness::Unknown,
-            ..Default::default()
-        });
+/// Returns `true` if `precise` is a more precise form of the `coarse` version, e.g. "22.04.4" for
+/// "22.04" or "8.9" for "8".
+fn is_refinement(coarse: &Version, precise: &Version) -> bool {
+    match (coarse, precise) {
+        (Version::Semantic(major, minor, build, release), Version::Semantic(p_major, p_minor, p_build, p_release)) => {
+            let coarse = [minor, build, release];
+            let precise = [p_minor, p_build, p_release];
+            // Components of the coarse version must match, up to the last one it specifies (the
+            // trailing zeros are missing components), e.g. "15.0.3" isn't refined by "15.7".
+            let specified = coarse.iter().rposition(|c| **c != 0).map_or(0, |last| last + 1);
+            let matching = coarse[..specified] == precise[..specified];
+            major == p_major && matching && precise > coarse
+        }
+        _ => false,
     }
+}
 
-    // Failed to determine os info
-    None
+/// Returns the os id of the distribution described by a `/etc/redhat-release` style file, e.g.
+/// "CentOS Linux release 7.9.2009 (Core)".
+fn redhat_release_id(release: &str) -> Option<String> {
+    const NAMES: [(&str, &str); 7] = [
+        ("Red Hat Enterprise Linux", consts::RHEL),
+        ("CentOS", consts::CENTOS),
+        ("Rocky Linux", consts::ROCKY),
+        ("AlmaLinux", consts::ALMALINUX),
+        ("Fedora", consts::FEDORA),
+        ("Oracle Linux", consts::OL),
+        ("Scientific Linux", consts::SCIENTIFIC),
+    ];
+
+    NAMES
+        .iter()
+        .find(|(name, _)| release.starts_with(name))
+        .map(|(_, id)| id.to_string())
 }
 
 /// Struct containing information on how to parse distribution info from a release file.
@@ -78,6 +367,8 @@ struct ReleaseInfo<'a> {
     variant: for<'b> fn(&'b str) -> Option<String>,
     /// A closure that determines the os codename from the release file contents.
     codename: for<'b> fn(&'b str) -> Option<String>,
+    /// A closure that determines whether the release is a long-term support one.
+    lts: for<'b> fn(&'b str) -> bool,
 }
 
 impl fmt::Debug for ReleaseInfo<'_> {
@@ -89,53 +380,604 @@ impl fmt::Debug for ReleaseInfo<'_> {
             .field("version", &(self.version as fn(&'a str) -> Option<Version>))
             .field("variant", &(self.variant as fn(&'a str) -> Option<String>))
             .field("codename", &(self.codename as fn(&'a str) -> Option<String>))
+            .field("lts", &(self.lts as fn(&'a str) -> bool))
             .finish()
     }
 }
 
-/// List of all supported distributions and the information on how to parse their version from the
-/// release file.
-static DISTRIBUTIONS: [ReleaseInfo; 1] = [
+/// os-release, which is the same file in both of its locations.
+const OS_RELEASE: ReleaseInfo = ReleaseInfo {
+    path: "etc/os-release",
+    id: |release| {
+        Matcher::KeyValue { key: "ID" }
+            .find(release)
+    },
+    name: |name| {
+        Matcher::KeyValue { key: "NAME" }
+            .find(name)
+    },
+    version: |version| {
+        let id = Matcher::KeyValue { key: "ID" }
+            .find(version)
+            .unwrap_or_default();
+        if let Some(version) = suse::version(&id, version).or_else(|| cloud::version(&id, version)) {
+            return Some(version);
+        }
+
+        let version_id = Matcher::KeyValue { key: "VERSION_ID" }
+            .find(version)?;
+        // VERSION may carry the full point release, e.g. "22.04.4 LTS (Jammy Jellyfish)"
+        // while VERSION_ID is only "22.04".
+        let point_release = Matcher::KeyValue { key: "VERSION" }
+            .find(version)
+            .and_then(|v| v.split_whitespace().next().map(str::to_owned))
+            .filter(|v| v.starts_with(&format!("{version_id}.")));
+        Some(Version::from_string(point_release.unwrap_or(version_id)))
+    },
+    variant: |variant| {
+        Matcher::KeyValue { key: "VARIANT_ID" }
+            .find(variant)
+            .or_else(|| {
+                let id = Matcher::KeyValue { key: "ID" }.find(variant)?;
+                suse::variant(&id).map(String::from)
+            })
+    },
+    codename: |codename| {
+        let id = Matcher::KeyValue { key: "ID" }.find(codename).unwrap_or_default();
+        let version_codename = Matcher::KeyValue { key: "VERSION_CODENAME" };
+        if !cloud::has_codename_in_version(&id) {
+            return version_codename.find(codename);
+        }
+        version_codename
+            .or(Matcher::KeyValue { key: "VERSION" }.then(Matcher::Between { start: '(', end: ')' }))
+            .find(codename)
+    },
+    lts: |release| {
+        Matcher::KeyValue { key: "VERSION" }
+            .find(release)
+            .is_some_and(|v| v.split_whitespace().any(|word| word == "LTS"))
+    },
+};
+
+/// List of all supported release files and the information on how to parse them, in priority
+/// order.
+static DISTRIBUTIONS: [ReleaseInfo; 7] = [
     // Keep this first; most modern distributions have this file.
+    OS_RELEASE,
     ReleaseInfo {
-        path: "etc/os-release",
+        path: "usr/lib/os-release",
+        ..OS_RELEASE
+    },
+    ReleaseInfo {
+        path: "etc/lsb-release",
         id: |release| {
-            Matcher::KeyValue { key: "ID" }
+            Matcher::KeyValue { key: "DISTRIB_ID" }
+                .map(|id| id.to_lowercase())
+                .find(release)
+        },
+        name: |release| {
+            Matcher::KeyValue { key: "DISTRIB_ID" }
+                .find(release)
+        },
+        version: |release| {
+            Matcher::KeyValue { key: "DISTRIB_RELEASE" }
+                .find(release)
+                .map(Version::from_string)
+        },
+        variant: |_| None,
+        codename: |release| {
+            Matcher::KeyValue { key: "DISTRIB_CODENAME" }
+                .find(release)
+        },
+        lts: |release| {
+            Matcher::KeyValue { key: "DISTRIB_DESCRIPTION" }
+                .find(release)
+                .is_some_and(|v| v.split_whitespace().any(|word| word == "LTS"))
+        },
+    },
+    // os-release may only report the major version, e.g. "8" while this has "8.9".
+    ReleaseInfo {
+        path: "etc/redhat-release",
+        id: redhat_release_id,
+        name: |release| {
+            release
+                .split(" release")
+                .next()
+                .map(|name| name.trim().to_string())
+        },
+        version: |release| {
+            Matcher::PrefixedVersion { prefix: "release" }
+                .find(release)
+                .map(Version::from_string)
+        },
+        variant: |_| None,
+        codename: |release| {
+            Matcher::Between { start: '(', end: ')' }
                 .find(release)
         },
-        name: |name| {
-            Matcher::KeyValue { key: "NAME" }
-                .find(name)
+        lts: |_| false,
+    },
+    // Amazon Linux's os-release only carries the major version (e.g. "2023"), while this has the
+    // full release (e.g. "2023.4.20240319") and Amazon Linux 2's codename.
+    ReleaseInfo {
+        path: "etc/system-release",
+        id: cloud::system_release_id,
+        name: |release| {
+            release
+                .split(" release")
+                .next()
+                .map(|name| name.trim().to_string())
         },
-        version: |version| {
-            Matcher::KeyValue { key: "VERSION_ID" }
-                .find(version)
+        version: |release| {
+            Matcher::PrefixedVersion { prefix: "release" }
+                .find(release)
                 .map(Version::from_string)
         },
-        variant: |variant| {
-            Matcher::KeyValue { key: "VARIANT_ID" }
-                .find(variant)
-                .map(|v| v.to_string())
-                .or_else(|| {Some("client".to_string())})
+        variant: |_| None,
+        // Amazon Linux 2023 repeats its name instead of a codename.
+        codename: |release| {
+            Matcher::Between { start: '(', end: ')' }
+                .find(release)
+                .filter(|codename| !codename.starts_with("Amazon Linux"))
         },
-        codename: |codename| {
-            let version_codename = Matcher::KeyValue { key: "VERSION_CODENAME" }
-                .find(codename);
-            match version_codename {
-                Some(v) => Some(v.to_string()),
-                None => {
-                    let version = Matcher::KeyValue { key: "VERSION" }
-                        .find(codename);
-                    if let Some(v) = version {
-                        Matcher::Between { start: '(', end: ')' }
-                            .find(&v)
-                            .map(|v| v.to_string())
-                    } else {
-                        None
-                    }
-                }
-            } 
+        lts: |_| false,
+    },
+    // Debian's os-release only carries the major version, while this has the point release
+    // (e.g. "12.5"). On testing/unstable os-release has no version at all and this contains the
+    // codename instead (e.g. "trixie/sid").
+    ReleaseInfo {
+        path: "etc/debian_version",
+        id: |_| Some(String::from(consts::DEBIAN)),
+        name: |_| None,
+        version: |release| {
+            match release.trim().split_once('/') {
+                Some((_, suite)) => Some(Version::Rolling(Some(suite.to_string()))),
+                None => Some(Version::from_string(release.trim())),
+            }
+        },
+        variant: |_| None,
+        codename: |release| {
+            release
+                .trim()
+                .split_once('/')
+                .map(|(codename, _)| codename.to_string())
+                .filter(|codename| !codename.is_empty())
+        },
+        lts: |_| false,
+    },
+    ReleaseInfo {
+        path: "etc/alpine-release",
+        id: |_| Some(String::from(consts::ALPINE)),
+        name: |_| Some(String::from("Alpine Linux")),
+        version: |release| {
+            Matcher::AllTrimmed
+                .find(release)
+                .map(Version::from_string)
         },
+        variant: |_| None,
+        codename: |_| None,
+        lts: |_| false,
     },
 ];
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+    use std::fs;
+
+    fn retrieve_from(root: &Path) -> OSInfo {
+        let info = retrieve(&DISTRIBUTIONS, root, &Config::default(), &mut Vec::new());
+        fs::remove_dir_all(root).unwrap();
+        info.unwrap()
+    }
+
+    #[test]
+    fn ubuntu_point_release() {
+        let root = root_with("ubuntu", &[(
+            "etc/os-release",
+            "NAME=\"Ubuntu\"\nVERSION_ID=\"22.04\"\nVERSION=\"22.04.4 LTS (Jammy Jellyfish)\"\nID=ubuntu\nVERSION_CODENAME=jammy\n",
+        )]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("ubuntu", info.id());
+        assert_eq!(&Version::Semantic(22, 4, 4, 0), info.version());
+        assert_eq!(Some("jammy"), info.codename());
+        assert!(info.is_lts());
+    }
+
+    #[test]
+    fn ubuntu_interim_release() {
+        let root = root_with("ubuntu-interim", &[(
+            "etc/os-release",
+            "NAME=\"Ubuntu\"\nVERSION_ID=\"23.10\"\nVERSION=\"23.10 (Mantic Minotaur)\"\nID=ubuntu\n",
+        )]);
+
+        let info = retrieve_from(&root);
+        assert_eq!(&Version::Semantic(23, 10, 0, 0), info.version());
+        assert_eq!(Some("Mantic Minotaur"), info.codename());
+        assert!(!info.is_lts());
+    }
+
+    #[test]
+    fn debian_point_release() {
+        let root = root_with("debian", &[
+            (
+                "etc/os-release",
+                "PRETTY_NAME=\"Debian GNU/Linux 12 (bookworm)\"\nNAME=\"Debian GNU/Linux\"\nVERSION_ID=\"12\"\nVERSION=\"12 (bookworm)\"\nVERSION_CODENAME=bookworm\nID=debian\n",
+            ),
+            ("etc/debian_version", "12.5\n"),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("debian", info.id());
+        assert_eq!(&Version::Semantic(12, 5, 0, 0), info.version());
+        assert_eq!(Some("bookworm"), info.codename());
+        assert!(!info.is_lts());
+    }
+
+    #[test]
+    fn raspbian_point_release() {
+        let root = root_with("raspbian", &[
+            (
+                "etc/os-release",
+                "PRETTY_NAME=\"Raspbian GNU/Linux 12 (bookworm)\"\nNAME=\"Raspbian GNU/Linux\"\nVERSION_ID=\"12\"\nVERSION_CODENAME=bookworm\nID=raspbian\nID_LIKE=debian\n",
+            ),
+            ("etc/debian_version", "12.5\n"),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("raspbian", info.id());
+        assert_eq!(&Version::Semantic(12, 5, 0, 0), info.version());
+        assert_eq!("Raspbian GNU/Linux", info.name());
+        assert_eq!(
+            Some(root.join("etc/debian_version").display().to_string().as_str()),
+            info.source_of(FieldName::Version)
+        );
+
+        // Another major version isn't a point release of this one.
+        let root = root_with("raspbian-mismatch", &[
+            ("etc/os-release", "NAME=\"Raspbian GNU/Linux\"\nVERSION_ID=\"12\"\nID=raspbian\n"),
+            ("etc/debian_version", "11.9\n"),
+        ]);
+        assert_eq!(&Version::Semantic(12, 0, 0, 0), retrieve_from(&root).version());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn absolute_symlink() {
+        use std::os::unix::fs::symlink;
+
+        let root = root_with("symlink-absolute", &[("usr/lib/os-release", "ID=fedora\nVERSION_ID=40\n")]);
+        fs::create_dir_all(root.join("etc")).unwrap();
+        // Must resolve to the image's file, not the running system's.
+        symlink("/usr/lib/os-release", root.join("etc/os-release")).unwrap();
+
+        let info = retrieve_from(&root);
+        assert_eq!("fedora", info.id());
+        assert_eq!(&Version::Semantic(40, 0, 0, 0), info.version());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn escaping_symlinks() {
+        use std::os::unix::fs::symlink;
+
+        let root = root_with("symlink-escaping", &[("usr/lib/os-release", "ID=fedora\nVERSION_ID=40\n")]);
+        fs::create_dir_all(root.join("etc")).unwrap();
+        // `..` stops at the root, like it does at the root directory of the running system.
+        symlink("/../../usr/lib/os-release", root.join("etc/os-release")).unwrap();
+        symlink("../../../../../../../usr/lib/os-release", root.join("etc/lsb-release")).unwrap();
+
+        let mut diagnostics = Vec::new();
+        for file in ["etc/os-release", "etc/lsb-release"] {
+            let content = read_file(&root, &root.join(file), DEFAULT_MAX_FILE_SIZE, &mut diagnostics);
+            assert_eq!(Some("ID=fedora\nVERSION_ID=40\n"), content.as_deref(), "{file}");
+        }
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_directory() {
+        use std::os::unix::fs::symlink;
+
+        let root = root_with("symlink-directory", &[("usr/lib/os-release", "ID=fedora\nVERSION_ID=40\n")]);
+        // Must resolve to the image's directory, not the running system's `/usr/lib`.
+        symlink("/usr/lib", root.join("etc")).unwrap();
+
+        let info = retrieve_from(&root);
+        assert_eq!("fedora", info.id());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_loop() {
+        use std::os::unix::fs::symlink;
+
+        let root = root_with("symlink-loop", &[]);
+        fs::create_dir_all(root.join("etc")).unwrap();
+        symlink("os-release", root.join("etc/os-release")).unwrap();
+
+        let path = root.join("etc/os-release");
+        let mut diagnostics = Vec::new();
+        assert_eq!(None, read_file(&root, &path, DEFAULT_MAX_FILE_SIZE, &mut diagnostics));
+        assert!(matches!(diagnostics[..], [Diagnostic::SourceUnreadable { .. }]));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn limits() {
+        let large = format!("ID=large\n{}", "#".repeat(DEFAULT_MAX_FILE_SIZE as usize));
+        let root = root_with("limits", &[("etc/os-release", &large), ("etc/lsb-release/DISTRIB_ID", "dir")]);
+
+        let mut diagnostics = Vec::new();
+        assert_eq!(None, read_file(&root, &root.join("etc/os-release"), DEFAULT_MAX_FILE_SIZE, &mut diagnostics));
+        assert_eq!(None, read_file(&root, &root.join("etc/lsb-release"), DEFAULT_MAX_FILE_SIZE, &mut diagnostics));
+        let reasons: Vec<_> = diagnostics
+            .iter()
+            .map(|diagnostic| match diagnostic {
+                Diagnostic::SourceIgnored { reason, .. } => reason.as_str(),
+                _ => "",
+            })
+            .collect();
+        assert_eq!(vec!["larger than 65536 bytes", "not a regular file"], reasons);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let root = root_with("invalid-utf8", &[]);
+        fs::create_dir_all(root.join("etc")).unwrap();
+        fs::write(root.join("etc/os-release"), b"NAME=\"Distribuci\xf3n\"\nID=distro\nVERSION_ID=3.1\n").unwrap();
+
+        let info = retrieve_from(&root);
+        assert_eq!("distro", info.id());
+        assert_eq!("Distribuci\u{FFFD}n", info.name());
+        assert_eq!(&Version::Semantic(3, 1, 0, 0), info.version());
+    }
+
+    #[test]
+    fn debian_testing() {
+        let root = root_with("debian-testing", &[
+            (
+                "etc/os-release",
+                "PRETTY_NAME=\"Debian GNU/Linux trixie/sid\"\nNAME=\"Debian GNU/Linux\"\nID=debian\n",
+            ),
+            ("etc/debian_version", "trixie/sid\n"),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("debian", info.id());
+        assert_eq!(&Version::Rolling(Some("sid".to_owned())), info.version());
+        assert_eq!(Some("trixie"), info.codename());
+    }
+
+    #[test]
+    fn rhel_minor_version() {
+        let root = root_with("rhel", &[
+            (
+                "etc/os-release",
+                "NAME=\"Red Hat Enterprise Linux\"\nVERSION=\"8 (Ootpa)\"\nID=\"rhel\"\nVERSION_ID=\"8\"\n",
+            ),
+            ("etc/redhat-release", "Red Hat Enterprise Linux release 8.9 (Ootpa)\n"),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("rhel", info.id());
+        assert_eq!(&Version::Semantic(8, 9, 0, 0), info.version());
+        assert_eq!(Some("Ootpa"), info.codename());
+    }
+
+    #[test]
+    fn centos_build_version() {
+        let root = root_with("centos", &[
+            (
+                "etc/os-release",
+                "NAME=\"CentOS Linux\"\nVERSION=\"7 (Core)\"\nID=\"centos\"\nVERSION_ID=\"7\"\n",
+            ),
+            ("etc/redhat-release", "CentOS Linux release 7.9.2009 (Core)\n"),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!(&Version::Semantic(7, 9, 2009, 0), info.version());
+    }
+
+    #[test]
+    fn unrelated_secondary_file_is_ignored() {
+        let root = root_with("stale", &[
+            ("etc/os-release", "NAME=\"Ubuntu\"\nID=ubuntu\nVERSION_ID=\"22.04\"\n"),
+            ("etc/redhat-release", "CentOS Linux release 7.9.2009 (Core)\n"),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("ubuntu", info.id());
+        assert_eq!(&Version::Semantic(22, 4, 0, 0), info.version());
+        assert_eq!(None, info.codename());
+    }
+
+    #[test]
+    fn lsb_release_codename() {
+        let root = root_with("lsb", &[
+            ("etc/os-release", "NAME=\"Linux Mint\"\nID=linuxmint\nVERSION_ID=\"21.3\"\n"),
+            (
+                "etc/lsb-release",
+                "DISTRIB_ID=LinuxMint\nDISTRIB_RELEASE=21.3\nDISTRIB_CODENAME=virginia\nDISTRIB_DESCRIPTION=\"Linux Mint 21.3 Virginia\"\n",
+            ),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("linuxmint", info.id());
+        assert_eq!("Linux Mint", info.name());
+        assert_eq!(&Version::Semantic(21, 3, 0, 0), info.version());
+        assert_eq!(Some("virginia"), info.codename());
+        assert_eq!(Some("client"), info.variant());
+    }
+
+    #[test]
+    fn config() {
+        let root = root_with("config", &[
+            ("etc/lsb-release", "DISTRIB_ID=Ubuntu\nDISTRIB_RELEASE=22.04\n"),
+            ("etc/acme-release", "Acme Linux release 4.2 (Roadrunner)\n"),
+        ]);
+
+        let config = Config {
+            follow_lsb_release: false,
+            extra_distros: vec![ExtraDistro {
+                codename: Some(Matcher::Between { start: '(', end: ')' }),
+                ..ExtraDistro::new("etc/acme-release", "acme", "Acme Linux", Matcher::PrefixedVersion { prefix: "release" })
+            }],
+            ..Default::default()
+        };
+        let info = retrieve(&DISTRIBUTIONS, &root, &config, &mut Vec::new()).unwrap();
+        assert_eq!("acme", info.id());
+        assert_eq!("Acme Linux", info.name());
+        assert_eq!(&Version::Semantic(4, 2, 0, 0), info.version());
+        assert_eq!(Some("Roadrunner"), info.codename());
+
+        let config = Config {
+            max_file_size: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(None, retrieve(&DISTRIBUTIONS, &root, &config, &mut Vec::new()));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn max_file_size_precedence() {
+        // Only raise the process-wide limit, so that concurrent tests still read their files.
+        set_max_file_size(2 * DEFAULT_MAX_FILE_SIZE);
+        let config = Config {
+            max_file_size: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(8, max_file_size(&config));
+        assert_eq!(2 * DEFAULT_MAX_FILE_SIZE, max_file_size(&Config::default()));
+        set_max_file_size(DEFAULT_MAX_FILE_SIZE);
+    }
+
+    #[test]
+    fn provenance() {
+        let root = root_with("provenance", &[
+            ("etc/os-release", "NAME=\"Red Hat Enterprise Linux\"\nID=\"rhel\"\nVERSION_ID=\"8\"\n"),
+            ("etc/redhat-release", "Red Hat Enterprise Linux release 8.9 (Ootpa)\n"),
+        ]);
+        let path = |p: &str| root.join(p).display().to_string();
+
+        let info = retrieve(&DISTRIBUTIONS, &root, &Config::default(), &mut Vec::new()).unwrap();
+        assert_eq!(
+            vec![
+                (FieldName::Id, path("etc/os-release")),
+                (FieldName::Name, path("etc/os-release")),
+                (FieldName::Version, path("etc/redhat-release")),
+                (FieldName::Codename, path("etc/redhat-release")),
+            ],
+            info.provenance()
+        );
+        assert_eq!(None, info.source_of(FieldName::Variant));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn legacy_files_only() {
+        let root = root_with("legacy", &[
+            ("etc/redhat-release", "CentOS release 6.10 (Final)\n"),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("centos", info.id());
+        assert_eq!("CentOS", info.name());
+        assert_eq!(&Version::Semantic(6, 10, 0, 0), info.version());
+        assert_eq!(Some("Final"), info.codename());
+    }
+
+    #[test]
+    fn usr_lib_os_release() {
+        let root = root_with("usr-lib", &[
+            ("usr/lib/os-release", "NAME=\"Arch Linux\"\nID=arch\nBUILD_ID=rolling\n"),
+        ]);
+
+        let info = retrieve_from(&root);
+        assert_eq!("arch", info.id());
+        assert_eq!("Arch Linux", info.name());
+    }
+
+    #[test]
+    fn diagnostics() {
+        let root = root_with("diagnostics", &[
+            ("etc/os-release", "NAME=\"Ubuntu\"\nID=ubuntu\n"),
+            ("etc/redhat-release", "CentOS Linux release 7.9.2009 (Core)\n"),
+            ("etc/alpine-release", ""),
+        ]);
+
+        let mut diagnostics = Vec::new();
+        let info = retrieve(&DISTRIBUTIONS, &root, &Config::default(), &mut diagnostics).unwrap();
+        assert_eq!(&Version::Unknown, info.version());
+
+        let path = |p: &str| root.join(p).display().to_string();
+        assert_eq!(
+            vec![
+                Diagnostic::field_not_found(path("etc/os-release"), "version"),
+                Diagnostic::source_used(path("etc/os-release")),
+                Diagnostic::source_missing(path("usr/lib/os-release")),
+                Diagnostic::source_missing(path("etc/lsb-release")),
+                Diagnostic::source_ignored(path("etc/redhat-release"), "describes centos instead of ubuntu"),
+                Diagnostic::source_missing(path("etc/system-release")),
+                Diagnostic::source_missing(path("etc/debian_version")),
+                Diagnostic::field_not_found(path("etc/alpine-release"), "version"),
+                Diagnostic::source_ignored(path("etc/alpine-release"), "describes alpine instead of ubuntu"),
+            ],
+            diagnostics
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn parallel() {
+        let root = root_with("parallel", &[
+            ("etc/os-release", "NAME=\"Red Hat Enterprise Linux\"\nID=\"rhel\"\nVERSION_ID=\"8\"\n"),
+            ("etc/redhat-release", "Red Hat Enterprise Linux release 8.9 (Ootpa)\n"),
+            ("etc/alpine-release", ""),
+        ]);
+
+        let mut sequential = Vec::new();
+        let expected = retrieve(&DISTRIBUTIONS, &root, &Config::default(), &mut sequential);
+        let mut diagnostics = Vec::new();
+        let config = Config {
+            parallel: true,
+            ..Default::default()
+        };
+        assert_eq!(expected, retrieve(&DISTRIBUTIONS, &root, &config, &mut diagnostics));
+        assert_eq!(&Version::Semantic(8, 9, 0, 0), expected.unwrap().version());
+        assert_eq!(sequential, diagnostics);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn panicked_reader() {
+        let path = Path::new("/etc/os-release");
+        let (content, diagnostics) = joined(path, Err(Box::new("boom")));
+        assert_eq!(None, content);
+        assert_eq!(vec![Diagnostic::source_unreadable("/etc/os-release", "the reader thread panicked")], diagnostics);
+    }
+
+    #[test]
+    fn refinement() {
+        let data = [
+            (Version::Semantic(22, 4, 0, 0), Version::Semantic(22, 4, 4, 0), true),
+            (Version::Semantic(8, 0, 0, 0), Version::Semantic(8, 9, 0, 0), true),
+            (Version::Semantic(8, 0, 0, 0), Version::Semantic(8, 0, 0, 0), false),
+            (Version::Semantic(8, 2, 0, 0), Version::Semantic(8, 9, 0, 0), false),
+            (Version::Semantic(12, 0, 0, 0), Version::Semantic(11, 9, 0, 0), false),
+            (Version::Semantic(15, 0, 3, 0), Version::Semantic(15, 7, 0, 0), false),
+            (Version::Semantic(15, 0, 3, 0), Version::Semantic(15, 0, 3, 1), true),
+            (Version::Unknown, Version::Semantic(12, 5, 0, 0), false),
+            (Version::Semantic(12, 0, 0, 0), Version::Custom("trixie/sid".to_owned()), false),
+        ];
+
+        for (coarse, precise, expected) in &data {
+            assert_eq!(*expected, is_refinement(coarse, precise));
+        }
+    }
+}
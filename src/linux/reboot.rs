@@ -0,0 +1,114 @@
+use std::{fs, path::Path};
+
+use crate::logging::trace;
+
+use super::host;
+use crate::exec;
+
+/// Returns whether a reboot is pending according to the files under `root`, or `None` if the
+/// distribution doesn't record it.
+pub fn pending_reboot(root: &Path) -> Option<bool> {
+    // Created by update-notifier and unattended-upgrades on Debian and Ubuntu.
+    if root.join("run/reboot-required").exists() || root.join("var/run/reboot-required").exists() {
+        trace!("Reboot pending: reboot-required exists");
+        return Some(true);
+    }
+
+    // Package managers remove the modules of uninstalled kernels, including the running one
+    // after a kernel update on most distributions. Containers run the host's kernel, and their
+    // images often ship an empty `lib/modules`, so only another installed kernel counts.
+    let release = fs::read_to_string(root.join("proc/sys/kernel/osrelease")).unwrap_or_default();
+    let release = release.trim();
+    if !release.is_empty()
+        && !host::container(root)
+        && let Ok(entries) = fs::read_dir(root.join("lib/modules"))
+    {
+        let kernels: Vec<_> = entries
+            .filter_map(Result::ok)
+            .filter(|entry| entry.path().is_dir())
+            .map(|entry| entry.file_name())
+            .collect();
+        if !kernels.is_empty() && !kernels.iter().any(|kernel| *kernel == *release) {
+            trace!("Reboot pending: modules of the running kernel {} were removed", release);
+            return Some(true);
+        }
+    }
+
+    root.join("var/lib/dpkg").exists().then_some(false)
+}
+
+/// Asks `needs-restarting` (from dnf-utils / yum-utils on Red Hat based distributions) whether
+/// core libraries or the kernel were updated since the boot.
+pub fn needs_restarting() -> Option<bool> {
+    match exec::run("needs-restarting", &["-r"]) {
+        // Exits with 1 if a reboot is required.
+        Ok(output) => match output.status.code() {
+            Some(0) => Some(false),
+            Some(1) => Some(true),
+            code => {
+                trace!("needs-restarting exited with {:?}", code);
+                None
+            }
+        },
+        Err(e) => {
+            trace!("Unable to run needs-restarting: {:?}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn debian() {
+        let root = root_with("reboot-debian", &[
+            ("run/reboot-required", "*** System restart required ***\n"),
+            ("var/lib/dpkg/status", ""),
+        ]);
+        assert_eq!(Some(true), pending_reboot(&root));
+        fs::remove_file(root.join("run/reboot-required")).unwrap();
+        assert_eq!(Some(false), pending_reboot(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn kernel_update() {
+        let root = root_with("reboot-kernel", &[
+            ("proc/sys/kernel/osrelease", "5.14.0-362.8.1.el9_3.x86_64\n"),
+            ("lib/modules/5.14.0-362.13.1.el9_3.x86_64/modules.dep", ""),
+        ]);
+        assert_eq!(Some(true), pending_reboot(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn container_modules() {
+        // Container images ship an empty `lib/modules`, and run the host's kernel.
+        let root = root_with("reboot-empty-modules", &[("proc/sys/kernel/osrelease", "6.8.9-arch1-1\n")]);
+        fs::create_dir_all(root.join("lib/modules")).unwrap();
+        assert_eq!(None, pending_reboot(&root));
+        fs::remove_dir_all(root).unwrap();
+
+        let root = root_with("reboot-container", &[
+            ("run/.containerenv", ""),
+            ("proc/sys/kernel/osrelease", "6.8.9-arch1-1\n"),
+            ("lib/modules/5.14.0-362.13.1.el9_3.x86_64/modules.dep", ""),
+        ]);
+        assert_eq!(None, pending_reboot(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn unknown() {
+        let root = root_with("reboot-unknown", &[
+            ("proc/sys/kernel/osrelease", "5.14.0-362.8.1.el9_3.x86_64\n"),
+            ("lib/modules/5.14.0-362.8.1.el9_3.x86_64/modules.dep", ""),
+        ]);
+        assert_eq!(None, pending_reboot(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
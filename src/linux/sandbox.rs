@@ -0,0 +1,157 @@
+// spell-checker:ignore snapd, hostfs
+
+use std::{fs, path::Path};
+
+use crate::logging::trace;
+
+use super::{host, os_release};
+use crate::{Config, HostOsRelease, Sandbox, SandboxKind};
+
+/// Description of the Flatpak instance, relative to the root.
+const FLATPAK_INFO_PATH: &str = ".flatpak-info";
+
+/// Host os-release files exposed to strictly confined snaps.
+const SNAP_HOST_PATHS: [&str; 2] = ["var/lib/snapd/hostfs/etc/os-release", "var/lib/snapd/hostfs/usr/lib/os-release"];
+
+/// Detects the Flatpak or Snap sandbox of the process given a lookup of environment variables.
+/// The host os-release is read within the limits of `config`, unless its `host_os_release` is
+/// `HostOsRelease::Ignore`.
+pub fn get_sandbox(root: &Path, config: &Config, var: impl Fn(&str) -> Option<String>) -> Option<Sandbox> {
+    let var = |name: &str| var(name).filter(|value| !value.is_empty());
+    let read_host = config.host_os_release != HostOsRelease::Ignore;
+
+    let flatpak_info = fs::read_to_string(root.join(FLATPAK_INFO_PATH)).ok();
+    if flatpak_info.is_some() || var("FLATPAK_ID").is_some() {
+        let flatpak_info = flatpak_info.unwrap_or_default();
+        trace!("Running in a Flatpak sandbox");
+        return Some(Sandbox {
+            app_id: var("FLATPAK_ID").or_else(|| ini_value(&flatpak_info, "Application", "name")),
+            runtime: ini_value(&flatpak_info, "Application", "runtime")
+                .map(|runtime| runtime.strip_prefix("runtime/").map(String::from).unwrap_or(runtime)),
+            host: read_host.then(|| host::get_host_os_release(root, config)).flatten(),
+            ..Sandbox::new(SandboxKind::Flatpak)
+        });
+    }
+
+    // Snapcraft sets `SNAP` for the commands it builds too, so the snap name is required.
+    if let (Some(snap), Some(name)) = (var("SNAP"), var("SNAP_NAME")) {
+        trace!("Running in snap {:?}", name);
+        let snap_yaml = fs::read_to_string(root.join(snap.trim_start_matches('/')).join("meta/snap.yaml")).ok();
+        return Some(Sandbox {
+            app_id: var("SNAP_INSTANCE_NAME").or(Some(name)),
+            runtime: snap_yaml.map(|snap_yaml| snap_base(&snap_yaml)),
+            host: SNAP_HOST_PATHS
+                .iter()
+                .filter(|_| read_host)
+                .find_map(|path| os_release::get_os_data_at(root, path, config, &mut Vec::new())),
+            ..Sandbox::new(SandboxKind::Snap)
+        });
+    }
+
+    None
+}
+
+/// Returns the value of `key` in `section` of an INI style keyfile such as `.flatpak-info`.
+fn ini_value(content: &str, section: &str, key: &str) -> Option<String> {
+    let mut in_section = false;
+    for line in content.lines().map(str::trim) {
+        if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+            in_section = name == section;
+        } else if in_section
+            && let Some((name, value)) = line.split_once('=')
+            && name.trim() == key
+        {
+            return Some(value.trim().to_string()).filter(|value| !value.is_empty());
+        }
+    }
+    None
+}
+
+/// Returns the base snap declared in `snap.yaml`. Snaps without one run on the original "core"
+/// base.
+fn snap_base(snap_yaml: &str) -> String {
+    snap_yaml
+        .lines()
+        .find_map(|line| line.strip_prefix("base:"))
+        .map(|base| base.trim().trim_matches(|c| c == '"' || c == '\'').to_string())
+        .filter(|base| !base.is_empty())
+        .unwrap_or_else(|| String::from("core"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    fn env(vars: &'static [(&'static str, &'static str)]) -> impl Fn(&str) -> Option<String> {
+        |name| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+    }
+
+    #[test]
+    fn flatpak() {
+        let root = root_with("sandbox-flatpak", &[
+            (
+                FLATPAK_INFO_PATH,
+                "[Application]\nname=org.mozilla.firefox\nruntime=runtime/org.freedesktop.Platform/x86_64/23.08\n\n[Instance]\ninstance-id=1234\n",
+            ),
+            ("etc/os-release", "ID=org.freedesktop.platform\nNAME=\"Freedesktop SDK\"\nVERSION_ID=\"23.08\"\n"),
+            ("run/host/os-release", "ID=ubuntu\nNAME=\"Ubuntu\"\nVERSION_ID=\"24.04\"\n"),
+        ]);
+
+        let sandbox = get_sandbox(&root, &Config::default(), env(&[])).unwrap();
+        assert_eq!(SandboxKind::Flatpak, sandbox.kind);
+        assert_eq!(Some("org.mozilla.firefox"), sandbox.app_id.as_deref());
+        assert_eq!(Some("org.freedesktop.Platform/x86_64/23.08"), sandbox.runtime.as_deref());
+        let host = sandbox.host.unwrap();
+        assert_eq!("ubuntu", host.id());
+        assert_eq!(&crate::Version::Semantic(24, 4, 0, 0), host.version());
+
+        let ignore = Config {
+            host_os_release: HostOsRelease::Ignore,
+            ..Config::default()
+        };
+        assert_eq!(None, get_sandbox(&root, &ignore, env(&[])).unwrap().host);
+        let limited = Config {
+            max_file_size: Some(8),
+            ..Config::default()
+        };
+        assert_eq!(None, get_sandbox(&root, &limited, env(&[])).unwrap().host);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn snap() {
+        let root = root_with("sandbox-snap", &[
+            ("snap/firefox/4136/meta/snap.yaml", "name: firefox\nversion: 124.0\nbase: core22\n"),
+            ("var/lib/snapd/hostfs/etc/os-release", "ID=fedora\nNAME=\"Fedora Linux\"\nVERSION_ID=40\n"),
+        ]);
+        let vars = env(&[("SNAP", "/snap/firefox/4136"), ("SNAP_NAME", "firefox"), ("SNAP_INSTANCE_NAME", "firefox_beta")]);
+
+        let sandbox = get_sandbox(&root, &Config::default(), vars).unwrap();
+        assert_eq!(SandboxKind::Snap, sandbox.kind);
+        assert_eq!(Some("firefox_beta"), sandbox.app_id.as_deref());
+        assert_eq!(Some("core22"), sandbox.runtime.as_deref());
+        assert_eq!("fedora", sandbox.host.unwrap().id());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn unsandboxed() {
+        let root = root_with("sandbox-none", &[("etc/os-release", "ID=debian\n")]);
+        assert_eq!(None, get_sandbox(&root, &Config::default(), env(&[])));
+        // Snapcraft build environments only set `SNAP`.
+        assert_eq!(None, get_sandbox(&root, &Config::default(), env(&[("SNAP", "/root/parts/app/install")])));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn keyfile_and_snap_yaml() {
+        let info = "[Runtime]\nname=org.gnome.Platform\n[Application]\nname = org.gnome.Maps\n";
+        assert_eq!(Some(String::from("org.gnome.Maps")), ini_value(info, "Application", "name"));
+        assert_eq!(None, ini_value(info, "Application", "runtime"));
+
+        assert_eq!("core18", snap_base("name: app\nbase: \"core18\"\n"));
+        assert_eq!("core", snap_base("name: app\nconfinement: strict\n"));
+    }
+}
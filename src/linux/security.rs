@@ -0,0 +1,162 @@
+use std::{fs, path::{Path, PathBuf}};
+
+use crate::logging::trace;
+
+use crate::{DiskEncryption, SecurityProcessor, Version};
+
+pub fn get_security_processor(root: &Path) -> Option<SecurityProcessor> {
+    let tpm = root.join("sys/class/tpm/tpm0");
+    if !tpm.exists() {
+        trace!("No TPM found at '{}'", tpm.display());
+        return None;
+    }
+
+    // Available since Linux 5.6.
+    let version = match fs::read_to_string(tpm.join("tpm_version_major")) {
+        Ok(major) => match major.trim() {
+            "2" => Version::Semantic(2, 0, 0, 0),
+            "1" => Version::Semantic(1, 2, 0, 0),
+            _ => Version::Unknown,
+        },
+        // Only TPM 1.2 devices expose their capabilities.
+        Err(_) if tpm.join("caps").exists() || tpm.join("device/caps").exists() => Version::Semantic(1, 2, 0, 0),
+        Err(_) => Version::Unknown,
+    };
+
+    Some(SecurityProcessor::Tpm(version))
+}
+
+pub fn get_disk_encryption(root: &Path) -> Option<DiskEncryption> {
+    let mountinfo = fs::read_to_string(root.join("proc/self/mountinfo")).ok()?;
+    let (device, source) = root_device(&mountinfo)?;
+    trace!("Root filesystem is mounted from {} ({})", source, device);
+
+    // Filesystems like btrfs report an anonymous device; fall back to the mount source.
+    let sysfs = if device.starts_with("0:") {
+        let name = source.strip_prefix("/dev/mapper/")?;
+        find_dm_device(root, name)?
+    } else {
+        root.join("sys/dev/block").join(device)
+    };
+
+    if is_luks(&sysfs, 0) {
+        Some(DiskEncryption::Luks)
+    } else {
+        Some(DiskEncryption::Unencrypted)
+    }
+}
+
+/// Returns the `major:minor` device number and the source of the root filesystem mount.
+fn root_device(mountinfo: &str) -> Option<(&str, &str)> {
+    // The last mount on "/" is the visible one.
+    mountinfo.lines().rev().find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let separator = fields.iter().position(|&f| f == "-")?;
+        match (fields.get(2), fields.get(4), fields.get(separator + 2)) {
+            (Some(&device), Some(&"/"), Some(&source)) => Some((device, source)),
+            _ => None,
+        }
+    })
+}
+
+/// Finds the sysfs directory of the device-mapper device with the given name.
+fn find_dm_device(root: &Path, name: &str) -> Option<PathBuf> {
+    fs::read_dir(root.join("sys/block"))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| fs::read_to_string(path.join("dm/name")).is_ok_and(|n| n.trim() == name))
+}
+
+/// Returns `true` if the block device is (or is stacked on top of) a LUKS device, e.g. LVM on LUKS.
+fn is_luks(device: &Path, depth: usize) -> bool {
+    // Guard against unexpected cycles in sysfs.
+    if depth > 8 {
+        return false;
+    }
+
+    if fs::read_to_string(device.join("dm/uuid")).is_ok_and(|uuid| uuid.starts_with("CRYPT-LUKS")) {
+        return true;
+    }
+
+    match fs::read_dir(device.join("slaves")) {
+        Ok(slaves) => slaves
+            .filter_map(Result::ok)
+            .any(|slave| is_luks(&slave.path(), depth + 1)),
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn tpm2() {
+        let root = root_with("tpm2", &[("sys/class/tpm/tpm0/tpm_version_major", "2\n")]);
+        assert_eq!(Some(SecurityProcessor::Tpm(Version::Semantic(2, 0, 0, 0))), get_security_processor(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn tpm12() {
+        let root = root_with("tpm12", &[("sys/class/tpm/tpm0/device/caps", "TCG version: 1.2\n")]);
+        assert_eq!(Some(SecurityProcessor::Tpm(Version::Semantic(1, 2, 0, 0))), get_security_processor(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn no_tpm() {
+        let root = root_with("no-tpm", &[("sys/class/.keep", "")]);
+        assert_eq!(None, get_security_processor(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    const MOUNTINFO: &str = "\
+22 28 0:21 / /proc rw,nosuid,nodev,noexec,relatime shared:12 - proc proc rw
+28 1 253:1 / / rw,relatime shared:1 - ext4 /dev/mapper/vg-root rw
+30 28 259:1 / /boot rw,relatime shared:60 - ext4 /dev/nvme0n1p2 rw
+";
+
+    #[test]
+    fn luks() {
+        let root = root_with("luks", &[
+            ("proc/self/mountinfo", MOUNTINFO),
+            ("sys/dev/block/253:1/dm/uuid", "LVM-Fxk7\n"),
+            ("sys/dev/block/253:1/slaves/dm-0/dm/uuid", "CRYPT-LUKS2-4b3e2c1a-root\n"),
+        ]);
+        assert_eq!(Some(DiskEncryption::Luks), get_disk_encryption(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn unencrypted() {
+        let root = root_with("unencrypted", &[
+            ("proc/self/mountinfo", MOUNTINFO),
+            ("sys/dev/block/253:1/dm/uuid", "LVM-Fxk7\n"),
+            ("sys/dev/block/253:1/slaves/nvme0n1p3/size", "1000\n"),
+        ]);
+        assert_eq!(Some(DiskEncryption::Unencrypted), get_disk_encryption(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn luks_btrfs() {
+        let root = root_with("luks-btrfs", &[
+            ("proc/self/mountinfo", "28 1 0:26 /root / rw,relatime shared:1 - btrfs /dev/mapper/luks-4b3e rw\n"),
+            ("sys/block/dm-0/dm/name", "luks-4b3e\n"),
+            ("sys/block/dm-0/dm/uuid", "CRYPT-LUKS2-4b3e-luks-4b3e\n"),
+        ]);
+        assert_eq!(Some(DiskEncryption::Luks), get_disk_encryption(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn no_mountinfo() {
+        let root = root_with("no-mountinfo", &[("proc/.keep", "")]);
+        assert_eq!(None, get_disk_encryption(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,49 @@
+use std::env;
+
+use crate::SessionKind;
+
+pub fn get_session_kind() -> SessionKind {
+    session_kind(|name| env::var(name).ok().filter(|value| !value.is_empty()))
+}
+
+/// Classifies the session from the environment set by the login manager (`XDG_SESSION_TYPE`),
+/// the display server and systemd.
+fn session_kind(var: impl Fn(&str) -> Option<String>) -> SessionKind {
+    match var("XDG_SESSION_TYPE").as_deref() {
+        Some("x11" | "wayland" | "mir") => return SessionKind::Desktop,
+        Some("tty") => return SessionKind::Console,
+        _ => {}
+    }
+
+    if var("WAYLAND_DISPLAY").is_some() || var("DISPLAY").is_some() {
+        SessionKind::Desktop
+    } else if var("INVOCATION_ID").is_some() {
+        // systemd sets this for the processes of its units.
+        SessionKind::Service
+    } else {
+        SessionKind::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn kinds() {
+        let data = [
+            (vec![("XDG_SESSION_TYPE", "wayland"), ("WAYLAND_DISPLAY", "wayland-0")], SessionKind::Desktop),
+            (vec![("XDG_SESSION_TYPE", "tty")], SessionKind::Console),
+            (vec![("DISPLAY", ":0")], SessionKind::Desktop),
+            (vec![("INVOCATION_ID", "6b9e1c0f2d6a4b5c8e7f0a1b2c3d4e5f")], SessionKind::Service),
+            (vec![("XDG_SESSION_TYPE", "unspecified")], SessionKind::Unknown),
+            (vec![], SessionKind::Unknown),
+        ];
+
+        for (environment, expected) in data {
+            let var = |name: &str| environment.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string());
+            assert_eq!(expected, session_kind(var));
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// spell-checker:ignore sles, sled, microos, slowroll
+
+use crate::{consts, matcher::Matcher, Version};
+
+/// IDs of SUSE Linux Enterprise products, whose `VERSION` carries the service pack, e.g.
+/// "15-SP5".
+const ENTERPRISE_IDS: [&str; 4] = [consts::SLES, consts::SLES_SAP, consts::SLED, consts::SLES_HPC];
+
+/// IDs of the rolling openSUSE distributions, whose `VERSION_ID` is the snapshot date, e.g.
+/// "20240320".
+const ROLLING_IDS: [&str; 3] = [consts::OPENSUSE_TUMBLEWEED, consts::OPENSUSE_MICROOS, consts::OPENSUSE_SLOWROLL];
+
+/// Returns the version of a SUSE distribution from its os-release, or `None` for other
+/// distributions and openSUSE Leap, whose `VERSION_ID` (e.g. "15.5") is already a version.
+///
+/// The service pack of SUSE Linux Enterprise becomes the minor version, e.g. 15.5 for 15 SP5.
+pub fn version(id: &str, release: &str) -> Option<Version> {
+    if ROLLING_IDS.contains(&id) {
+        let snapshot = Matcher::KeyValue { key: "VERSION_ID" }.find(release);
+        return Some(Version::Rolling(snapshot.filter(|snapshot| !snapshot.is_empty())));
+    }
+
+    if ENTERPRISE_IDS.contains(&id) {
+        let version = Matcher::KeyValue { key: "VERSION" }.find(release)?;
+        let (major, service_pack) = match version.split_once("-SP") {
+            Some((major, service_pack)) => (major, service_pack.parse().ok()?),
+            None => (version.as_str(), 0),
+        };
+        return Some(Version::Semantic(major.trim().parse().ok()?, service_pack, 0, 0));
+    }
+
+    None
+}
+
+/// Returns the variant of SUSE Linux Enterprise products, which have no `VARIANT_ID`: "server"
+/// for SLES (including SLES for SAP Applications and HPC) and "client" for SLED.
+pub fn variant(id: &str) -> Option<&'static str> {
+    match id {
+        consts::SLES | consts::SLES_SAP | consts::SLES_HPC => Some("server"),
+        consts::SLED => Some("client"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn versions() {
+        let data = [
+            (consts::SLES, "VERSION=\"15-SP5\"\nVERSION_ID=\"15.5\"\n", Some(Version::Semantic(15, 5, 0, 0))),
+            (consts::SLES, "VERSION=\"15\"\nVERSION_ID=\"15\"\n", Some(Version::Semantic(15, 0, 0, 0))),
+            (consts::SLED, "VERSION=\"12-SP5\"\nVERSION_ID=\"12.5\"\n", Some(Version::Semantic(12, 5, 0, 0))),
+            (consts::SLES, "VERSION=\"15-SPX\"\n", None),
+            (
+                consts::OPENSUSE_TUMBLEWEED,
+                "VERSION_ID=\"20240320\"\n",
+                Some(Version::Rolling(Some(String::from("20240320")))),
+            ),
+            (consts::OPENSUSE_MICROOS, "", Some(Version::Rolling(None))),
+            (consts::OPENSUSE_LEAP, "VERSION=\"15.5\"\nVERSION_ID=\"15.5\"\n", None),
+        ];
+
+        for (id, release, expected) in data {
+            assert_eq!(expected, version(id, release), "{id}: {release}");
+        }
+    }
+}
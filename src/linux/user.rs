@@ -0,0 +1,65 @@
+#![allow(unsafe_code)]
+
+use std::{env, fs, path::Path};
+
+/// Returns the effective user ID of the process.
+#[cfg(unix)]
+pub fn effective_uid() -> Option<u32> {
+    unsafe extern "C" {
+        fn geteuid() -> u32;
+    }
+
+    // SAFETY: geteuid has no preconditions and always succeeds.
+    Some(unsafe { geteuid() })
+}
+
+#[cfg(not(unix))]
+pub fn effective_uid() -> Option<u32> {
+    None
+}
+
+/// Returns the name of the effective user from `/etc/passwd`, or the `USER` / `LOGNAME`
+/// environment variables for users from other sources, e.g. LDAP.
+pub fn current_user(root: &Path) -> Option<String> {
+    fs::read_to_string(root.join("etc/passwd"))
+        .ok()
+        .and_then(|passwd| user_name(&passwd, effective_uid()?))
+        .or_else(|| ["USER", "LOGNAME"].iter().find_map(|name| env::var(name).ok().filter(|user| !user.is_empty())))
+}
+
+/// Finds the name of the user with the given ID in the contents of `/etc/passwd`.
+fn user_name(passwd: &str, uid: u32) -> Option<String> {
+    passwd.lines().find_map(|line| {
+        let mut fields = line.split(':');
+        let name = fields.next()?;
+        let id = fields.nth(1)?.parse::<u32>().ok()?;
+        (id == uid && !name.is_empty()).then(|| name.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn names() {
+        let passwd = "root:x:0:0:root:/root:/bin/bash\n\
+            # comment\n\
+            daemon:x:1:1:daemon:/usr/sbin:/usr/sbin/nologin\n\
+            alice:x:1000:1000:Alice,,,:/home/alice:/bin/bash\n";
+        assert_eq!(Some(String::from("root")), user_name(passwd, 0));
+        assert_eq!(Some(String::from("alice")), user_name(passwd, 1000));
+        assert_eq!(None, user_name(passwd, 1001));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn current() {
+        let uid = effective_uid().unwrap();
+        let root = root_with("user", &[("etc/passwd", &format!("tester:x:{uid}:0::/:/bin/sh\n"))]);
+        assert_eq!(Some(String::from("tester")), current_user(&root));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,20 @@
+//! Logging macros: those of the `log` crate with the `log` feature, and macros that only
+//! type-check their arguments without it, so that the crate has no dependencies in its minimal
+//! configuration.
+
+#[cfg(feature = "log")]
+pub(crate) use log::{error, info, trace, warn};
+
+#[cfg(not(feature = "log"))]
+macro_rules! disabled {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = ::core::format_args!($($arg)+);
+        }
+    };
+}
+
+// Without `detect`, only some of the macros are used.
+#[cfg(not(feature = "log"))]
+#[cfg_attr(not(feature = "detect"), allow(unused_imports))]
+pub(crate) use {disabled as error, disabled as info, disabled as trace, disabled as warn};
@@ -0,0 +1,347 @@
+//! Mappings between release codenames and versions, e.g. to build apt repository lines or
+//! download URLs alongside detection.
+//!
+//! Codenames are matched case-insensitively, and Ubuntu codenames may be given in full ("Jammy
+//! Jellyfish") or as their first word ("jammy").
+//!
+//! # Examples
+//!
+//! ```
+//! use osinfo::{Version, lookup};
+//!
+//! assert_eq!(Some(Version::Semantic(22, 4, 0, 0)), lookup::ubuntu_codename("jammy"));
+//! assert_eq!(Some("bookworm"), lookup::debian_codename_for(&Version::Semantic(12, 5, 0, 0)));
+//! assert_eq!(Some(22631), lookup::windows_build("23H2"));
+//! assert_eq!(Some("2022"), lookup::windows_server_release(20348));
+//! assert_eq!(Some(Version::Semantic(14, 0, 0, 0)), lookup::macos("Sonoma"));
+//! ```
+
+use crate::Version;
+
+/// Ubuntu releases: short codename, full codename, year and month.
+static UBUNTU: [(&str, &str, u32, u32); 43] = [
+    ("warty", "Warty Warthog", 4, 10),
+    ("hoary", "Hoary Hedgehog", 5, 4),
+    ("breezy", "Breezy Badger", 5, 10),
+    ("dapper", "Dapper Drake", 6, 6),
+    ("edgy", "Edgy Eft", 6, 10),
+    ("feisty", "Feisty Fawn", 7, 4),
+    ("gutsy", "Gutsy Gibbon", 7, 10),
+    ("hardy", "Hardy Heron", 8, 4),
+    ("intrepid", "Intrepid Ibex", 8, 10),
+    ("jaunty", "Jaunty Jackalope", 9, 4),
+    ("karmic", "Karmic Koala", 9, 10),
+    ("lucid", "Lucid Lynx", 10, 4),
+    ("maverick", "Maverick Meerkat", 10, 10),
+    ("natty", "Natty Narwhal", 11, 4),
+    ("oneiric", "Oneiric Ocelot", 11, 10),
+    ("precise", "Precise Pangolin", 12, 4),
+    ("quantal", "Quantal Quetzal", 12, 10),
+    ("raring", "Raring Ringtail", 13, 4),
+    ("saucy", "Saucy Salamander", 13, 10),
+    ("trusty", "Trusty Tahr", 14, 4),
+    ("utopic", "Utopic Unicorn", 14, 10),
+    ("vivid", "Vivid Vervet", 15, 4),
+    ("wily", "Wily Werewolf", 15, 10),
+    ("xenial", "Xenial Xerus", 16, 4),
+    ("yakkety", "Yakkety Yak", 16, 10),
+    ("zesty", "Zesty Zapus", 17, 4),
+    ("artful", "Artful Aardvark", 17, 10),
+    ("bionic", "Bionic Beaver", 18, 4),
+    ("cosmic", "Cosmic Cuttlefish", 18, 10),
+    ("disco", "Disco Dingo", 19, 4),
+    ("eoan", "Eoan Ermine", 19, 10),
+    ("focal", "Focal Fossa", 20, 4),
+    ("groovy", "Groovy Gorilla", 20, 10),
+    ("hirsute", "Hirsute Hippo", 21, 4),
+    ("impish", "Impish Indri", 21, 10),
+    ("jammy", "Jammy Jellyfish", 22, 4),
+    ("kinetic", "Kinetic Kudu", 22, 10),
+    ("lunar", "Lunar Lobster", 23, 4),
+    ("mantic", "Mantic Minotaur", 23, 10),
+    ("noble", "Noble Numbat", 24, 4),
+    ("oracular", "Oracular Oriole", 24, 10),
+    ("plucky", "Plucky Puffin", 25, 4),
+    ("questing", "Questing Quokka", 25, 10),
+];
+
+/// Debian releases: codename, major and minor version. The minor version only identifies the
+/// release before Debian 4.
+static DEBIAN: [(&str, u32, u32); 20] = [
+    ("buzz", 1, 1),
+    ("rex", 1, 2),
+    ("bo", 1, 3),
+    ("hamm", 2, 0),
+    ("slink", 2, 1),
+    ("potato", 2, 2),
+    ("woody", 3, 0),
+    ("sarge", 3, 1),
+    ("etch", 4, 0),
+    ("lenny", 5, 0),
+    ("squeeze", 6, 0),
+    ("wheezy", 7, 0),
+    ("jessie", 8, 0),
+    ("stretch", 9, 0),
+    ("buster", 10, 0),
+    ("bullseye", 11, 0),
+    ("bookworm", 12, 0),
+    ("trixie", 13, 0),
+    ("forky", 14, 0),
+    ("duke", 15, 0),
+];
+
+/// macOS releases: marketing name, major and minor version. The minor version only identifies
+/// the release before macOS 11.
+static MACOS: [(&str, u32, u32); 22] = [
+    ("Cheetah", 10, 0),
+    ("Puma", 10, 1),
+    ("Jaguar", 10, 2),
+    ("Panther", 10, 3),
+    ("Tiger", 10, 4),
+    ("Leopard", 10, 5),
+    ("Snow Leopard", 10, 6),
+    ("Lion", 10, 7),
+    ("Mountain Lion", 10, 8),
+    ("Mavericks", 10, 9),
+    ("Yosemite", 10, 10),
+    ("El Capitan", 10, 11),
+    ("Sierra", 10, 12),
+    ("High Sierra", 10, 13),
+    ("Mojave", 10, 14),
+    ("Catalina", 10, 15),
+    ("Big Sur", 11, 0),
+    ("Monterey", 12, 0),
+    ("Ventura", 13, 0),
+    ("Sonoma", 14, 0),
+    ("Sequoia", 15, 0),
+    ("Tahoe", 26, 0),
+];
+
+/// Windows 10 and 11 feature releases: display version, build number and product. Windows 10
+/// and 11 both have a 21H2 and a 22H2 release.
+static WINDOWS: [(&str, u32, &str); 19] = [
+    ("1507", 10240, "Windows 10"),
+    ("1511", 10586, "Windows 10"),
+    ("1607", 14393, "Windows 10"),
+    ("1703", 15063, "Windows 10"),
+    ("1709", 16299, "Windows 10"),
+    ("1803", 17134, "Windows 10"),
+    ("1809", 17763, "Windows 10"),
+    ("1903", 18362, "Windows 10"),
+    ("1909", 18363, "Windows 10"),
+    ("2004", 19041, "Windows 10"),
+    ("20H2", 19042, "Windows 10"),
+    ("21H1", 19043, "Windows 10"),
+    ("21H2", 19044, "Windows 10"),
+    ("22H2", 19045, "Windows 10"),
+    ("21H2", 22000, "Windows 11"),
+    ("22H2", 22621, "Windows 11"),
+    ("23H2", 22631, "Windows 11"),
+    ("24H2", 26100, "Windows 11"),
+    ("25H2", 26200, "Windows 11"),
+];
+
+/// Windows Server releases: year and build number. Semi-annual channel releases in between
+/// are based on the previous one.
+static WINDOWS_SERVER: [(&str, u32); 4] = [
+    ("2016", 14393),
+    ("2019", 17763),
+    ("2022", 20348),
+    ("2025", 26100),
+];
+
+/// Returns the version of the Ubuntu release with the given codename, e.g. 22.04 for "jammy"
+/// or "Jammy Jellyfish".
+pub fn ubuntu_codename(codename: &str) -> Option<Version> {
+    let codename = codename.trim();
+    UBUNTU
+        .iter()
+        .find(|&&(short, full, ..)| short.eq_ignore_ascii_case(codename) || full.eq_ignore_ascii_case(codename))
+        .map(|&(_, _, year, month)| Version::Semantic(year, month, 0, 0))
+}
+
+/// Returns the short codename of the Ubuntu release of the given version, e.g. "jammy" for
+/// 22.04 or 22.04.4.
+pub fn ubuntu_codename_for(version: &Version) -> Option<&'static str> {
+    let &Version::Semantic(year, month, ..) = version else {
+        return None;
+    };
+    UBUNTU
+        .iter()
+        .find(|&&(_, _, y, m)| (y, m) == (year, month))
+        .map(|&(short, ..)| short)
+}
+
+/// Returns the version of the Debian release with the given codename, e.g. 12 for "bookworm".
+pub fn debian(codename: &str) -> Option<Version> {
+    let codename = codename.trim();
+    DEBIAN
+        .iter()
+        .find(|&&(name, ..)| name.eq_ignore_ascii_case(codename))
+        .map(|&(_, major, minor)| Version::Semantic(major, minor, 0, 0))
+}
+
+/// Returns the codename of the Debian release of the given version, e.g. "bookworm" for 12 or
+/// 12.5.
+pub fn debian_codename_for(version: &Version) -> Option<&'static str> {
+    let &Version::Semantic(major, minor, ..) = version else {
+        return None;
+    };
+    DEBIAN
+        .iter()
+        .find(|&&(_, m, n)| m == major && (major >= 4 || n == minor))
+        .map(|&(name, ..)| name)
+}
+
+/// Returns the version of the macOS release with the given marketing name, e.g. 14 for
+/// "Sonoma" or 10.15 for "Catalina".
+pub fn macos(name: &str) -> Option<Version> {
+    let name = name.trim();
+    MACOS
+        .iter()
+        .find(|&&(n, ..)| n.eq_ignore_ascii_case(name))
+        .map(|&(_, major, minor)| Version::Semantic(major, minor, 0, 0))
+}
+
+/// Returns the marketing name of the macOS release of the given version, e.g. "Sonoma" for
+/// 14.2.1 or "Catalina" for 10.15.7.
+pub fn macos_name_for(version: &Version) -> Option<&'static str> {
+    let &Version::Semantic(major, minor, ..) = version else {
+        return None;
+    };
+    MACOS
+        .iter()
+        .find(|&&(_, m, n)| m == major && (major >= 11 || n == minor))
+        .map(|&(name, ..)| name)
+}
+
+/// Returns the build number of the Windows feature release with the given display version,
+/// e.g. 22631 for "23H2". For 21H2 and 22H2, this is the Windows 11 build; see
+/// `windows_10_build`.
+pub fn windows_build(display_version: &str) -> Option<u32> {
+    windows_build_of("Windows 11", display_version).or_else(|| windows_10_build(display_version))
+}
+
+/// Returns the build number of the Windows 10 feature release with the given display version,
+/// e.g. 19045 for "22H2".
+pub fn windows_10_build(display_version: &str) -> Option<u32> {
+    windows_build_of("Windows 10", display_version)
+}
+
+fn windows_build_of(product: &str, display_version: &str) -> Option<u32> {
+    let display_version = display_version.trim();
+    WINDOWS
+        .iter()
+        .find(|&&(release, _, p)| p == product && release.eq_ignore_ascii_case(display_version))
+        .map(|&(_, build, _)| build)
+}
+
+/// Returns the display version of the Windows 10 or 11 feature release with the given build
+/// number, e.g. "23H2" for 22631.
+pub fn windows_release(build: u32) -> Option<&'static str> {
+    WINDOWS.iter().find(|&&(_, b, _)| b == build).map(|&(release, ..)| release)
+}
+
+/// Returns the Windows Server release a server with the given build number belongs to, e.g.
+/// "2022" for 20348, for inventory labels like "Windows Server 2022". Builds between two
+/// releases, such as updates and semi-annual channel releases, belong to the previous one.
+pub fn windows_server_release(build: u32) -> Option<&'static str> {
+    WINDOWS_SERVER
+        .iter()
+        .rev()
+        .find(|&&(_, first)| build >= first)
+        .map(|&(release, _)| release)
+}
+
+/// Returns the build number of the Windows Server release with the given year, e.g. 20348 for
+/// "2022".
+pub fn windows_server_build(release: &str) -> Option<u32> {
+    let release = release.trim();
+    WINDOWS_SERVER.iter().find(|&&(r, _)| r == release).map(|&(_, build)| build)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ubuntu() {
+        assert_eq!(Some(Version::Semantic(22, 4, 0, 0)), ubuntu_codename("jammy"));
+        assert_eq!(Some(Version::Semantic(24, 4, 0, 0)), ubuntu_codename("Noble Numbat"));
+        assert_eq!(Some(Version::Semantic(6, 6, 0, 0)), ubuntu_codename(" Dapper "));
+        assert_eq!(None, ubuntu_codename("sid"));
+
+        assert_eq!(Some("jammy"), ubuntu_codename_for(&Version::Semantic(22, 4, 4, 0)));
+        assert_eq!(None, ubuntu_codename_for(&Version::Semantic(22, 5, 0, 0)));
+        assert_eq!(None, ubuntu_codename_for(&Version::Rolling(None)));
+    }
+
+    #[test]
+    fn debian_codenames() {
+        assert_eq!(Some(Version::Semantic(12, 0, 0, 0)), debian("bookworm"));
+        assert_eq!(Some(Version::Semantic(3, 1, 0, 0)), debian("Sarge"));
+        assert_eq!(None, debian("sid"));
+
+        assert_eq!(Some("bookworm"), debian_codename_for(&Version::Semantic(12, 5, 0, 0)));
+        assert_eq!(Some("woody"), debian_codename_for(&Version::Semantic(3, 0, 0, 0)));
+        assert_eq!(Some("sarge"), debian_codename_for(&Version::Semantic(3, 1, 0, 0)));
+        assert_eq!(None, debian_codename_for(&Version::Semantic(3, 2, 0, 0)));
+    }
+
+    #[test]
+    fn macos_names() {
+        assert_eq!(Some(Version::Semantic(14, 0, 0, 0)), macos("Sonoma"));
+        assert_eq!(Some(Version::Semantic(10, 15, 0, 0)), macos("catalina"));
+        assert_eq!(None, macos("Rhapsody"));
+
+        assert_eq!(Some("Sonoma"), macos_name_for(&Version::Semantic(14, 2, 1, 0)));
+        assert_eq!(Some("Catalina"), macos_name_for(&Version::Semantic(10, 15, 7, 0)));
+        assert_eq!(None, macos_name_for(&Version::Semantic(10, 16, 0, 0)));
+    }
+
+    #[test]
+    fn windows() {
+        assert_eq!(Some(22631), windows_build("23H2"));
+        assert_eq!(Some(22000), windows_build("21h2"));
+        assert_eq!(Some(19045), windows_10_build("22H2"));
+        assert_eq!(Some(19043), windows_build("21H1"));
+        assert_eq!(None, windows_10_build("23H2"));
+        assert_eq!(None, windows_build("Vista"));
+
+        assert_eq!(Some("23H2"), windows_release(22631));
+        assert_eq!(Some("22H2"), windows_release(19045));
+        assert_eq!(None, windows_release(20348));
+    }
+
+    #[test]
+    fn windows_server() {
+        assert_eq!(Some("2022"), windows_server_release(20348));
+        assert_eq!(Some("2019"), windows_server_release(18363));
+        assert_eq!(Some("2025"), windows_server_release(26100));
+        assert_eq!(None, windows_server_release(10240));
+
+        assert_eq!(Some(17763), windows_server_build("2019"));
+        assert_eq!(None, windows_server_build("2012"));
+    }
+
+    #[test]
+    fn round_trip() {
+        for &(short, ..) in &UBUNTU {
+            assert_eq!(Some(short), ubuntu_codename(short).as_ref().and_then(ubuntu_codename_for));
+        }
+        for &(name, ..) in &DEBIAN {
+            assert_eq!(Some(name), debian(name).as_ref().and_then(debian_codename_for));
+        }
+        for &(name, ..) in &MACOS {
+            assert_eq!(Some(name), macos(name).as_ref().and_then(macos_name_for));
+        }
+        for &(release, build, _) in &WINDOWS {
+            assert_eq!(Some(release), windows_release(build));
+        }
+        for &(release, build) in &WINDOWS_SERVER {
+            assert_eq!(Some(release), windows_server_build(release).and_then(windows_server_release));
+            assert_eq!(Some(build), windows_server_build(release));
+        }
+    }
+}
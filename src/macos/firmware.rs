@@ -0,0 +1,57 @@
+use crate::logging::trace;
+
+use crate::{Firmware, FirmwareType, exec};
+
+use super::platform_details;
+
+pub fn get_firmware() -> Firmware {
+    let firmware_type = if platform_details().apple_silicon {
+        FirmwareType::Iboot
+    } else {
+        FirmwareType::Uefi
+    };
+
+    let version = match exec::run("system_profiler", &["SPHardwareDataType"]) {
+        Ok(output) => parse_firmware_version(&String::from_utf8_lossy(&output.stdout)),
+        Err(e) => {
+            trace!("Unable to run system_profiler: {:?}", e);
+            None
+        }
+    };
+
+    Firmware {
+        firmware_type: Some(firmware_type),
+        vendor: Some(String::from("Apple")),
+        version,
+    }
+}
+
+/// Parses the firmware version from the `system_profiler SPHardwareDataType` output, e.g.
+/// "System Firmware Version: 10151.101.3", or "Boot ROM Version: ..." on older releases.
+fn parse_firmware_version(hardware: &str) -> Option<String> {
+    hardware.lines().find_map(|line| {
+        let line = line.trim();
+        line.strip_prefix("System Firmware Version:")
+            .or_else(|| line.strip_prefix("Boot ROM Version:"))
+            .map(|version| version.trim().to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn firmware_version() {
+        let data = [
+            ("Hardware:\n\n    Hardware Overview:\n\n      Model Name: MacBook Pro\n      System Firmware Version: 10151.101.3\n      OS Loader Version: 10151.101.3\n", Some("10151.101.3")),
+            ("      Boot ROM Version: 1554.140.20.0.0 (iBridge: 18.16.14759.0.1,0)\n", Some("1554.140.20.0.0 (iBridge: 18.16.14759.0.1,0)")),
+            ("", None),
+        ];
+
+        for (hardware, expected) in &data {
+            assert_eq!(expected.map(String::from), parse_firmware_version(hardware));
+        }
+    }
+}
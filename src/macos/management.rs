@@ -0,0 +1,61 @@
+use std::path::Path;
+
+use crate::logging::trace;
+
+use crate::exec;
+
+/// The macOS Server app, which installs the server components.
+const SERVER_APP_PATH: &str = "/Applications/Server.app";
+
+/// Returns the variant of the installation: "server" if the macOS Server components are
+/// installed, "managed client" if the Mac is enrolled in an MDM, and "client" otherwise.
+///
+/// The MDM enrollment is only checked if `run_external_commands` is `true`.
+pub fn get_variant(run_external_commands: bool) -> &'static str {
+    if Path::new(SERVER_APP_PATH).exists() {
+        return "server";
+    }
+    if !run_external_commands {
+        return "client";
+    }
+
+    // Reading the enrollment status doesn't require elevated privileges.
+    match exec::run("profiles", &["status", "-type", "enrollment"]) {
+        Ok(output) if is_mdm_enrolled(&String::from_utf8_lossy(&output.stdout)) => "managed client",
+        Ok(_) => "client",
+        Err(e) => {
+            trace!("Unable to run profiles: {:?}", e);
+            "client"
+        }
+    }
+}
+
+/// Parses the output of `profiles status -type enrollment`, e.g.
+/// "Enrolled via DEP: Yes\nMDM enrollment: Yes (User Approved)". Devices enrolled via DEP
+/// (Automated Device Enrollment) are supervised.
+fn is_mdm_enrolled(status: &str) -> bool {
+    status.lines().any(|line| {
+        line.strip_prefix("MDM enrollment:")
+            .is_some_and(|value| value.trim_start().starts_with("Yes"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn enrollment_status() {
+        let data = [
+            ("Enrolled via DEP: Yes\nMDM enrollment: Yes (User Approved)\nMDM server: https://mdm.example.com/\n", true),
+            ("Enrolled via DEP: No\nMDM enrollment: Yes\n", true),
+            ("Enrolled via DEP: No\nMDM enrollment: No\n", false),
+            ("", false),
+        ];
+
+        for (status, expected) in &data {
+            assert_eq!(*expected, is_mdm_enrolled(status));
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! macOS specific information.
+
+mod firmware;
+mod management;
+mod network;
+mod platform;
+mod reboot;
+mod security;
+mod session;
+mod system_version;
+mod user;
+
+pub use platform::{PlatformDetails, platform_details};
+
+use crate::logging::trace;
+
+use crate::{Cgroups, Config, Deployment, Diagnostic, DisplayServer, DisplayServerKind, FieldName, Firmware, LibcInfo, NetworkEnv, OSInfo, Sandbox, SecurityProfile, SessionKind, Version, boot::BootStatus};
+
+pub(crate) fn get_info(config: &Config, diagnostics: &mut Vec<Diagnostic>) -> OSInfo {
+    trace!("macos::get_info is called");
+    let info = system_version::get_os_data(config.run_external_commands, diagnostics).map(|info| {
+        let (edition, source) = platform::edition(config.run_external_commands);
+        let mut info = OSInfo {
+            variant: Some(String::from(management::get_variant(config.run_external_commands))),
+            edition: Some(String::from(edition)),
+            ..info
+        };
+        info.record_source(&[FieldName::Edition], source);
+        info
+    });
+    trace!("Returning {:?}", info);
+    info.unwrap_or_else(|| {
+        diagnostics.push(Diagnostic::NotDetected);
+        OSInfo::default()
+    })
+}
+
+pub(crate) fn get_kernel_version() -> Option<Version> {
+    // The Darwin kernel is versioned together with the operating system.
+    None
+}
+
+pub(crate) fn get_boot_status() -> BootStatus {
+    // Apple's boot policy (Secure Boot on Apple Silicon and T2 Macs) isn't readable without
+    // elevated privileges.
+    BootStatus::default()
+}
+
+pub(crate) fn get_firmware() -> Firmware {
+    trace!("macos::get_firmware is called");
+    firmware::get_firmware()
+}
+
+pub(crate) fn fips_enabled() -> Option<bool> {
+    // macOS has no system-wide FIPS mode; CoreCrypto's validated modules are always used.
+    None
+}
+
+pub(crate) fn get_deployment() -> Option<Deployment> {
+    // The sealed system volume is versioned together with the operating system.
+    None
+}
+
+pub(crate) fn get_sandbox(_config: &Config) -> Option<Sandbox> {
+    // The App Sandbox doesn't hide the operating system.
+    None
+}
+
+pub(crate) fn get_cgroups() -> Option<Cgroups> {
+    // Control groups are Linux only.
+    None
+}
+
+pub(crate) fn get_libc() -> Option<LibcInfo> {
+    // The C library is part of libSystem, which is versioned with the operating system.
+    None
+}
+
+pub(crate) fn install_language() -> Option<String> {
+    // macOS installs all languages; the language is a per-user preference.
+    None
+}
+
+pub(crate) fn system_locale() -> Option<String> {
+    None
+}
+
+pub(crate) fn pending_reboot() -> Option<bool> {
+    trace!("macos::pending_reboot is called");
+    reboot::pending_reboot()
+}
+
+pub(crate) fn get_session_kind() -> SessionKind {
+    trace!("macos::get_session_kind is called");
+    session::get_session_kind()
+}
+
+pub(crate) fn network_env() -> NetworkEnv {
+    trace!("macos::network_env is called");
+    network::network_env()
+}
+
+pub(crate) fn get_display_server() -> DisplayServer {
+    // Only processes in the Aqua session can connect to the WindowServer.
+    let kind = match session::get_session_kind() {
+        SessionKind::Desktop => DisplayServerKind::Quartz,
+        _ => DisplayServerKind::Headless,
+    };
+    DisplayServer {
+        kind,
+        compositor: None,
+        display_attached: None,
+    }
+}
+
+pub(crate) fn is_elevated() -> bool {
+    user::effective_uid() == 0
+}
+
+pub(crate) fn current_user() -> Option<String> {
+    trace!("macos::current_user is called");
+    user::current_user()
+}
+
+pub(crate) fn get_security_profile() -> SecurityProfile {
+    trace!("macos::get_security_profile is called");
+    SecurityProfile {
+        security_processor: security::get_security_processor(),
+        disk_encryption: security::get_disk_encryption(),
+    }
+}
@@ -0,0 +1,96 @@
+use std::env;
+
+use crate::logging::trace;
+
+use crate::{NetworkEnv, exec};
+
+/// Returns the proxy configuration from the environment, or from the SystemConfiguration
+/// settings reported by `scutil --proxy` if no proxy variables are set.
+pub fn network_env() -> NetworkEnv {
+    let from_env = NetworkEnv::from_env(|name| env::var(name).ok());
+    if !from_env.is_direct() {
+        return from_env;
+    }
+
+    match exec::run("scutil", &["--proxy"]) {
+        Ok(output) if output.status.success() => parse_scutil(&String::from_utf8_lossy(&output.stdout)),
+        Ok(_) => from_env,
+        Err(e) => {
+            trace!("Unable to run scutil: {:?}", e);
+            from_env
+        }
+    }
+}
+
+/// Parses the dictionary printed by `scutil --proxy`, e.g. "HTTPEnable : 1\nHTTPProxy : proxy".
+fn parse_scutil(settings: &str) -> NetworkEnv {
+    let value = |key: &str| {
+        settings.lines().find_map(|line| {
+            let (name, value) = line.split_once(" : ")?;
+            (name.trim() == key).then(|| value.trim().to_string())
+        })
+    };
+    let enabled = |key: &str| value(key).as_deref() == Some("1");
+    let proxy = |protocol: &str| {
+        if !enabled(&format!("{protocol}Enable")) {
+            return None;
+        }
+        let host = value(&format!("{protocol}Proxy"))?;
+        Some(match value(&format!("{protocol}Port")) {
+            Some(port) => format!("http://{host}:{port}"),
+            None => format!("http://{host}"),
+        })
+    };
+
+    // The exceptions are an array whose items are printed as "<index> : <host>".
+    let mut no_proxy = Vec::new();
+    let mut in_exceptions = false;
+    for line in settings.lines() {
+        let line = line.trim();
+        if line.starts_with("ExceptionsList :") {
+            in_exceptions = true;
+        } else if in_exceptions && line == "}" {
+            in_exceptions = false;
+        } else if in_exceptions && let Some((_, host)) = line.split_once(" : ") {
+            no_proxy.push(host.trim().to_string());
+        }
+    }
+
+    NetworkEnv {
+        http_proxy: proxy("HTTP"),
+        https_proxy: proxy("HTTPS"),
+        no_proxy,
+        auto_config_url: enabled("ProxyAutoConfigEnable").then(|| value("ProxyAutoConfigURLString")).flatten(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn scutil() {
+        let settings = "<dictionary> {\n\
+            \x20 ExceptionsList : <array> {\n\
+            \x20   0 : *.local\n\
+            \x20   1 : 169.254/16\n\
+            \x20 }\n\
+            \x20 FTPPassive : 1\n\
+            \x20 HTTPEnable : 1\n\
+            \x20 HTTPPort : 3128\n\
+            \x20 HTTPProxy : proxy.example.com\n\
+            \x20 HTTPSEnable : 0\n\
+            \x20 HTTPSProxy : proxy.example.com\n\
+            \x20 ProxyAutoConfigEnable : 1\n\
+            \x20 ProxyAutoConfigURLString : http://wpad/wpad.dat\n\
+            }\n";
+        let expected = NetworkEnv {
+            http_proxy: Some(String::from("http://proxy.example.com:3128")),
+            https_proxy: None,
+            no_proxy: vec![String::from("*.local"), String::from("169.254/16")],
+            auto_config_url: Some(String::from("http://wpad/wpad.dat")),
+        };
+        assert_eq!(expected, parse_scutil(settings));
+    }
+}
@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use crate::logging::trace;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::exec;
+
+/// Directory installed by Rosetta 2.
+const ROSETTA_PATH: &str = "/Library/Apple/usr/share/rosetta";
+
+/// Hardware platform of a Mac, e.g. to decide whether x86_64 binaries can run.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlatformDetails {
+    /// Whether the Mac has an Apple Silicon (arm64) processor. This is also `true` when the
+    /// calling process is an x86_64 binary translated by Rosetta.
+    pub apple_silicon: bool,
+    /// Whether Rosetta 2 is installed, i.e. x86_64 binaries can run on Apple Silicon. Always
+    /// `false` on Intel Macs, which run them natively.
+    pub rosetta_installed: bool,
+    /// Hardware model identifier, e.g. "Mac14,2" or "MacBookPro16,1".
+    pub model: Option<String>,
+}
+
+/// Returns the hardware platform details of the Mac.
+///
+/// # Examples
+///
+/// ```
+/// let details = osinfo::macos::platform_details();
+/// if details.apple_silicon && !details.rosetta_installed {
+///     println!("x86_64 binaries won't run");
+/// }
+/// ```
+pub fn platform_details() -> PlatformDetails {
+    let apple_silicon = sysctl("hw.optional.arm64").as_deref() == Some("1");
+    PlatformDetails {
+        apple_silicon,
+        rosetta_installed: apple_silicon && Path::new(ROSETTA_PATH).exists(),
+        model: sysctl("hw.model"),
+    }
+}
+
+/// Returns the edition of macOS, i.e. the processor family of the Mac it is installed for:
+/// "Apple Silicon" or "Intel", along with the source it was determined from.
+///
+/// Without `run_external_commands`, it is the architecture the crate was compiled for, so x86_64
+/// binaries translated by Rosetta report "Intel".
+pub(super) fn edition(run_external_commands: bool) -> (&'static str, &'static str) {
+    let (apple_silicon, source) = match run_external_commands.then(|| sysctl("hw.optional.arm64")).flatten() {
+        Some(arm64) => (arm64 == "1", "hw.optional.arm64"),
+        None => (cfg!(target_arch = "aarch64"), "target_arch"),
+    };
+    (if apple_silicon { "Apple Silicon" } else { "Intel" }, source)
+}
+
+/// Reads the given sysctl value. Returns `None` if it doesn't exist on this Mac, e.g.
+/// `hw.optional.arm64` on older Intel Macs.
+pub(super) fn sysctl(name: &str) -> Option<String> {
+    let output = match exec::run("sysctl", &["-n", name]) {
+        Ok(output) => output,
+        Err(e) => {
+            trace!("Unable to run sysctl: {:?}", e);
+            return None;
+        }
+    };
+
+    let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !value.is_empty()).then_some(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current() {
+        let details = platform_details();
+        if cfg!(target_arch = "aarch64") {
+            assert!(details.apple_silicon);
+        }
+        assert!(details.model.is_some());
+
+        let (edition, _) = edition(true);
+        assert_eq!(if details.apple_silicon { "Apple Silicon" } else { "Intel" }, edition);
+    }
+}
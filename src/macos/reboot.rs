@@ -0,0 +1,40 @@
+use crate::logging::trace;
+
+use crate::exec;
+
+/// Returns whether software updates requiring a restart are pending, as listed by
+/// `softwareupdate --list` (e.g. "Action: restart").
+pub fn pending_reboot() -> Option<bool> {
+    let output = match exec::run("softwareupdate", &["--list"]) {
+        Ok(output) => output,
+        Err(e) => {
+            trace!("Unable to run softwareupdate: {:?}", e);
+            return None;
+        }
+    };
+
+    if !output.status.success() {
+        return None;
+    }
+    Some(requires_restart(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn requires_restart(updates: &str) -> bool {
+    updates.lines().any(|line| line.contains("Action: restart") || line.contains("[restart]"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart() {
+        let updates = "Software Update Tool\n\
+            Finding available software\n\
+            Software Update found the following new or updated software:\n\
+            * Label: macOS Sonoma 14.4.1-23E224\n\
+            \tTitle: macOS Sonoma 14.4.1, Version: 14.4.1, Size: 1587728KiB, Recommended: YES, Action: restart,\n";
+        assert!(requires_restart(updates));
+        assert!(!requires_restart("Software Update Tool\n\nFinding available software\nNo new software available.\n"));
+    }
+}
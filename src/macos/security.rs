@@ -0,0 +1,62 @@
+use crate::logging::trace;
+
+use crate::{DiskEncryption, SecurityProcessor, exec};
+
+pub fn get_security_processor() -> Option<SecurityProcessor> {
+    // The Secure Enclave Processor manager is only registered on Apple Silicon and T2 Macs.
+    let output = match exec::run("ioreg", &["-c", "AppleSEPManager"]) {
+        Ok(output) => output,
+        Err(e) => {
+            trace!("Unable to run ioreg: {:?}", e);
+            return None;
+        }
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .contains("AppleSEPManager")
+        .then_some(SecurityProcessor::SecureEnclave)
+}
+
+pub fn get_disk_encryption() -> Option<DiskEncryption> {
+    let output = match exec::run("fdesetup", &["status"]) {
+        Ok(output) => output,
+        Err(e) => {
+            trace!("Unable to run fdesetup: {:?}", e);
+            return None;
+        }
+    };
+
+    parse_fdesetup_status(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Parses the output of `fdesetup status`, e.g. "FileVault is On.".
+fn parse_fdesetup_status(status: &str) -> Option<DiskEncryption> {
+    let first_line = status.lines().next()?;
+    if first_line.starts_with("FileVault is On") {
+        Some(DiskEncryption::FileVault)
+    } else if first_line.starts_with("FileVault is Off") {
+        Some(DiskEncryption::Unencrypted)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn fdesetup_status() {
+        let data = [
+            ("FileVault is On.\n", Some(DiskEncryption::FileVault)),
+            ("FileVault is Off.\n", Some(DiskEncryption::Unencrypted)),
+            ("FileVault is On.\nDeferred enablement appears to be active for user 'admin'.\n", Some(DiskEncryption::FileVault)),
+            ("", None),
+        ];
+
+        for (status, expected) in &data {
+            assert_eq!(*expected, parse_fdesetup_status(status));
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::logging::trace;
+
+use crate::{SessionKind, exec};
+
+/// Returns the kind of the launchd session the process belongs to, as reported by
+/// `launchctl managername`.
+pub fn get_session_kind() -> SessionKind {
+    match exec::run("launchctl", &["managername"]) {
+        Ok(output) if output.status.success() => session_kind(&String::from_utf8_lossy(&output.stdout)),
+        Ok(_) => SessionKind::Unknown,
+        Err(e) => {
+            trace!("Unable to run launchctl: {:?}", e);
+            SessionKind::Unknown
+        }
+    }
+}
+
+fn session_kind(manager_name: &str) -> SessionKind {
+    match manager_name.trim() {
+        "Aqua" => SessionKind::Desktop,
+        // Logins on a terminal or over SSH without the OpenSSH environment.
+        "StandardIO" => SessionKind::Console,
+        "Background" | "System" | "LoginWindow" => SessionKind::Service,
+        _ => SessionKind::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn kinds() {
+        assert_eq!(SessionKind::Desktop, session_kind("Aqua\n"));
+        assert_eq!(SessionKind::Console, session_kind("StandardIO\n"));
+        assert_eq!(SessionKind::Service, session_kind("System\n"));
+        assert_eq!(SessionKind::Unknown, session_kind(""));
+    }
+}
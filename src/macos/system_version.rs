@@ -0,0 +1,121 @@
+use std::fs;
+
+use crate::logging::warn;
+
+use super::platform::sysctl;
+use crate::{Diagnostic, FieldName, OSInfo, Version, consts, lookup};
+
+const SYSTEM_VERSION_PATH: &str = "/System/Library/CoreServices/SystemVersion.plist";
+
+/// Returns the information from the system version property list. With `run_external_commands`,
+/// a Rapid Security Response missing from it is read with `sysctl`.
+pub fn get_os_data(run_external_commands: bool, diagnostics: &mut Vec<Diagnostic>) -> Option<OSInfo> {
+    match fs::read_to_string(SYSTEM_VERSION_PATH) {
+        Ok(content) => {
+            diagnostics.push(Diagnostic::source_used(SYSTEM_VERSION_PATH));
+            let mut info = parse(&content);
+            if info.version_extra.is_none() && run_external_commands {
+                info.version_extra = sysctl("kern.osproductversionextra");
+            }
+            // The variant is determined separately, see `management::get_variant`.
+            info.record_source(&[FieldName::Name, FieldName::Version, FieldName::Codename], SYSTEM_VERSION_PATH);
+            if info.version == Version::Unknown {
+                diagnostics.push(Diagnostic::field_not_found(SYSTEM_VERSION_PATH, "ProductVersion"));
+            }
+            Some(info)
+        }
+        Err(e) => {
+            warn!("Unable to read {:?} file: {:?}", SYSTEM_VERSION_PATH, e);
+            diagnostics.push(Diagnostic::source_unreadable(SYSTEM_VERSION_PATH, e));
+            None
+        }
+    }
+}
+
+fn parse(plist: &str) -> OSInfo {
+    let version = plist_value(plist, "ProductVersion")
+        .map(Version::from_string)
+        .unwrap_or_default();
+    let codename = lookup::macos_name_for(&version).map(str::to_owned);
+
+    OSInfo {
+        id: Some(String::from(consts::MACOS)),
+        name: plist_value(plist, "ProductName"),
+        version,
+        // Rapid Security Responses, e.g. "(a)", don't change the product version.
+        version_extra: plist_value(plist, "ProductVersionExtra").filter(|extra| !extra.is_empty()),
+        variant: Some(String::from("client")),
+        codename,
+        ..Default::default()
+    }
+}
+
+/// Finds the string value following the given `<key>` in an XML property list.
+fn plist_value(plist: &str, key: &str) -> Option<String> {
+    let key = format!("<key>{key}</key>");
+    let rest = &plist[plist.find(&key)? + key.len()..];
+    let rest = rest.trim_start().strip_prefix("<string>")?;
+    let end = rest.find("</string>")?;
+    Some(rest[..end].trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const SONOMA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+	<key>BuildID</key>
+	<string>6F1C6A42-8F8E-11EE-A8E5-3E3C4B1E5F0C</string>
+	<key>ProductBuildVersion</key>
+	<string>23C71</string>
+	<key>ProductCopyright</key>
+	<string>1983-2023 Apple Inc.</string>
+	<key>ProductName</key>
+	<string>macOS</string>
+	<key>ProductUserVisibleVersion</key>
+	<string>14.2.1</string>
+	<key>ProductVersion</key>
+	<string>14.2.1</string>
+	<key>iOSSupportVersion</key>
+	<string>17.2</string>
+</dict>
+</plist>
+"#;
+
+    #[test]
+    fn sonoma() {
+        let info = parse(SONOMA);
+        assert_eq!("macos", info.id());
+        assert_eq!("macOS", info.name());
+        assert_eq!(&Version::Semantic(14, 2, 1, 0), info.version());
+        assert_eq!(Some("Sonoma"), info.codename());
+    }
+
+    #[test]
+    fn rapid_security_response() {
+        let plist = SONOMA
+            .replace("14.2.1", "13.3.1")
+            .replace("</dict>", "\t<key>ProductVersionExtra</key>\n\t<string>(a)</string>\n</dict>");
+        let info = parse(&plist);
+        assert_eq!(&Version::Semantic(13, 3, 1, 0), info.version());
+        assert_eq!(Some("(a)"), info.version_extra());
+        assert_eq!(Some("Ventura"), info.codename());
+        assert_eq!(None, parse(SONOMA).version_extra());
+    }
+
+    #[test]
+    fn missing_key() {
+        assert_eq!(None, plist_value(SONOMA, "Missing"));
+        assert_eq!(Some("23C71".to_string()), plist_value(SONOMA, "ProductBuildVersion"));
+    }
+
+    #[test]
+    fn current() {
+        let info = get_os_data(true, &mut Vec::new()).unwrap();
+        assert_eq!("macos", info.id());
+    }
+}
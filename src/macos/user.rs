@@ -0,0 +1,39 @@
+#![allow(unsafe_code)]
+
+use std::ffi::{CStr, c_char};
+
+/// Prefix of `struct passwd`, whose first field is the user name on every Unix.
+#[repr(C)]
+struct Passwd {
+    pw_name: *const c_char,
+}
+
+unsafe extern "C" {
+    fn geteuid() -> u32;
+    fn getpwuid(uid: u32) -> *const Passwd;
+}
+
+/// Returns the effective user ID of the process.
+pub fn effective_uid() -> u32 {
+    // SAFETY: geteuid has no preconditions and always succeeds.
+    unsafe { geteuid() }
+}
+
+/// Returns the name of the effective user from the directory services.
+pub fn current_user() -> Option<String> {
+    // SAFETY: getpwuid returns null or a pointer to a static `struct passwd`, which is only read
+    // before the next call.
+    let passwd = unsafe { getpwuid(effective_uid()) };
+    if passwd.is_null() {
+        return None;
+    }
+
+    // SAFETY: `passwd` is valid and `pw_name` points to a NUL-terminated string.
+    let name = unsafe { (*passwd).pw_name };
+    if name.is_null() {
+        return None;
+    }
+    // SAFETY: `name` is a valid NUL-terminated string.
+    let name = unsafe { CStr::from_ptr(name) };
+    Some(name.to_string_lossy().into_owned())
+}
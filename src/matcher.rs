@@ -1,6 +1,11 @@
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    string::{String, ToString},
+};
+
 /// An implementation to match on simple strings.
 #[derive(Debug, Clone)]
-#[allow(dead_code)]
 pub enum Matcher {
     /// Considers the entire string (trimmed) to be the match.
     AllTrimmed,
@@ -31,6 +36,8 @@ impl Matcher {
     ///
     /// An `Option<String>` containing the matched value, or `None` if no match is found.
     ///
+    /// This never panics, whatever the input.
+    ///
     /// # Examples
     ///
     /// ```
@@ -49,6 +56,122 @@ impl Matcher {
             Self::Between { start, end } => slice_string(string, start, end),
         }
     }
+
+    /// Finds the match on the `bytes`, e.g. the contents of a file which may not be valid
+    /// UTF-8. Invalid sequences are replaced with U+FFFD REPLACEMENT CHARACTER before matching.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osinfo::Matcher;
+    /// let matcher = Matcher::KeyValue { key: "VERSION_ID" };
+    /// assert_eq!(matcher.find_bytes(b"NAME=\"Caf\xe9 OS\"\nVERSION_ID=\"2.1\""), Some("2.1".to_string()));
+    /// ```
+    pub fn find_bytes(&self, bytes: &[u8]) -> Option<String> {
+        self.find(&String::from_utf8_lossy(bytes))
+    }
+
+    /// Returns a matcher applying `next` to the match of this matcher, e.g. to find the
+    /// `VERSION` value and then take the text between its parentheses.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use osinfo::Matcher;
+    /// let matcher = Matcher::KeyValue { key: "VERSION" }.then(Matcher::Between { start: '(', end: ')' });
+    /// assert_eq!(matcher.find("VERSION=\"22.04.4 LTS (Jammy Jellyfish)\""), Some("Jammy Jellyfish".to_string()));
+    /// ```
+    pub fn then(self, next: impl Into<CombinedMatcher>) -> CombinedMatcher {
+        CombinedMatcher::from(self).then(next)
+    }
+
+    /// Returns a matcher falling back to `other` if this matcher doesn't match.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use osinfo::Matcher;
+    /// let matcher = Matcher::KeyValue { key: "VERSION_CODENAME" }.or(Matcher::KeyValue { key: "UBUNTU_CODENAME" });
+    /// assert_eq!(matcher.find("UBUNTU_CODENAME=jammy"), Some("jammy".to_string()));
+    /// ```
+    pub fn or(self, other: impl Into<CombinedMatcher>) -> CombinedMatcher {
+        CombinedMatcher::from(self).or(other)
+    }
+
+    /// Returns a matcher transforming the match of this matcher with `f`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use osinfo::Matcher;
+    /// let matcher = Matcher::KeyValue { key: "DISTRIB_ID" }.map(|id| id.to_lowercase());
+    /// assert_eq!(matcher.find("DISTRIB_ID=Ubuntu"), Some("ubuntu".to_string()));
+    /// ```
+    pub fn map(self, f: fn(String) -> String) -> CombinedMatcher {
+        CombinedMatcher::from(self).map(f)
+    }
+}
+
+/// Matchers chained by `Matcher::then`, `Matcher::or` and `Matcher::map`, which can be combined
+/// further.
+///
+/// # Example
+///
+/// ```
+/// use osinfo::Matcher;
+/// let codename = Matcher::KeyValue { key: "VERSION_CODENAME" }
+///     .or(Matcher::KeyValue { key: "VERSION" }.then(Matcher::Between { start: '(', end: ')' }))
+///     .map(|codename| codename.to_lowercase());
+/// assert_eq!(codename.find("VERSION=\"7 (Core)\""), Some("core".to_string()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CombinedMatcher(Combinator);
+
+#[derive(Debug, Clone)]
+enum Combinator {
+    Single(Matcher),
+    Then(Box<CombinedMatcher>, Box<CombinedMatcher>),
+    Or(Box<CombinedMatcher>, Box<CombinedMatcher>),
+    Map(Box<CombinedMatcher>, fn(String) -> String),
+}
+
+impl CombinedMatcher {
+    /// Finds the match on the input `string`, see `Matcher::find`.
+    pub fn find(&self, string: &str) -> Option<String> {
+        match self.0 {
+            Combinator::Single(ref matcher) => matcher.find(string),
+            Combinator::Then(ref first, ref second) => second.find(&first.find(string)?),
+            Combinator::Or(ref first, ref second) => first.find(string).or_else(|| second.find(string)),
+            Combinator::Map(ref matcher, f) => matcher.find(string).map(f),
+        }
+    }
+
+    /// Finds the match on the `bytes`, see `Matcher::find_bytes`.
+    pub fn find_bytes(&self, bytes: &[u8]) -> Option<String> {
+        self.find(&String::from_utf8_lossy(bytes))
+    }
+
+    /// Returns a matcher applying `next` to the match of this matcher, see `Matcher::then`.
+    pub fn then(self, next: impl Into<CombinedMatcher>) -> CombinedMatcher {
+        Self(Combinator::Then(Box::new(self), Box::new(next.into())))
+    }
+
+    /// Returns a matcher falling back to `other` if this matcher doesn't match, see
+    /// `Matcher::or`.
+    pub fn or(self, other: impl Into<CombinedMatcher>) -> CombinedMatcher {
+        Self(Combinator::Or(Box::new(self), Box::new(other.into())))
+    }
+
+    /// Returns a matcher transforming the match of this matcher with `f`, see `Matcher::map`.
+    pub fn map(self, f: fn(String) -> String) -> CombinedMatcher {
+        Self(Combinator::Map(Box::new(self), f))
+    }
+}
+
+impl From<Matcher> for CombinedMatcher {
+    fn from(matcher: Matcher) -> Self {
+        Self(Combinator::Single(matcher))
+    }
 }
 
 /// Returns the substring between the first occurrence of `start_char` and the next occurrence of `end_char`.
@@ -72,10 +195,11 @@ impl Matcher {
 /// ```
 fn slice_string(input: &str, start_char: char, end_char: char) -> Option<String> {
     if let Some(start_idx) = input.find(start_char) {
-        if let Some(end_idx) = input[start_idx + 1..].find(end_char) {
-            return Some(&input[start_idx+1..start_idx+end_idx+1]).and_then(|s| Some(s.to_string()));
+        let content_idx = start_idx + start_char.len_utf8();
+        if let Some(end_idx) = input[content_idx..].find(end_char) {
+            return Some(&input[content_idx..content_idx + end_idx]).map(|s| s.to_string());
         }
-        return Some(&input[start_idx..]).and_then(|s| Some(s.to_string()));
+        return Some(&input[start_idx..]).map(|s| s.to_string());
     }
     None
 }
@@ -170,6 +294,7 @@ fn is_valid_version(word: &str) -> bool {
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use proptest::prelude::*;
 
     #[test]
     fn trimmed() {
@@ -270,4 +395,70 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn between_multibyte_start() {
+        let matcher = Matcher::Between { start: '«', end: '»' };
+        assert_eq!(matcher.find("Debian «bookworm»"), Some("bookworm".to_string()));
+    }
+
+    #[test]
+    fn between_non_ascii() {
+        let data = [
+            (('(', ')'), "Ubuntu 22.04 LTS (ジャミー・ジェリーフィッシュ)", Some("ジャミー・ジェリーフィッシュ")),
+            (('（', '）'), "Kylin V10（Lance）", Some("Lance")),
+            (('«', '»'), "Début «sans fin", Some("«sans fin")),
+            (('«', '»'), "Aucun délimiteur", None),
+            (('é', 'é'), "éé", Some("")),
+        ];
+
+        for ((start, end), input, expected) in &data {
+            let matcher = Matcher::Between { start: *start, end: *end };
+            assert_eq!(matcher.find(input).as_deref(), *expected);
+        }
+    }
+
+    #[test]
+    fn bytes() {
+        let input = b"NAME=\"Caf\xe9 Linux\"\nID=cafe\n";
+        assert_eq!(Matcher::KeyValue { key: "ID" }.find_bytes(input).as_deref(), Some("cafe"));
+        assert_eq!(Matcher::KeyValue { key: "NAME" }.find_bytes(input).as_deref(), Some("Caf\u{FFFD} Linux"));
+    }
+
+    #[test]
+    fn combinators() {
+        let codename = Matcher::KeyValue { key: "VERSION_CODENAME" }
+            .or(Matcher::KeyValue { key: "VERSION" }.then(Matcher::Between { start: '(', end: ')' }))
+            .map(|codename| codename.to_lowercase());
+
+        let data = [
+            ("VERSION_CODENAME=bookworm\nVERSION=\"12 (Bookworm)\"", Some("bookworm")),
+            ("VERSION=\"7 (Core)\"", Some("core")),
+            ("VERSION=\"15-SP5\"", None),
+            ("ID=alpine", None),
+        ];
+
+        for (input, expected) in &data {
+            assert_eq!(codename.find(input).as_deref(), *expected);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn find_never_panics(input in any::<String>(), start in any::<char>(), end in any::<char>()) {
+            let matchers = [
+                Matcher::AllTrimmed,
+                Matcher::PrefixedWord { prefix: "VERSION" },
+                Matcher::PrefixedVersion { prefix: "release" },
+                Matcher::KeyValue { key: "ID" },
+                Matcher::Between { start, end },
+            ];
+
+            for matcher in &matchers {
+                matcher.find(&input);
+            }
+            let combined = Matcher::KeyValue { key: "VERSION" }.then(Matcher::Between { start, end }).or(Matcher::AllTrimmed);
+            combined.find(&input);
+        }
+    }
 }
@@ -0,0 +1,82 @@
+//! This module defines the `NetworkEnv` struct describing the proxy configuration of the
+//! operating system.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Proxy configuration for outgoing HTTP(S) connections.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NetworkEnv {
+    /// Proxy for HTTP requests, e.g. "http://proxy.example.com:8080".
+    pub http_proxy: Option<String>,
+    /// Proxy for HTTPS requests, e.g. "http://proxy.example.com:8080".
+    pub https_proxy: Option<String>,
+    /// Hosts and domains reached without proxy, e.g. "localhost", ".example.com" or
+    /// "10.0.0.0/8".
+    pub no_proxy: Vec<String>,
+    /// URL of the proxy auto-configuration (PAC) script, e.g. "http://wpad/wpad.dat".
+    pub auto_config_url: Option<String>,
+}
+
+#[cfg_attr(not(feature = "detect"), allow(dead_code))]
+impl NetworkEnv {
+    /// Reads the proxy configuration from the conventional environment variables (`http_proxy`,
+    /// `https_proxy`, `all_proxy` and `no_proxy`, in lower or upper case), given a lookup of
+    /// environment variables.
+    pub(crate) fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        let lookup = |name: &str| {
+            var(name)
+                .or_else(|| var(&name.to_uppercase()))
+                .filter(|value| !value.trim().is_empty())
+        };
+        let all_proxy = lookup("all_proxy");
+        Self {
+            http_proxy: lookup("http_proxy").or_else(|| all_proxy.clone()),
+            https_proxy: lookup("https_proxy").or(all_proxy),
+            no_proxy: lookup("no_proxy").map(|hosts| split_hosts(&hosts, ',')).unwrap_or_default(),
+            auto_config_url: None,
+        }
+    }
+
+    /// Returns whether neither a proxy nor an auto-configuration script is configured.
+    pub fn is_direct(&self) -> bool {
+        self.http_proxy.is_none() && self.https_proxy.is_none() && self.auto_config_url.is_none()
+    }
+}
+
+/// Splits a list of hosts, dropping empty entries.
+#[cfg_attr(not(feature = "detect"), allow(dead_code))]
+pub(crate) fn split_hosts(hosts: &str, separator: char) -> Vec<String> {
+    hosts
+        .split(separator)
+        .map(str::trim)
+        .filter(|host| !host.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn environment() {
+        let environment = [
+            ("HTTP_PROXY", "http://proxy.example.com:3128"),
+            ("all_proxy", "socks5://socks.example.com:1080"),
+            ("no_proxy", "localhost, .example.com,,10.0.0.0/8"),
+        ];
+        let var = |name: &str| environment.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string());
+
+        let expected = NetworkEnv {
+            http_proxy: Some(String::from("http://proxy.example.com:3128")),
+            https_proxy: Some(String::from("socks5://socks.example.com:1080")),
+            no_proxy: vec![String::from("localhost"), String::from(".example.com"), String::from("10.0.0.0/8")],
+            auto_config_url: None,
+        };
+        assert_eq!(expected, NetworkEnv::from_env(var));
+        assert!(NetworkEnv::from_env(|_| None).is_direct());
+    }
+}
@@ -1,11 +1,32 @@
 //! This module defines the `OSInfo` struct and related methods for representing and querying
 //! operating system information in a platform-agnostic way.
 
-use std::fmt::{self, Display, Formatter};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display, Formatter, Write};
 
-use super::{Version};
+use super::{DistroFamily, FieldChange, FieldName, OSInfoBuilder, OsKind, Version, consts};
 
 /// Represents information about an operating system, such as its ID, name, version, variant, edition, and codename.
+///
+/// The fields are only accessible through getters, and more may be added in minor releases.
+/// Detection functions such as `osinfo::get()` return populated values; to construct one
+/// yourself, use `OSInfo::builder()`, `OSInfo::new_unknown()` (formerly `OSInfo::unknown()`),
+/// or the `with_id` / `with_name` shorthands:
+///
+/// ```
+/// use osinfo::{OSInfo, Version};
+/// // Before: OSInfo::unknown()
+/// let unknown = OSInfo::new_unknown();
+/// let fedora = OSInfo::builder().id("fedora").name("Fedora Linux").version(Version::Semantic(40, 0, 0, 0)).build();
+/// assert_eq!(unknown.version(), &Version::Unknown);
+/// assert_eq!(fedora.name(), "Fedora Linux");
+/// ```
+#[non_exhaustive]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OSInfo {
@@ -17,6 +38,10 @@ pub struct OSInfo {
     pub(crate) name: Option<String>,
     /// Operating system version. See `Version` for details.
     pub(crate) version: Version,
+    /// Supplemental identifier of the version, e.g. "(a)" for a macOS Rapid Security Response
+    /// applied to 13.3.1.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub(crate) version_extra: Option<String>,
     /// Operating system variant.
     /// This is the variant of the operating system, such as "Server", "client", "Embedded", etc.
     /// It may be `None` if the variant is not known or not applicable.
@@ -25,6 +50,23 @@ pub struct OSInfo {
     pub(crate) edition: Option<String>,
     /// Operating system codename.
     pub(crate) codename: Option<String>,
+    /// Whether the release is a long-term support one (e.g. Ubuntu 22.04 LTS). Defaults to
+    /// `false` when deserializing values serialized by versions that didn't have it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) lts: bool,
+    /// Environment this operating system runs in, e.g. the WSL layer for a WSL distribution or
+    /// ChromeOS for a Crostini container. The host may itself have a host, forming a chain from
+    /// the innermost to the outermost layer. See `host` for details.
+    pub(crate) host: Option<Box<OSInfo>>,
+    /// Windows product type (SKU) number returned by `GetProductInfo`, e.g. `0x7D` for
+    /// `PRODUCT_ENTERPRISE_S`.
+    pub(crate) product_sku: Option<u32>,
+    /// Operating system name as displayed to the user, which may be localized. Only set when it
+    /// differs from the locale-neutral `name`, i.e. on Windows.
+    pub(crate) localized_name: Option<String>,
+    /// Source each field was detected from, e.g. `/etc/os-release` or a registry key.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
+    pub(crate) provenance: Vec<(FieldName, String)>,
 }
 
 impl OSInfo {
@@ -36,97 +78,647 @@ impl OSInfo {
     /// # Examples
     /// ```
     /// use osinfo::{Version, OSInfo};
-    /// let info = OSInfo::unknown();
-    /// assert_eq!(String::from("Unknown"), info.get_id());
-    /// assert_eq!(String::new(), info.get_name());
-    /// assert_eq!(Version::Unknown, info.get_version());
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!("Unknown", info.id());
+    /// assert_eq!("", info.name());
+    /// assert_eq!(&Version::Unknown, info.version());
     /// ```
-    pub fn unknown() -> Self {
+    pub fn new_unknown() -> Self {
         Self {
-            id: Some(String::from("Unknown")),
+            id: Some(String::from(consts::UNKNOWN)),
             name: Some(String::new()),
             version: Version::Unknown,
+            version_extra: None,
             variant: None,
             edition: None,
             codename: None,
+            lts: false,
+            host: None,
+            product_sku: None,
+            localized_name: None,
+            provenance: Vec::new(),
         }
     }
 
-    /// Returns the operating system ID as a `String`.
+    /// Constructs an `OSInfo` instance representing an unknown operating system.
+    #[deprecated(since = "1.1.0", note = "use `new_unknown()` or `builder()`")]
+    pub fn unknown() -> Self {
+        Self::new_unknown()
+    }
+
+    /// Returns a builder to construct an `OSInfo` value field by field, starting from
+    /// `new_unknown()`.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{OSInfo, Version};
+    /// let info = OSInfo::builder().id("debian").version(Version::Semantic(12, 5, 0, 0)).build();
+    /// assert_eq!(info.id(), "debian");
+    /// assert_eq!(info.name(), "");
+    /// ```
+    pub fn builder() -> OSInfoBuilder {
+        OSInfoBuilder::new()
+    }
+
+    /// Constructs an `OSInfo` instance describing the target the crate was compiled for, from the
+    /// `target_os`, `target_arch` and `target_env` configuration without any runtime detection,
+    /// e.g. for cross-compilation tooling reporting the build target next to the host.
+    ///
+    /// The ID is the target operating system (e.g. "linux" or "windows"), the edition the
+    /// architecture (e.g. "x86_64" or "aarch64") and the variant the ABI (e.g. "gnu", "musl" or
+    /// "msvc"), if any. The version is unknown. The provenance names the configuration option
+    /// each field comes from.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{FieldName, OSInfo};
+    /// let target = OSInfo::target();
+    /// assert_eq!(target.id(), std::env::consts::OS);
+    /// assert_eq!(target.edition(), Some(std::env::consts::ARCH));
+    /// assert_eq!(target.source_of(FieldName::Edition), Some("target_arch"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn target() -> Self {
+        use std::env::consts::{ARCH, OS};
+
+        let name = TARGET_NAMES
+            .iter()
+            .find(|&&(id, _)| id == OS)
+            .map_or(OS, |&(_, name)| name);
+        let mut info = Self {
+            id: Some(String::from(OS)),
+            name: Some(String::from(name)),
+            version: Version::Unknown,
+            variant: TARGET_ENV.map(String::from),
+            edition: Some(String::from(ARCH)),
+            ..Default::default()
+        };
+        info.record_source(&[FieldName::Id, FieldName::Name], "target_os");
+        info.record_source(&[FieldName::Edition], "target_arch");
+        info.record_source(&[FieldName::Variant], "target_env");
+        info
+    }
+
+    /// Returns the operating system ID, e.g. "ubuntu" or "windows".
     /// If the ID is not set, returns an empty string.
     ///
     /// # Example
     /// ```
     /// use osinfo::OSInfo;
-    /// let info = OSInfo::unknown();
-    /// assert_eq!(info.get_id(), "Unknown");
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.id(), "Unknown");
     /// ```
+    pub fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the operating system ID as a `String`.
+    /// If the ID is not set, returns an empty string.
+    #[deprecated(since = "1.1.0", note = "use `id()`, which doesn't allocate")]
     pub fn get_id(&self) -> String {
-        self.id.clone().unwrap_or_default()
+        self.id().to_string()
     }
-    
-    /// Returns the operating system name as a `String`.
+
+    /// Returns the operating system name, e.g. "Ubuntu" or "Windows 11 Pro".
     /// If the name is not set, returns an empty string.
     ///
+    /// The name is locale-neutral, e.g. "Windows 11 Pro" even on a German Windows, so it is safe
+    /// to match on. See `localized_name` for the name as displayed to the user.
+    ///
     /// # Example
     /// ```
     /// use osinfo::OSInfo;
-    /// let info = OSInfo::unknown();
-    /// assert_eq!(info.get_name(), "");
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.name(), "");
     /// ```
+    pub fn name(&self) -> &str {
+        self.name.as_deref().unwrap_or_default()
+    }
+
+    /// Returns the operating system name as a `String`.
+    /// If the name is not set, returns an empty string.
+    #[deprecated(since = "1.1.0", note = "use `name()`, which doesn't allocate")]
     pub fn get_name(&self) -> String {
-        self.name.clone().unwrap_or_default()
+        self.name().to_string()
+    }
+
+    /// Returns the operating system name as displayed to the user, which may be localized, e.g.
+    /// the `ProductName` registry value on Windows. Falls back to `name` on the operating
+    /// systems that don't localize it.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::with_name("Ubuntu".to_string());
+    /// assert_eq!(info.localized_name(), "Ubuntu");
+    /// ```
+    pub fn localized_name(&self) -> &str {
+        self.localized_name.as_deref().unwrap_or_else(|| self.name())
+    }
+
+    /// Returns the name translated into the language of the BCP 47 tag `language`, e.g. "fr",
+    /// "fr-CA" or "zh-TW", from tables embedded in the crate, e.g. to show "Windows 11 Famille"
+    /// in a French dialog on an English system. Falls back to `name` if the vendor doesn't
+    /// translate it, e.g. for Linux distributions and macOS.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::with_name("Windows 11 Home".to_string());
+    /// assert_eq!(info.display_name_in("fr"), "Windows 11 Famille");
+    /// assert_eq!(info.display_name_in("de"), "Windows 11 Home");
+    /// ```
+    #[cfg(feature = "i18n")]
+    pub fn display_name_in(&self, language: &str) -> String {
+        crate::i18n::translate(self.name(), language).unwrap_or_else(|| self.name().to_string())
     }
 
-    /// Returns the operating system version as a `Version`.
+    /// Returns the operating system version.
     ///
     /// # Example
     /// ```
     /// use osinfo::{Version, OSInfo};
-    /// let info = OSInfo::unknown();
-    /// assert_eq!(info.get_version(), Version::Unknown);
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.version(), &Version::Unknown);
     /// ```
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Returns a copy of the operating system version.
+    #[deprecated(since = "1.1.0", note = "use `version()`, which doesn't allocate")]
     pub fn get_version(&self) -> Version {
         self.version.clone()
     }
 
+    /// Returns the supplemental identifier of the version, if any: the Rapid Security Response
+    /// applied on macOS, e.g. "(a)" for "13.3.1 (a)". The version itself stays 13.3.1.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.version_extra(), None);
+    /// ```
+    pub fn version_extra(&self) -> Option<&str> {
+        self.version_extra.as_deref()
+    }
+
+    /// Returns the operating system variant, e.g. "server" or "client", if known.
+    ///
+    /// - Linux: `VARIANT_ID` of os-release, e.g. "server" or "workstation"; "embedded" on
+    ///   single-board computers, and "client" if the distribution doesn't say.
+    /// - Windows: the installation type, "Client" or "Server" (e.g. "Server Core").
+    /// - macOS: "client", "managed client" if enrolled in an MDM, or "server".
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.variant(), None);
+    /// ```
+    pub fn variant(&self) -> Option<&str> {
+        self.variant.as_deref()
+    }
+
     /// Returns the operating system variant as a `String`.
     /// If the variant is not set, returns an empty string.
+    #[deprecated(since = "1.1.0", note = "use `variant()`, which doesn't allocate")]
+    pub fn get_variant(&self) -> String {
+        self.variant().unwrap_or_default().to_string()
+    }
+
+    /// Returns the operating system edition, e.g. "Professional" on Windows, if known.
+    ///
+    /// - Linux: the board of single-board computers (e.g. "Raspberry Pi 4 Model B Rev 1.4") and
+    ///   the Portage profile on Gentoo; unset otherwise.
+    /// - Windows: the edition ID, e.g. "Professional" or "ServerDatacenter".
+    /// - macOS: the processor family of the Mac, "Apple Silicon" or "Intel".
     ///
     /// # Example
     /// ```
     /// use osinfo::OSInfo;
-    /// let info = OSInfo::unknown();
-    /// assert_eq!(info.get_variant(), "");
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.edition(), None);
     /// ```
-    pub fn get_variant(&self) -> String {
-        self.variant.clone().unwrap_or_default()
+    pub fn edition(&self) -> Option<&str> {
+        self.edition.as_deref()
     }
 
     /// Returns the operating system edition as a `String`.
     /// If the edition is not set, returns an empty string.
+    #[deprecated(since = "1.1.0", note = "use `edition()`, which doesn't allocate")]
+    pub fn get_edition(&self) -> String {
+        self.edition().unwrap_or_default().to_string()
+    }
+
+    /// Returns the operating system codename, e.g. "jammy", or the display version on Windows
+    /// (e.g. "23H2", or "Server 2022 21H2" on Windows Server), if known.
+    ///
+    /// - Linux: `VERSION_CODENAME` of os-release, e.g. "jammy" or "bookworm".
+    /// - Windows: the display version, e.g. "23H2".
+    /// - macOS: the marketing name, e.g. "Sonoma".
     ///
     /// # Example
     /// ```
     /// use osinfo::OSInfo;
-    /// let info = OSInfo::unknown();
-    /// assert_eq!(info.get_edition(), "");
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.codename(), None);
     /// ```
-    pub fn get_edition(&self) -> String {
-        self.edition.clone().unwrap_or_default()
+    pub fn codename(&self) -> Option<&str> {
+        self.codename.as_deref()
     }
 
     /// Returns the operating system codename as a `String`.
     /// If the codename is not set, returns an empty string.
+    #[deprecated(since = "1.1.0", note = "use `codename()`, which doesn't allocate")]
+    pub fn get_codename(&self) -> String {
+        self.codename().unwrap_or_default().to_string()
+    }
+
+    /// Returns `true` if the release is a long-term support one, e.g. Ubuntu 22.04 LTS.
     ///
     /// # Example
     /// ```
     /// use osinfo::OSInfo;
-    /// let info = OSInfo::unknown();
-    /// assert_eq!(info.get_codename(), "");
+    /// let info = OSInfo::new_unknown();
+    /// assert!(!info.is_lts());
     /// ```
-    pub fn get_codename(&self) -> String {
-        self.codename.clone().unwrap_or_default()
+    pub fn is_lts(&self) -> bool {
+        self.lts
+    }
+
+    /// Returns the Windows product type (SKU) number, e.g. `0x7D` for `PRODUCT_ENTERPRISE_S`
+    /// (Windows Enterprise LTSC), or `None` on other operating systems.
+    ///
+    /// Unlike the edition, the number is the same in every language, so licensing and compliance
+    /// tooling should prefer it. See the `PRODUCT_*` constants of `GetProductInfo` for the values.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.product_sku(), None);
+    /// ```
+    pub fn product_sku(&self) -> Option<u32> {
+        self.product_sku
+    }
+
+    /// Returns the source each field was detected from, e.g. `(FieldName::Version,
+    /// "/etc/redhat-release")` when os-release only had the major version. Fields that weren't
+    /// detected, or were set by the caller, have no entry.
+    ///
+    /// This tells why a machine reports an unexpected value, see also
+    /// `osinfo::get_with_diagnostics`.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::with_id("ubuntu".to_string());
+    /// assert!(info.provenance().is_empty());
+    /// ```
+    pub fn provenance(&self) -> &[(FieldName, String)] {
+        &self.provenance
+    }
+
+    /// Returns the source the given field was detected from, if known. See `provenance`.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{FieldName, OSInfo};
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.source_of(FieldName::Id), None);
+    /// ```
+    pub fn source_of(&self, field: FieldName) -> Option<&str> {
+        self.provenance
+            .iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, source)| source.as_str())
+    }
+
+    /// Returns the value of the given field as a string, e.g. "22.4.4.0" for the version (followed
+    /// by the `version_extra`, if any), "true" for `Lts` or "0x30" for `ProductSku`, or `None` if
+    /// the field isn't set. `LocalizedName` is `None` unless a localized name was detected.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{FieldName, OSInfo};
+    /// let info = OSInfo::with_id("ubuntu".to_string());
+    /// assert_eq!(info.field_value(FieldName::Id), Some("ubuntu".to_string()));
+    /// assert_eq!(info.field_value(FieldName::Version), None);
+    /// ```
+    pub fn field_value(&self, field: FieldName) -> Option<String> {
+        match field {
+            FieldName::Id => self.id.clone(),
+            FieldName::Name => self.name.clone(),
+            FieldName::Version => match (&self.version, &self.version_extra) {
+                (Version::Unknown, _) => None,
+                (version, Some(extra)) => Some(alloc::format!("{version} {extra}")),
+                (version, None) => Some(version.to_string()),
+            },
+            FieldName::Variant => self.variant.clone(),
+            FieldName::Edition => self.edition.clone(),
+            FieldName::Codename => self.codename.clone(),
+            FieldName::Lts => Some(self.lts.to_string()),
+            FieldName::ProductSku => self.product_sku.map(|sku| alloc::format!("{sku:#x}")),
+            FieldName::LocalizedName => self.localized_name.clone(),
+        }
+    }
+
+    /// Returns every field with its value, in the order of `FieldName::ALL`, e.g. for exporters
+    /// writing CSV columns or key-value pairs that shouldn't need updating when a field is added.
+    /// The values are formatted like `field_value`; the string fields are borrowed rather than
+    /// cloned.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{FieldName, OSInfo};
+    /// let info = OSInfo::with_id("ubuntu".to_string());
+    /// for (field, value) in info.fields() {
+    ///     println!("{field}={}", value.unwrap_or_default());
+    /// }
+    /// let (field, value) = info.fields().next().unwrap();
+    /// assert_eq!((FieldName::Id, Some("ubuntu")), (field, value.as_deref()));
+    /// ```
+    pub fn fields(&self) -> impl Iterator<Item = (FieldName, Option<Cow<'_, str>>)> {
+        FieldName::ALL.into_iter().map(move |field| {
+            let value = match field {
+                FieldName::Id => self.id.as_deref().map(Cow::Borrowed),
+                FieldName::Name => self.name.as_deref().map(Cow::Borrowed),
+                FieldName::Variant => self.variant.as_deref().map(Cow::Borrowed),
+                FieldName::Edition => self.edition.as_deref().map(Cow::Borrowed),
+                FieldName::Codename => self.codename.as_deref().map(Cow::Borrowed),
+                FieldName::Lts => Some(Cow::Borrowed(if self.lts { "true" } else { "false" })),
+                FieldName::LocalizedName => self.localized_name.as_deref().map(Cow::Borrowed),
+                FieldName::Version | FieldName::ProductSku => self.field_value(field).map(Cow::Owned),
+            };
+            (field, value)
+        })
+    }
+
+    /// Returns `true` if the given field has the same value in both, e.g. to check whether only
+    /// the version changed.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{FieldName, OSInfo};
+    /// let ubuntu = OSInfo::with_id("ubuntu".to_string());
+    /// assert!(ubuntu.field_eq(&OSInfo::with_id("ubuntu".to_string()), FieldName::Id));
+    /// assert!(!ubuntu.field_eq(&OSInfo::with_id("debian".to_string()), FieldName::Id));
+    /// ```
+    pub fn field_eq(&self, other: &Self, field: FieldName) -> bool {
+        match field {
+            FieldName::Id => self.id == other.id,
+            FieldName::Name => self.name == other.name,
+            FieldName::Version => self.version == other.version && self.version_extra == other.version_extra,
+            FieldName::Variant => self.variant == other.variant,
+            FieldName::Edition => self.edition == other.edition,
+            FieldName::Codename => self.codename == other.codename,
+            FieldName::Lts => self.lts == other.lts,
+            FieldName::ProductSku => self.product_sku == other.product_sku,
+            FieldName::LocalizedName => self.localized_name == other.localized_name,
+        }
+    }
+
+    /// Returns `true` if both describe the same release, i.e. the ID and the version are equal.
+    /// Unlike `==`, the host chain, the provenance and the other fields are ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::new_unknown();
+    /// assert!(info.same_release(&OSInfo::with_name("Ubuntu".to_string())));
+    /// assert!(!info.same_release(&OSInfo::with_id("ubuntu".to_string())));
+    /// ```
+    pub fn same_release(&self, other: &Self) -> bool {
+        self.field_eq(other, FieldName::Id) && self.field_eq(other, FieldName::Version)
+    }
+
+    /// Returns the fields whose value differs from `newer`, in the order of `FieldName::ALL`,
+    /// e.g. to report "version changed from 22.4.0.0 to 24.4.0.0" when comparing a cached
+    /// snapshot with the current one.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{FieldName, OSInfo};
+    /// let previous = OSInfo::with_id("ubuntu".to_string());
+    /// let current = OSInfo::with_id("debian".to_string());
+    /// let changes = previous.diff(&current);
+    /// assert_eq!(changes.len(), 1);
+    /// assert_eq!(changes[0].field, FieldName::Id);
+    /// assert_eq!(changes[0].to_string(), "id changed from ubuntu to debian");
+    /// ```
+    pub fn diff(&self, newer: &Self) -> Vec<FieldChange> {
+        FieldName::ALL
+            .iter()
+            .filter(|&&field| !self.field_eq(newer, field))
+            .map(|&field| FieldChange {
+                field,
+                old: self.field_value(field),
+                new: newer.field_value(field),
+            })
+            .collect()
+    }
+
+    /// Returns a stable fingerprint of the release, e.g. as a cache key or to deduplicate
+    /// telemetry: the lowercase hexadecimal SHA-256 hash of a canonical serialization of the
+    /// ID, name, version (with the `version_extra`), variant, edition, codename and LTS flag.
+    ///
+    /// The fingerprint only depends on these fields, and is the same on every platform and for
+    /// every version of this crate. The host chain, the provenance, the localized name, the
+    /// product SKU and fields added in the future don't participate.
+    ///
+    /// The serialization starts with the line `osinfo-fingerprint-v1`, followed by one line per
+    /// field in the order above: the field name (see `FieldName::as_str`), then a space and `-`
+    /// if the field isn't set, or a space, the length of the value in bytes, a space and the
+    /// value as formatted by `field_value`. Every line ends with a line feed.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let fingerprint = OSInfo::with_id("ubuntu".to_string()).fingerprint();
+    /// assert_eq!(fingerprint.len(), 64);
+    /// assert_eq!(fingerprint, OSInfo::with_id("ubuntu".to_string()).fingerprint());
+    /// assert_ne!(fingerprint, OSInfo::with_id("debian".to_string()).fingerprint());
+    /// ```
+    pub fn fingerprint(&self) -> String {
+        const FIELDS: [FieldName; 7] = [
+            FieldName::Id,
+            FieldName::Name,
+            FieldName::Version,
+            FieldName::Variant,
+            FieldName::Edition,
+            FieldName::Codename,
+            FieldName::Lts,
+        ];
+
+        let mut canonical = String::from("osinfo-fingerprint-v1\n");
+        for field in FIELDS {
+            // Writing to a `String` can't fail.
+            let _ = match self.field_value(field) {
+                Some(value) => writeln!(canonical, "{field} {} {value}", value.len()),
+                None => writeln!(canonical, "{field} -"),
+            };
+        }
+
+        crate::sha256::hex_digest(canonical.as_bytes())
+    }
+
+    /// Records `source` as the source of the given fields. Fields that aren't set lose their
+    /// previous source.
+    #[cfg_attr(not(feature = "detect"), allow(dead_code))]
+    pub(crate) fn record_source(&mut self, fields: &[FieldName], source: &str) {
+        for &field in fields {
+            let is_set = match field {
+                FieldName::Id => self.id.is_some(),
+                FieldName::Name => self.name.is_some(),
+                FieldName::Version => self.version != Version::Unknown,
+                FieldName::Variant => self.variant.is_some(),
+                FieldName::Edition => self.edition.is_some(),
+                FieldName::Codename => self.codename.is_some(),
+                FieldName::Lts => self.lts,
+                FieldName::ProductSku => self.product_sku.is_some(),
+                FieldName::LocalizedName => self.localized_name.is_some(),
+            };
+
+            self.provenance.retain(|(name, _)| *name != field);
+            if is_set {
+                self.provenance.push((field, source.to_string()));
+            }
+        }
+    }
+
+    /// Returns the environment this operating system runs in, if it runs in a layered environment.
+    ///
+    /// Each layer is described by its own `OSInfo`, whose host is the next outer layer:
+    ///
+    /// - WSL: the distribution's host is the WSL layer (id `wsl`, version 1 or 2), whose host is
+    ///   Windows.
+    /// - Crostini: the container's host is ChromeOS (id `chromeos`, version is the milestone).
+    /// - Containers: the host is the Linux kernel shared with the host (id `linux`, version is the
+    ///   kernel version). The host distribution isn't visible from inside the container.
+    /// - Virtual machines: the host is the hypervisor (e.g. id `kvm`, `vmware` or `hyperv`), since
+    ///   the host operating system isn't visible from inside the guest.
+    ///
+    /// For example, an Ubuntu container in a VM is represented as Ubuntu → Linux → KVM.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::new_unknown();
+    /// assert!(info.host().is_none());
+    /// ```
+    pub fn host(&self) -> Option<&OSInfo> {
+        self.host.as_deref()
+    }
+
+    /// Returns an iterator over the chain of hosts, from the innermost to the outermost layer.
+    /// See `host` for details.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let info = OSInfo::new_unknown();
+    /// assert_eq!(info.hosts().count(), 0);
+    /// ```
+    pub fn hosts(&self) -> impl Iterator<Item = &OSInfo> {
+        core::iter::successors(self.host(), |host| host.host())
+    }
+
+    /// Returns the family the operating system belongs to, derived from its ID.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{DistroFamily, OSInfo};
+    /// let info = OSInfo::with_id("ubuntu".to_string());
+    /// assert_eq!(info.family(), DistroFamily::Debian);
+    /// ```
+    pub fn family(&self) -> DistroFamily {
+        DistroFamily::of(self.id.as_deref().unwrap_or_default())
+    }
+
+    /// Returns the kind of the operating system, derived from its ID.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{OsKind, OSInfo};
+    /// let info = OSInfo::with_id("fedora".to_string());
+    /// assert_eq!(info.kind(), OsKind::Linux);
+    /// ```
+    pub fn kind(&self) -> OsKind {
+        OsKind::of(self.id.as_deref().unwrap_or_default())
+    }
+
+    /// Returns `true` if the operating system is Windows, see `OsKind::Windows`.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// assert!(OSInfo::with_id("windows".to_string()).is_windows());
+    /// assert!(!OSInfo::with_id("ubuntu".to_string()).is_windows());
+    /// ```
+    pub fn is_windows(&self) -> bool {
+        self.kind() == OsKind::Windows
+    }
+
+    /// Returns `true` if the operating system is macOS.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// assert!(OSInfo::with_id("macos".to_string()).is_macos());
+    /// ```
+    pub fn is_macos(&self) -> bool {
+        self.kind() == OsKind::MacOS
+    }
+
+    /// Returns `true` if the operating system is a Linux distribution, including one running in
+    /// WSL or a container (see `host` for the environment).
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// assert!(OSInfo::with_id("alpine".to_string()).is_linux());
+    /// assert!(!OSInfo::new_unknown().is_linux());
+    /// ```
+    pub fn is_linux(&self) -> bool {
+        self.kind() == OsKind::Linux
+    }
+
+    /// Returns `true` if the operating system belongs to the given family, e.g. Ubuntu to
+    /// `DistroFamily::Debian`.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{DistroFamily, OSInfo};
+    /// let info = OSInfo::with_id("rocky".to_string());
+    /// assert!(info.is_like(DistroFamily::RedHat));
+    /// assert!(!info.is_like(DistroFamily::Debian));
+    /// ```
+    pub fn is_like(&self, family: DistroFamily) -> bool {
+        self.family() == family
+    }
+
+    /// Returns `true` if the version is a semantic version greater than or equal to `version`,
+    /// a semantic version of one to four components, e.g. "10.15" or "10.0.22000".
+    ///
+    /// Returns `false` if the version isn't semantic (unknown, rolling or custom), and if
+    /// `version` isn't a valid semantic version (see `Version::parse_strict`), so that a typo
+    /// never enables a code path.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{OSInfo, Version};
+    /// let info = OSInfo::builder().id("macos").version(Version::Semantic(14, 2, 1, 0)).build();
+    /// assert!(info.version_at_least("10.15"));
+    /// assert!(info.version_at_least("14.2.1"));
+    /// assert!(!info.version_at_least("15"));
+    /// ```
+    pub fn version_at_least(&self, version: &str) -> bool {
+        match (&self.version, Version::parse_strict(version)) {
+            (Version::Semantic(..), Ok(minimum @ Version::Semantic(..))) => self.version >= minimum,
+            _ => false,
+        }
     }
 
     /// Constructs an `OSInfo` instance with the specified ID.
@@ -136,7 +728,7 @@ impl OSInfo {
     /// ```
     /// use osinfo::OSInfo;
     /// let info = OSInfo::with_id("linux".to_string());
-    /// assert_eq!(info.get_id(), "linux");
+    /// assert_eq!(info.id(), "linux");
     /// ```
     pub fn with_id(id: String) -> Self {
         Self {
@@ -152,7 +744,7 @@ impl OSInfo {
     /// ```
     /// use osinfo::OSInfo;
     /// let info = OSInfo::with_name("Ubuntu".to_string());
-    /// assert_eq!(info.get_name(), "Ubuntu");
+    /// assert_eq!(info.name(), "Ubuntu");
     /// ```
     pub fn with_name(name: String) -> Self {
         Self {
@@ -161,17 +753,340 @@ impl OSInfo {
         }
     }
 
+    /// Returns the information as label pairs for Prometheus `node_os_info`-style gauges.
+    ///
+    /// The labels are `os_id`, `os_name`, `os_version`, `os_variant`, `os_edition`,
+    /// `os_codename`, `os_lts` (`true` / `false`) and `os_family`, always in this order. Unknown
+    /// values are empty strings, which Prometheus treats like missing labels.
+    ///
+    /// The values are escaped as required by the Prometheus text exposition format: a backslash
+    /// becomes `\\`, a double quote `\"` and a line feed `\n`. Carriage returns and other
+    /// control characters are dropped, and surrounding whitespace is trimmed.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let labels = OSInfo::with_id("ubuntu".to_string()).as_labels();
+    /// assert_eq!(labels[0], ("os_id".to_string(), "ubuntu".to_string()));
+    /// ```
+    pub fn as_labels(&self) -> Vec<(String, String)> {
+        let version = match self.version {
+            Version::Unknown => String::new(),
+            ref version => version.to_string(),
+        };
+        let family = self.family().to_string();
+        let labels = [
+            ("os_id", self.id()),
+            ("os_name", self.name()),
+            ("os_version", &version),
+            ("os_variant", self.variant().unwrap_or_default()),
+            ("os_edition", self.edition().unwrap_or_default()),
+            ("os_codename", self.codename().unwrap_or_default()),
+            ("os_lts", if self.lts { "true" } else { "false" }),
+            ("os_family", &family),
+        ];
+
+        labels
+            .into_iter()
+            .map(|(name, value)| (String::from(name), escape_label_value(value)))
+            .collect()
+    }
+
+    /// Sets the `OS_ID`, `OS_VERSION_ID` and `OS_CODENAME` environment variables of a child
+    /// process to the ID, version and codename, e.g. for scripts run by a build system. Unknown
+    /// values, including the `"Unknown"` ID of `new_unknown()`, are removed from the environment
+    /// rather than inherited from the parent.
+    ///
+    /// Like the os-release `VERSION_ID`, `OS_VERSION_ID` never contains spaces: it is the
+    /// semantic version without its trailing zero components (e.g. "12" for Debian 12 and
+    /// "22.4.4" for Ubuntu 22.04.4), the date of a rolling release or a custom version without
+    /// whitespace.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{OSInfo, Version};
+    /// use std::{ffi::OsStr, process::Command};
+    ///
+    /// fn env<'a>(cmd: &'a Command, name: &str) -> Option<&'a OsStr> {
+    ///     cmd.get_envs().find(|(key, _)| *key == name).and_then(|(_, value)| value)
+    /// }
+    ///
+    /// let info = OSInfo::builder().id("ubuntu").version(Version::Semantic(22, 4, 4, 0)).build();
+    /// let mut cmd = Command::new("make");
+    /// info.export_env(&mut cmd);
+    /// assert_eq!(Some("ubuntu".as_ref()), env(&cmd, "OS_ID"));
+    /// assert_eq!(Some("22.4.4".as_ref()), env(&cmd, "OS_VERSION_ID"));
+    ///
+    /// let info = OSInfo::builder().id("arch").version(Version::Rolling(Some("20240101".to_string()))).build();
+    /// info.export_env(&mut cmd);
+    /// assert_eq!(Some("20240101".as_ref()), env(&cmd, "OS_VERSION_ID"));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn export_env(&self, cmd: &mut std::process::Command) {
+        let version = version_id(&self.version);
+        let vars = [
+            ("OS_ID", self.id.as_deref().filter(|&id| id != consts::UNKNOWN)),
+            ("OS_VERSION_ID", version.as_deref()),
+            ("OS_CODENAME", self.codename()),
+        ];
+
+        for (name, value) in vars {
+            match value {
+                Some(value) if !value.is_empty() => cmd.env(name, value),
+                _ => cmd.env_remove(name),
+            };
+        }
+    }
+
+    /// Returns a multi-line report of all known fields, aligned in columns and followed by the
+    /// source each field was detected from, e.g. for the `--version --verbose` output of a CLI:
+    ///
+    /// ```text
+    /// ID        ubuntu    (/etc/os-release)
+    /// Name      Ubuntu    (/etc/os-release)
+    /// Version   22.4.4.0  (/etc/os-release)
+    /// Codename  jammy     (/etc/os-release)
+    /// LTS       yes
+    /// Family    Debian
+    /// Host      wsl 2.0.0.0
+    /// Host      windows 10.0.22631.0
+    /// ```
+    ///
+    /// Fields that aren't set are left out. Every line ends with a line feed.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let pretty = OSInfo::with_id("ubuntu".to_string()).pretty();
+    /// assert!(pretty.starts_with("ID      ubuntu\n"));
+    /// ```
+    pub fn pretty(&self) -> String {
+        let mut rows: Vec<(&str, String, Option<&str>)> = Vec::new();
+        for field in FieldName::ALL {
+            let label = match field {
+                FieldName::Id => "ID",
+                FieldName::Name => "Name",
+                FieldName::Version => "Version",
+                FieldName::Variant => "Variant",
+                FieldName::Edition => "Edition",
+                FieldName::Codename => "Codename",
+                FieldName::Lts => "LTS",
+                // Shown after the name and the family below.
+                FieldName::ProductSku | FieldName::LocalizedName => continue,
+            };
+            let value = match field {
+                FieldName::Lts => Some(String::from(if self.lts { "yes" } else { "no" })),
+                field => self.field_value(field),
+            };
+            if let Some(value) = value {
+                rows.push((label, value, self.source_of(field)));
+            }
+            if field == FieldName::Name && let Some(localized_name) = self.field_value(FieldName::LocalizedName) {
+                rows.push(("Localized name", localized_name, self.source_of(FieldName::LocalizedName)));
+            }
+        }
+        rows.push(("Family", self.family().to_string(), None));
+        if let Some(sku) = self.field_value(FieldName::ProductSku) {
+            rows.push(("Product SKU", sku, self.source_of(FieldName::ProductSku)));
+        }
+        for host in self.hosts() {
+            let value = match host.version {
+                Version::Unknown => host.id().to_string(),
+                ref version => alloc::format!("{} {version}", host.id()),
+            };
+            rows.push(("Host", value, None));
+        }
+
+        let label_width = rows.iter().map(|(label, _, _)| label.chars().count()).max().unwrap_or_default();
+        // Only the values followed by a source need padding.
+        let value_width = rows
+            .iter()
+            .filter(|(_, _, source)| source.is_some())
+            .map(|(_, value, _)| value.chars().count())
+            .max()
+            .unwrap_or_default();
+        let mut pretty = String::new();
+        for (label, value, source) in rows {
+            let line = match source {
+                Some(source) => alloc::format!("{label:<label_width$}  {value:<value_width$}  ({source})"),
+                None => alloc::format!("{label:<label_width$}  {value}"),
+            };
+            // Writing to a `String` can't fail.
+            let _ = writeln!(pretty, "{}", line.trim_end());
+        }
+        pretty
+    }
+
+    /// Serializes the information to a pretty-printed JSON string, e.g. to embed it in a crash
+    /// report. Requires the `json` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let json = OSInfo::with_id("ubuntu".to_string()).to_json_string();
+    /// assert!(json.contains(r#""id": "ubuntu""#));
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> String {
+        serde_json::to_string_pretty(self).expect("OSInfo is always serializable to JSON")
+    }
+
+    /// Writes the information to a bundle file at `path`, together with the release files and
+    /// registry values of this machine it is detected from, e.g. for a support team to reproduce
+    /// the detection of a customer's air-gapped machine from a single file. See `Bundle`.
+    /// Requires the `json` and `detect` features.
+    ///
+    /// # Example
+    /// ```no_run
+    /// osinfo::get().export_bundle("osinfo-bundle.json")?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(all(feature = "json", feature = "detect"))]
+    pub fn export_bundle(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, crate::Bundle::new(self.clone()).to_json_string())
+    }
+
+    /// Writes a bundle file like `export_bundle`, capturing the release files within the size
+    /// limit of `config`, e.g. the configuration the information was detected with.
+    /// Requires the `json` and `detect` features.
+    ///
+    /// # Example
+    /// ```no_run
+    /// let config = osinfo::Config {
+    ///     max_file_size: Some(1024 * 1024),
+    ///     ..osinfo::Config::default()
+    /// };
+    /// osinfo::get_with(&config).export_bundle_with("osinfo-bundle.json", &config)?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(all(feature = "json", feature = "detect"))]
+    pub fn export_bundle_with(&self, path: impl AsRef<std::path::Path>, config: &crate::Config) -> std::io::Result<()> {
+        std::fs::write(path, crate::Bundle::with_config(self.clone(), config).to_json_string())
+    }
+
+    /// Reads a bundle file written by `export_bundle`, failing with `io::ErrorKind::InvalidData`
+    /// if it is malformed or its checksum doesn't match. `Bundle::replay` runs the detection
+    /// again on its data. Requires the `json` and `detect` features.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use osinfo::OSInfo;
+    ///
+    /// let bundle = OSInfo::import_bundle("osinfo-bundle.json")?;
+    /// println!("Reported {}, reproduced {:?}", bundle.info, bundle.replay()?);
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[cfg(all(feature = "json", feature = "detect"))]
+    pub fn import_bundle(path: impl AsRef<std::path::Path>) -> std::io::Result<crate::Bundle> {
+        crate::Bundle::from_json_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// Serializes the information to a TOML string, e.g. to embed it in a configuration dump.
+    /// Fields without a value are omitted, as TOML has no null value, which also leaves an empty
+    /// `version` table for a `Version::Rolling(None)`. Requires the `toml` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let toml = OSInfo::with_id("ubuntu".to_string()).to_toml_string();
+    /// assert!(toml.contains(r#"id = "ubuntu""#));
+    /// ```
+    #[cfg(feature = "toml")]
+    pub fn to_toml_string(&self) -> String {
+        toml::to_string(self).expect("OSInfo is always serializable to TOML")
+    }
+
+    /// Serializes the information to a YAML string, e.g. to embed it in a crash report. Requires
+    /// the `yaml` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::OSInfo;
+    /// let yaml = OSInfo::with_id("ubuntu".to_string()).to_yaml_string();
+    /// assert!(yaml.contains("id: ubuntu"));
+    /// ```
+    #[cfg(feature = "yaml")]
+    pub fn to_yaml_string(&self) -> String {
+        serde_norway::to_string(self).expect("OSInfo is always serializable to YAML")
+    }
+}
+
+/// Display names of the `target_os` values whose name differs from the ID.
+#[cfg(feature = "std")]
+pub(crate) static TARGET_NAMES: [(&str, &str); 10] = [
+    ("linux", "Linux"),
+    ("windows", "Windows"),
+    ("macos", "macOS"),
+    ("ios", "iOS"),
+    ("android", "Android"),
+    ("freebsd", "FreeBSD"),
+    ("netbsd", "NetBSD"),
+    ("openbsd", "OpenBSD"),
+    ("dragonfly", "DragonFly BSD"),
+    ("solaris", "Solaris"),
+];
+
+/// ABI (`target_env`) the crate was compiled for, or `None` for targets without one, e.g.
+/// macOS.
+#[cfg(feature = "std")]
+const TARGET_ENV: Option<&str> = if cfg!(target_env = "gnu") {
+    Some("gnu")
+} else if cfg!(target_env = "musl") {
+    Some("musl")
+} else if cfg!(target_env = "msvc") {
+    Some("msvc")
+} else if cfg!(target_env = "ohos") {
+    Some("ohos")
+} else if cfg!(target_env = "uclibc") {
+    Some("uclibc")
+} else if cfg!(target_env = "newlib") {
+    Some("newlib")
+} else if cfg!(target_env = "sgx") {
+    Some("sgx")
+} else {
+    None
+};
+
+/// Returns `version` in the form of the os-release `VERSION_ID`, see `OSInfo::export_env`.
+#[cfg(feature = "std")]
+fn version_id(version: &Version) -> Option<String> {
+    let id = match *version {
+        Version::Semantic(major, minor, build, release) => {
+            let components = [major, minor, build, release];
+            let len = components.iter().rposition(|&component| component != 0).map_or(1, |last| last + 1);
+            components[..len].iter().map(u32::to_string).collect::<Vec<_>>().join(".")
+        }
+        Version::Rolling(Some(ref date)) => date.clone(),
+        Version::Custom(ref version) => version.trim().to_string(),
+        Version::Rolling(None) | Version::Unknown => return None,
+    };
+    Some(id).filter(|id| !id.contains(char::is_whitespace))
+}
+
+/// Escapes a Prometheus label value. See `OSInfo::as_labels` for the rules.
+fn escape_label_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.trim().chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            c if c.is_control() => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
 }
 
 impl Default for OSInfo {
     fn default() -> Self {
-        Self::unknown()
+        Self::new_unknown()
     }
 }
 
 impl Display for OSInfo {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}", self.get_id())?;
+        write!(f, "{}", self.id())?;
         
         if let Some(ref name) = self.name {
             write!(f, " ({name})")?;
@@ -190,32 +1105,424 @@ mod tests {
 
     #[test]
     fn unknown() {
-        let info = OSInfo::unknown();
-        assert_eq!(String::from("Unknown"), info.get_id());
-        assert_eq!(String::new(), info.get_name());
-        assert_eq!(Version::Unknown, info.get_version());
+        let info = OSInfo::new_unknown();
+        assert_eq!("Unknown", info.id());
+        assert_eq!("", info.name());
+        assert_eq!(&Version::Unknown, info.version());
+        assert_eq!(None, info.variant());
+        assert_eq!(None, info.edition());
+        assert_eq!(None, info.codename());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn cloning_getters() {
+        let info = OSInfo {
+            version: Version::Semantic(22, 4, 4, 0),
+            codename: Some("jammy".to_string()),
+            ..OSInfo::with_id("ubuntu".to_string())
+        };
+        assert_eq!(info.id(), info.get_id());
+        assert_eq!(info.name(), info.get_name());
+        assert_eq!(info.version(), &info.get_version());
         assert_eq!(String::new(), info.get_variant());
         assert_eq!(String::new(), info.get_edition());
-        assert_eq!(String::new(), info.get_codename());
+        assert_eq!(String::from("jammy"), info.get_codename());
     }
 
     #[test]
+    #[allow(deprecated)]
     fn default() {
-        assert_eq!(OSInfo::default(), OSInfo::unknown());
+        assert_eq!(OSInfo::default(), OSInfo::new_unknown());
+        assert_eq!(OSInfo::unknown(), OSInfo::new_unknown());
     }
 
     #[test]
     fn with_id_sets_id() {
         let info = OSInfo::with_id("test_id".to_string());
-        assert_eq!(info.get_id(), "test_id");
-        assert_eq!(info.get_name(), "");
+        assert_eq!(info.id(), "test_id");
+        assert_eq!(info.name(), "");
     }
 
     #[test]
     fn with_name_sets_name() {
         let info = OSInfo::with_name("TestOS".to_string());
-        assert_eq!(info.get_name(), "TestOS");
-        assert_eq!(info.get_id(), "Unknown");
+        assert_eq!(info.name(), "TestOS");
+        assert_eq!(info.id(), "Unknown");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn target_kind() {
+        assert_eq!(OsKind::current(), OSInfo::target().kind());
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", target_os = "linux", target_env = "gnu"))]
+    fn target() {
+        let target = OSInfo::target();
+        assert_eq!("linux", target.id());
+        assert_eq!("Linux", target.name());
+        assert_eq!(Some("gnu"), target.variant());
+        assert_eq!(Some(std::env::consts::ARCH), target.edition());
+        assert_eq!(OsKind::Linux, target.kind());
+        assert_eq!(Some("target_env"), target.source_of(FieldName::Variant));
+    }
+
+    #[test]
+    fn fields() {
+        let info = OSInfo {
+            version: Version::Semantic(22, 4, 4, 0),
+            codename: Some("jammy".to_string()),
+            lts: true,
+            product_sku: Some(0x30),
+            localized_name: Some("Ubuntu (localisé)".to_string()),
+            ..OSInfo::with_id("ubuntu".to_string())
+        };
+        let fields: Vec<_> = info.fields().collect();
+        assert_eq!(FieldName::ALL.len(), fields.len());
+        for (field, value) in fields {
+            assert_eq!(info.field_value(field).as_deref(), value.as_deref(), "{field}");
+        }
+        assert_eq!(Some(Cow::Borrowed("jammy")), info.fields().nth(5).unwrap().1);
+        assert_eq!(Some(Cow::Borrowed("0x30")), info.fields().nth(7).unwrap().1);
+        assert_eq!(Some(Cow::Borrowed("Ubuntu (localisé)")), info.fields().nth(8).unwrap().1);
+        assert_eq!(None, OSInfo::new_unknown().field_value(FieldName::LocalizedName));
+    }
+
+    #[test]
+    fn fingerprint() {
+        // sha256("osinfo-fingerprint-v1\nid 7 Unknown\nname 0 \nversion -\nvariant -\nedition -\ncodename -\nlts 5 false\n")
+        assert_eq!("17e46a79a4e51c66b26968cd157ac142af62c36bca82a24621aadc1f9f07f3c6", OSInfo::new_unknown().fingerprint());
+
+        let jammy = OSInfo {
+            version: Version::Semantic(22, 4, 4, 0),
+            codename: Some("jammy".to_string()),
+            ..OSInfo::with_id("ubuntu".to_string())
+        };
+        let fingerprint = jammy.fingerprint();
+        // Neither the host chain nor the provenance participate.
+        let mut with_host = OSInfo {
+            host: Some(Box::new(OSInfo::with_id("wsl".to_string()))),
+            ..jammy.clone()
+        };
+        with_host.record_source(&FieldName::ALL, "/etc/os-release");
+        assert_eq!(fingerprint, with_host.fingerprint());
+
+        // Values can't be shifted between fields.
+        let shifted = OSInfo {
+            codename: None,
+            edition: Some("jammy".to_string()),
+            ..jammy.clone()
+        };
+        assert_ne!(fingerprint, shifted.fingerprint());
+        let extra = OSInfo {
+            version_extra: Some("(a)".to_string()),
+            ..jammy
+        };
+        assert_ne!(fingerprint, extra.fingerprint());
+    }
+
+    #[test]
+    fn diff() {
+        let jammy = OSInfo {
+            version: Version::Semantic(22, 4, 4, 0),
+            codename: Some("jammy".to_string()),
+            lts: true,
+            ..OSInfo::with_id("ubuntu".to_string())
+        };
+        let noble = OSInfo {
+            version: Version::Semantic(24, 4, 0, 0),
+            codename: Some("noble".to_string()),
+            edition: Some("Pro".to_string()),
+            ..jammy.clone()
+        };
+
+        let expected = vec![
+            FieldChange {
+                field: FieldName::Version,
+                old: Some("22.4.4.0".to_string()),
+                new: Some("24.4.0.0".to_string()),
+            },
+            FieldChange {
+                field: FieldName::Edition,
+                old: None,
+                new: Some("Pro".to_string()),
+            },
+            FieldChange {
+                field: FieldName::Codename,
+                old: Some("jammy".to_string()),
+                new: Some("noble".to_string()),
+            },
+        ];
+        assert_eq!(expected, jammy.diff(&noble));
+        assert!(jammy.diff(&jammy.clone()).is_empty());
+        assert!(!jammy.same_release(&noble));
+        assert!(jammy.field_eq(&noble, FieldName::Lts));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn export_env() {
+        use std::{ffi::OsStr, process::Command};
+
+        let info = OSInfo {
+            version: Version::Semantic(22, 4, 4, 0),
+            ..OSInfo::with_id("ubuntu".to_string())
+        };
+        let mut cmd = Command::new("true");
+        cmd.env("OS_CODENAME", "stale");
+        info.export_env(&mut cmd);
+
+        let mut envs: Vec<_> = cmd.get_envs().collect();
+        envs.sort();
+        let expected: [(&OsStr, Option<&OsStr>); 3] = [
+            ("OS_CODENAME".as_ref(), None),
+            ("OS_ID".as_ref(), Some("ubuntu".as_ref())),
+            ("OS_VERSION_ID".as_ref(), Some("22.4.4".as_ref())),
+        ];
+        assert_eq!(expected.as_slice(), envs.as_slice());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn export_env_unknown() {
+        use std::{ffi::OsStr, process::Command};
+
+        let mut cmd = Command::new("true");
+        cmd.env("OS_ID", "stale");
+        OSInfo::new_unknown().export_env(&mut cmd);
+
+        let mut envs: Vec<_> = cmd.get_envs().collect();
+        envs.sort();
+        let expected: [(&OsStr, Option<&OsStr>); 3] = [
+            ("OS_CODENAME".as_ref(), None),
+            ("OS_ID".as_ref(), None),
+            ("OS_VERSION_ID".as_ref(), None),
+        ];
+        assert_eq!(expected.as_slice(), envs.as_slice());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn version_ids() {
+        let data = [
+            (Version::Semantic(12, 0, 0, 0), Some("12")),
+            (Version::Semantic(22, 4, 0, 0), Some("22.4")),
+            (Version::Semantic(10, 0, 22631, 3296), Some("10.0.22631.3296")),
+            (Version::Semantic(0, 0, 0, 0), Some("0")),
+            (Version::Rolling(Some(String::from("20240101"))), Some("20240101")),
+            (Version::Rolling(None), None),
+            (Version::Custom(String::from("15-SP5")), Some("15-SP5")),
+            (Version::Custom(String::from("10 (buster)")), None),
+            (Version::Unknown, None),
+        ];
+
+        for (version, expected) in data {
+            assert_eq!(expected, version_id(&version).as_deref(), "{version:?}");
+        }
+    }
+
+    #[test]
+    fn pretty() {
+        let mut info = OSInfo {
+            name: Some("Ubuntu".to_string()),
+            version: Version::Semantic(22, 4, 4, 0),
+            codename: Some("jammy".to_string()),
+            lts: true,
+            host: Some(Box::new(OSInfo {
+                version: Version::Semantic(2, 0, 0, 0),
+                host: Some(Box::new(OSInfo::with_id("windows".to_string()))),
+                ..OSInfo::with_id("wsl".to_string())
+            })),
+            ..OSInfo::with_id("ubuntu".to_string())
+        };
+        info.record_source(&[FieldName::Id, FieldName::Name, FieldName::Version], "/etc/os-release");
+
+        let expected = "ID        ubuntu    (/etc/os-release)\n\
+            Name      Ubuntu    (/etc/os-release)\n\
+            Version   22.4.4.0  (/etc/os-release)\n\
+            Codename  jammy\n\
+            LTS       yes\n\
+            Family    Debian\n\
+            Host      wsl 2.0.0.0\n\
+            Host      windows\n";
+        assert_eq!(expected, info.pretty());
+    }
+
+    #[test]
+    fn family() {
+        assert_eq!(DistroFamily::Debian, OSInfo::with_id("raspbian".to_string()).family());
+        assert_eq!(DistroFamily::Other, OSInfo::new_unknown().family());
+    }
+
+    #[test]
+    fn kind_predicates() {
+        let data = [
+            ("windows", (true, false, false)),
+            ("wsl", (true, false, false)),
+            ("macos", (false, true, false)),
+            ("ubuntu", (false, false, true)),
+            ("freebsd", (false, false, false)),
+            ("openbsd", (false, false, false)),
+            ("netbsd", (false, false, false)),
+            ("illumos", (false, false, false)),
+            ("solaris", (false, false, false)),
+            ("ios", (false, false, false)),
+            ("android", (false, false, false)),
+            ("kvm", (false, false, false)),
+            ("Unknown", (false, false, false)),
+        ];
+
+        for (id, expected) in data {
+            let info = OSInfo::with_id(id.to_string());
+            assert_eq!(expected, (info.is_windows(), info.is_macos(), info.is_linux()), "{id}");
+        }
+
+        assert!(OSInfo::with_id("linuxmint".to_string()).is_like(DistroFamily::Debian));
+        assert!(OSInfo::with_id("windows".to_string()).is_like(DistroFamily::Windows));
+        assert!(OSInfo::new_unknown().is_like(DistroFamily::Other));
+        assert!(!OSInfo::with_id("fedora".to_string()).is_like(DistroFamily::Suse));
+    }
+
+    #[test]
+    fn version_at_least() {
+        let with_version = |version| OSInfo::builder().version(version).build();
+        let monterey = with_version(Version::Semantic(12, 7, 4, 0));
+        let data = [
+            ("12", true),
+            ("12.7", true),
+            ("12.7.4", true),
+            ("12.7.4.0", true),
+            ("12.7.5", false),
+            ("10.15", true),
+            ("13", false),
+            (" 11 ", true),
+            ("12.x", false),
+            ("", false),
+            ("1.2.3.4.5", false),
+        ];
+
+        for (version, expected) in data {
+            assert_eq!(expected, monterey.version_at_least(version), "{version}");
+        }
+
+        assert!(!with_version(Version::Unknown).version_at_least("0"));
+        assert!(!with_version(Version::Rolling(Some("20240320".to_string()))).version_at_least("1"));
+        assert!(!with_version(Version::Custom("2.0".to_string())).version_at_least("1"));
+    }
+
+    #[test]
+    fn host_chain() {
+        let windows = OSInfo::with_id("windows".to_string());
+        let wsl = OSInfo {
+            host: Some(Box::new(windows.clone())),
+            ..OSInfo::with_id("wsl".to_string())
+        };
+        let info = OSInfo {
+            host: Some(Box::new(wsl.clone())),
+            ..OSInfo::with_id("ubuntu".to_string())
+        };
+
+        assert_eq!(Some(&wsl), info.host());
+        let chain: Vec<&str> = info.hosts().map(OSInfo::id).collect();
+        assert_eq!(vec!["wsl", "windows"], chain);
+    }
+
+    /// Information with every kind of field set, including a host chain.
+    #[cfg(any(feature = "json", feature = "toml", feature = "yaml"))]
+    fn layered() -> OSInfo {
+        OSInfo {
+            version: Version::Semantic(22, 4, 4, 0),
+            codename: Some("jammy".to_string()),
+            lts: true,
+            host: Some(Box::new(OSInfo {
+                version: Version::Rolling(Some("20240101".to_string())),
+                ..OSInfo::with_id("wsl".to_string())
+            })),
+            ..OSInfo::with_id("ubuntu".to_string())
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_round_trip() {
+        let info = layered();
+        assert_eq!(info, serde_json::from_str(&info.to_json_string()).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn json_1_0_0() {
+        // As serialized by osinfo 1.0.0, before the LTS flag and the later fields.
+        let json = r#"{"id":"ubuntu","name":"Ubuntu","version":{"Semantic":[22,4,0,0]},"variant":"client","edition":null,"codename":"jammy"}"#;
+        let info: OSInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            OSInfo::builder()
+                .id("ubuntu")
+                .name("Ubuntu")
+                .version(Version::Semantic(22, 4, 0, 0))
+                .variant("client")
+                .codename("jammy")
+                .build(),
+            info
+        );
+        assert!(!info.is_lts());
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn toml_round_trip() {
+        let info = layered();
+        assert_eq!(info, toml::from_str(&info.to_toml_string()).unwrap());
+        assert_eq!(OSInfo::new_unknown(), toml::from_str(&OSInfo::new_unknown().to_toml_string()).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "yaml")]
+    fn yaml_round_trip() {
+        let info = layered();
+        assert_eq!(info, serde_norway::from_str(&info.to_yaml_string()).unwrap());
+    }
+
+    #[test]
+    fn labels() {
+        let info = OSInfo {
+            version: Version::Semantic(22, 4, 4, 0),
+            codename: Some("jammy".to_string()),
+            lts: true,
+            ..OSInfo::with_id("ubuntu".to_string())
+        };
+
+        let expected = [
+            ("os_id", "ubuntu"),
+            ("os_name", ""),
+            ("os_version", "22.4.4.0"),
+            ("os_variant", ""),
+            ("os_edition", ""),
+            ("os_codename", "jammy"),
+            ("os_lts", "true"),
+            ("os_family", "Debian"),
+        ];
+        let expected: Vec<_> = expected.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        assert_eq!(expected, info.as_labels());
+    }
+
+    #[test]
+    fn label_escaping() {
+        let data = [
+            ("plain", "plain"),
+            (" padded\n", "padded"),
+            ("C:\\Windows", "C:\\\\Windows"),
+            ("say \"hi\"", "say \\\"hi\\\""),
+            ("two\nlines", "two\\nlines"),
+            ("crlf\r\nend\u{7}", "crlf\\nend"),
+            ("Débian «sid»", "Débian «sid»"),
+        ];
+
+        for (value, expected) in &data {
+            assert_eq!(*expected, escape_label_value(value));
+        }
     }
 
     #[test]
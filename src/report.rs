@@ -0,0 +1,171 @@
+//! This module defines the detection `Report`, which pairs the detected `OSInfo` with the raw
+//! facts it was cross-checked against.
+
+use crate::logging::warn;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{OSInfo, Version};
+
+/// Result of a detection run, including the data used to cross-check the result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Report {
+    /// Detected operating system information.
+    pub info: OSInfo,
+    /// Version of the running kernel, if it could be determined separately from the userland.
+    pub kernel: Option<Version>,
+    /// Disagreements between the sources of information.
+    pub inconsistencies: Vec<Inconsistency>,
+}
+
+/// Disagreement between two sources of information beyond the expected bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
+pub enum Inconsistency {
+    /// The running kernel is older than the oldest kernel the userland release shipped with.
+    /// This usually means a container running on an older host, e.g. a RHEL 9 userland on a
+    /// 3.10 kernel.
+    KernelTooOld {
+        /// Operating system ID reported by the userland.
+        id: String,
+        /// Operating system version reported by the userland.
+        version: Version,
+        /// Version of the running kernel.
+        kernel: Version,
+        /// Oldest kernel version the userland release shipped with.
+        minimum: Version,
+    },
+}
+
+impl Report {
+    /// Constructs a `Report` from the given information, running the consistency checks.
+    pub(crate) fn new(info: OSInfo, kernel: Option<Version>) -> Self {
+        let inconsistencies = check(&info, kernel.as_ref());
+        for inconsistency in &inconsistencies {
+            warn!("Inconsistent detection result: {:?}", inconsistency);
+        }
+
+        Self {
+            info,
+            kernel,
+            inconsistencies,
+        }
+    }
+}
+
+/// Oldest kernel a userland release shipped with.
+struct KernelBaseline {
+    /// Operating system IDs the baseline applies to.
+    ids: &'static [&'static str],
+    /// Release (major, minor). A zero minor matches every minor release.
+    release: (u32, u32),
+    /// Kernel (major, minor).
+    kernel: (u32, u32),
+}
+
+static RHEL_LIKE: &[&str] = &["rhel", "centos", "rocky", "almalinux", "ol"];
+
+static KERNEL_BASELINES: [KernelBaseline; 10] = [
+    KernelBaseline { ids: RHEL_LIKE, release: (7, 0), kernel: (3, 10) },
+    KernelBaseline { ids: RHEL_LIKE, release: (8, 0), kernel: (4, 18) },
+    KernelBaseline { ids: RHEL_LIKE, release: (9, 0), kernel: (5, 14) },
+    KernelBaseline { ids: &["ubuntu"], release: (18, 4), kernel: (4, 15) },
+    KernelBaseline { ids: &["ubuntu"], release: (20, 4), kernel: (5, 4) },
+    KernelBaseline { ids: &["ubuntu"], release: (22, 4), kernel: (5, 15) },
+    KernelBaseline { ids: &["ubuntu"], release: (24, 4), kernel: (6, 8) },
+    KernelBaseline { ids: &["debian"], release: (10, 0), kernel: (4, 19) },
+    KernelBaseline { ids: &["debian"], release: (11, 0), kernel: (5, 10) },
+    KernelBaseline { ids: &["debian"], release: (12, 0), kernel: (6, 1) },
+];
+
+/// Cross-checks the userland information against the running kernel.
+fn check(info: &OSInfo, kernel: Option<&Version>) -> Vec<Inconsistency> {
+    let mut inconsistencies = Vec::new();
+
+    let (Some(kernel), Version::Semantic(major, minor, _, _)) = (kernel, &info.version) else {
+        return inconsistencies;
+    };
+    let Version::Semantic(k_major, k_minor, _, _) = *kernel else {
+        return inconsistencies;
+    };
+
+    let id = info.id();
+    let baseline = KERNEL_BASELINES.iter().find(|baseline| {
+        let (r_major, r_minor) = baseline.release;
+        baseline.ids.contains(&id) && r_major == *major && (r_minor == 0 || r_minor == *minor)
+    });
+
+    if let Some(baseline) = baseline
+        && (k_major, k_minor) < baseline.kernel
+    {
+        let (min_major, min_minor) = baseline.kernel;
+        inconsistencies.push(Inconsistency::KernelTooOld {
+            id: id.to_string(),
+            version: info.version.clone(),
+            kernel: kernel.clone(),
+            minimum: Version::Semantic(min_major, min_minor, 0, 0),
+        });
+    }
+
+    inconsistencies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn info(id: &str, version: Version) -> OSInfo {
+        OSInfo {
+            version,
+            ..OSInfo::with_id(id.to_string())
+        }
+    }
+
+    #[test]
+    fn rhel_on_old_kernel() {
+        let report = Report::new(
+            info("rhel", Version::Semantic(9, 3, 0, 0)),
+            Some(Version::Semantic(3, 10, 0, 0)),
+        );
+
+        assert_eq!(
+            vec![Inconsistency::KernelTooOld {
+                id: "rhel".to_string(),
+                version: Version::Semantic(9, 3, 0, 0),
+                kernel: Version::Semantic(3, 10, 0, 0),
+                minimum: Version::Semantic(5, 14, 0, 0),
+            }],
+            report.inconsistencies
+        );
+    }
+
+    #[test]
+    fn consistent() {
+        let data = [
+            (info("rhel", Version::Semantic(9, 3, 0, 0)), Some(Version::Semantic(5, 14, 0, 0))),
+            (info("ubuntu", Version::Semantic(22, 4, 4, 0)), Some(Version::Semantic(6, 5, 0, 0))),
+            (info("debian", Version::Semantic(12, 5, 0, 0)), Some(Version::Semantic(6, 1, 0, 0))),
+            // Unknown releases and kernels aren't checked.
+            (info("arch", Version::Rolling(None)), Some(Version::Semantic(2, 6, 0, 0))),
+            (info("ubuntu", Version::Semantic(22, 4, 0, 0)), None),
+            (info("ubuntu", Version::Semantic(23, 10, 0, 0)), Some(Version::Semantic(3, 10, 0, 0))),
+        ];
+
+        for (info, kernel) in data {
+            assert_eq!(Vec::<Inconsistency>::new(), Report::new(info, kernel).inconsistencies);
+        }
+    }
+
+    #[test]
+    fn ubuntu_on_old_kernel() {
+        let report = Report::new(
+            info("ubuntu", Version::Semantic(24, 4, 0, 0)),
+            Some(Version::Semantic(5, 15, 0, 0)),
+        );
+        assert_eq!(1, report.inconsistencies.len());
+    }
+}
@@ -0,0 +1,60 @@
+//! This module defines the `Sandbox` struct describing the application sandbox (Flatpak or
+//! Snap) a process runs in.
+
+use std::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::OSInfo;
+
+/// Application sandbox the process runs in.
+///
+/// Inside a sandbox, the os-release files describe the runtime the application was built
+/// against rather than the system it runs on, e.g. "Freedesktop SDK 23.08" on an Ubuntu host.
+/// `host` describes the latter, if the sandbox exposes it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sandbox {
+    /// Sandboxing technology.
+    pub kind: SandboxKind,
+    /// ID of the sandboxed application, e.g. "org.mozilla.firefox" (Flatpak) or "firefox"
+    /// (Snap).
+    pub app_id: Option<String>,
+    /// Runtime (Flatpak) or base snap (Snap) providing the libraries the application sees, e.g.
+    /// "org.freedesktop.Platform/x86_64/23.08" or "core22".
+    pub runtime: Option<String>,
+    /// Operating system of the host, read from the os-release file the sandbox exposes.
+    pub host: Option<OSInfo>,
+}
+
+/// Sandboxing technology.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SandboxKind {
+    /// Flatpak.
+    Flatpak,
+    /// Snap, with strict or classic confinement.
+    Snap,
+}
+
+impl Sandbox {
+    /// Creates a sandbox of the given kind with unknown application, runtime and host.
+    pub fn new(kind: SandboxKind) -> Self {
+        Self {
+            kind,
+            app_id: None,
+            runtime: None,
+            host: None,
+        }
+    }
+}
+
+impl Display for SandboxKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Flatpak => "Flatpak",
+            Self::Snap => "Snap",
+        })
+    }
+}
@@ -0,0 +1,71 @@
+//! This module defines the `SecurityProfile` struct describing the security posture of the
+//! operating system and the hardware it runs on.
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Version;
+
+/// Security related facts about the system, usually reported together with the OS version.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SecurityProfile {
+    /// Hardware security processor, or `None` if there isn't one (or it couldn't be detected).
+    pub security_processor: Option<SecurityProcessor>,
+    /// Encryption of the system volume, or `None` if it couldn't be determined.
+    pub disk_encryption: Option<DiskEncryption>,
+}
+
+/// Hardware security processor used to protect keys and measure the boot process.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SecurityProcessor {
+    /// Trusted Platform Module with its specification version, e.g. `2.0`.
+    /// The version is `Version::Unknown` if the TPM is present but its version couldn't be read.
+    Tpm(Version),
+    /// Apple Secure Enclave (Apple Silicon and T2 Macs).
+    SecureEnclave,
+}
+
+/// Full disk encryption of the system volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum DiskEncryption {
+    /// The system volume isn't encrypted.
+    Unencrypted,
+    /// BitLocker (Windows).
+    BitLocker,
+    /// FileVault (macOS).
+    FileVault,
+    /// LUKS / dm-crypt (Linux).
+    Luks,
+}
+
+impl DiskEncryption {
+    /// Returns `true` if the system volume is encrypted.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::DiskEncryption;
+    /// assert!(DiskEncryption::Luks.is_encrypted());
+    /// assert!(!DiskEncryption::Unencrypted.is_encrypted());
+    /// ```
+    pub fn is_encrypted(&self) -> bool {
+        *self != Self::Unencrypted
+    }
+}
+
+impl SecurityProcessor {
+    /// Returns `true` if this is a TPM implementing the 2.0 specification.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{SecurityProcessor, Version};
+    /// assert!(SecurityProcessor::Tpm(Version::Semantic(2, 0, 0, 0)).is_tpm2());
+    /// assert!(!SecurityProcessor::Tpm(Version::Semantic(1, 2, 0, 0)).is_tpm2());
+    /// assert!(!SecurityProcessor::SecureEnclave.is_tpm2());
+    /// ```
+    pub fn is_tpm2(&self) -> bool {
+        matches!(self, Self::Tpm(Version::Semantic(2, _, _, _)))
+    }
+}
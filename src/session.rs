@@ -0,0 +1,73 @@
+//! This module defines the `Session` struct describing how the current process is attached to a
+//! user, e.g. an interactive terminal, an SSH connection or a Windows service.
+
+use std::io::{self, IsTerminal};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Environment variables set by OpenSSH (including the Windows port) in the sessions it starts.
+const SSH_VARIABLES: [&str; 3] = ["SSH_CONNECTION", "SSH_CLIENT", "SSH_TTY"];
+
+/// Session context of the current process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Session {
+    /// Kind of session the process runs in.
+    pub kind: SessionKind,
+    /// Whether standard input and standard output are terminals, i.e. the user can answer
+    /// prompts.
+    pub interactive: bool,
+}
+
+/// Kind of session a process runs in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SessionKind {
+    /// Graphical session on the local display (X11, Wayland, the macOS Aqua session or the
+    /// Windows console session).
+    Desktop,
+    /// Session of a user logged in over SSH.
+    Ssh,
+    /// Windows Remote Desktop session.
+    RemoteDesktop,
+    /// Text console without graphical session, e.g. a Linux virtual terminal.
+    Console,
+    /// Background service without user session: a Windows service (session 0), a systemd
+    /// service or a launchd daemon.
+    Service,
+    /// The session couldn't be determined, e.g. in a container.
+    #[default]
+    Unknown,
+}
+
+#[cfg_attr(not(feature = "detect"), allow(dead_code))]
+impl Session {
+    /// Detects the session of the current process; `platform_kind` classifies sessions not
+    /// started by SSH.
+    pub(crate) fn detect(platform_kind: impl FnOnce() -> SessionKind) -> Self {
+        let kind = if is_ssh(|name| std::env::var_os(name).is_some()) { SessionKind::Ssh } else { platform_kind() };
+        Self {
+            kind,
+            interactive: io::stdin().is_terminal() && io::stdout().is_terminal(),
+        }
+    }
+}
+
+/// Returns whether the process runs in an SSH session, given a check for the presence of an
+/// environment variable.
+fn is_ssh(is_set: impl Fn(&str) -> bool) -> bool {
+    SSH_VARIABLES.iter().any(|name| is_set(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ssh() {
+        assert!(is_ssh(|name| name == "SSH_CONNECTION"));
+        assert!(is_ssh(|name| name == "SSH_TTY"));
+        assert!(!is_ssh(|name| name == "DISPLAY"));
+    }
+}
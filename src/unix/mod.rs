@@ -0,0 +1,110 @@
+//! Detection shared by Unix systems, and the backend of the ones without a dedicated module
+//! (the BSDs, Solaris and illumos, ...).
+
+mod uname;
+
+pub use uname::{Uname, parse_release};
+
+use crate::logging::trace;
+
+use std::path::Path;
+
+use crate::{Cgroups, Config, Deployment, Diagnostic, DisplayServer, Firmware, LibcInfo, NetworkEnv, OSInfo, Sandbox, SecurityProfile, SessionKind, Version, boot::BootStatus, linux};
+
+/// Describes the operating system by its kernel when nothing identifies it more precisely.
+pub fn get_info_from_uname(root: &Path, config: &Config, diagnostics: &mut Vec<Diagnostic>) -> Option<OSInfo> {
+    if let Some(uname) = Uname::from_proc(root, diagnostics) {
+        return Some(uname.to_os_info(&root.join("proc/sys/kernel/ostype").display().to_string()));
+    }
+    if !config.run_external_commands {
+        return None;
+    }
+    Uname::from_command(diagnostics).map(|uname| uname.to_os_info("uname"))
+}
+
+pub fn get_info(config: &Config, diagnostics: &mut Vec<Diagnostic>) -> OSInfo {
+    trace!("unix::get_info is called");
+    // FreeBSD ships an os-release file since 13.0.
+    let info = linux::get_info_from(Path::new("/"), config, diagnostics)
+        .or_else(|| get_info_from_uname(Path::new("/"), config, diagnostics));
+    trace!("Returning {:?}", info);
+    info.unwrap_or_else(|| {
+        diagnostics.push(Diagnostic::NotDetected);
+        OSInfo::default()
+    })
+}
+
+pub fn get_kernel_version() -> Option<Version> {
+    trace!("unix::get_kernel_version is called");
+    Uname::from_command(&mut Vec::new()).and_then(|uname| parse_release(&uname.release))
+}
+
+pub fn get_security_profile() -> SecurityProfile {
+    SecurityProfile::default()
+}
+
+pub fn get_boot_status() -> BootStatus {
+    BootStatus::default()
+}
+
+pub fn get_firmware() -> Firmware {
+    Firmware::default()
+}
+
+pub fn fips_enabled() -> Option<bool> {
+    None
+}
+
+pub fn get_deployment() -> Option<Deployment> {
+    None
+}
+
+pub fn get_sandbox(_config: &Config) -> Option<Sandbox> {
+    // Flatpak and Snap are Linux only.
+    None
+}
+
+pub fn get_cgroups() -> Option<Cgroups> {
+    // Control groups are Linux only.
+    None
+}
+
+pub fn get_libc() -> Option<LibcInfo> {
+    // Each BSD ships its own C library, versioned with the system.
+    None
+}
+
+pub fn install_language() -> Option<String> {
+    None
+}
+
+pub fn system_locale() -> Option<String> {
+    None
+}
+
+pub fn pending_reboot() -> Option<bool> {
+    None
+}
+
+// The environment variables and files consulted on Linux are set the same way by the desktop
+// environments and login services of the other Unix systems.
+
+pub fn get_session_kind() -> SessionKind {
+    linux::get_session_kind()
+}
+
+pub fn network_env() -> NetworkEnv {
+    linux::network_env()
+}
+
+pub fn get_display_server() -> DisplayServer {
+    linux::get_display_server()
+}
+
+pub fn is_elevated() -> bool {
+    linux::is_elevated()
+}
+
+pub fn current_user() -> Option<String> {
+    linux::current_user()
+}
@@ -0,0 +1,247 @@
+use std::{fs, path::Path};
+
+use crate::logging::trace;
+
+use crate::{Diagnostic, FieldName, OSInfo, Version, consts, exec};
+
+/// Kernel name and release, as printed by `uname -s -r`, e.g. "FreeBSD" and "14.0-RELEASE".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Uname {
+    /// Kernel name, e.g. "Linux", "FreeBSD" or "SunOS".
+    pub sysname: String,
+    /// Kernel release, e.g. "6.8.9-arch1-1", "14.0-RELEASE" or "5.11".
+    pub release: String,
+}
+
+impl Uname {
+    /// Reads the kernel name and release from `proc/sys/kernel` under `root`, which only exists
+    /// on Linux.
+    pub fn from_proc(root: &Path, diagnostics: &mut Vec<Diagnostic>) -> Option<Self> {
+        let mut read = |name: &str| {
+            let path = root.join("proc/sys/kernel").join(name);
+            match fs::read_to_string(&path) {
+                Ok(content) if !content.trim().is_empty() => {
+                    diagnostics.push(Diagnostic::source_used(path.display()));
+                    Some(content.trim().to_string())
+                }
+                Ok(_) => {
+                    diagnostics.push(Diagnostic::field_not_found(path.display(), name));
+                    None
+                }
+                Err(_) => {
+                    diagnostics.push(Diagnostic::source_missing(path.display()));
+                    None
+                }
+            }
+        };
+
+        let sysname = read("ostype")?;
+        let release = read("osrelease")?;
+        Some(Self { sysname, release })
+    }
+
+    /// Runs `uname -s -r`.
+    pub fn from_command(diagnostics: &mut Vec<Diagnostic>) -> Option<Self> {
+        match exec::run("uname", &["-s", "-r"]) {
+            Ok(output) if output.status.success() => {
+                let uname = parse(&String::from_utf8_lossy(&output.stdout));
+                if uname.is_some() {
+                    diagnostics.push(Diagnostic::source_used("uname"));
+                }
+                uname
+            }
+            Ok(output) => {
+                diagnostics.push(Diagnostic::source_unreadable("uname", output.status));
+                None
+            }
+            Err(e) => {
+                trace!("Unable to run uname: {:?}", e);
+                diagnostics.push(Diagnostic::source_unreadable("uname", e));
+                None
+            }
+        }
+    }
+
+    /// Describes the operating system by its kernel: the ID is the one in `consts` of the kernel
+    /// name (e.g. "freebsd", or "hurd" for "GNU") or the lowercase kernel name for other kernels,
+    /// the name the kernel name and the version the kernel release, `source` being recorded as
+    /// the source of these fields.
+    pub fn to_os_info(&self, source: &str) -> OSInfo {
+        let version = parse_release(&self.release).unwrap_or_else(|| Version::Custom(self.release.clone()));
+        let mut info = OSInfo {
+            id: Some(kernel_id(&self.sysname)),
+            name: Some(self.sysname.clone()),
+            version,
+            ..Default::default()
+        };
+        info.record_source(&[FieldName::Id, FieldName::Name, FieldName::Version], source);
+        info
+    }
+}
+
+/// Kernel names reported as is, lowercased, by `Uname::to_os_info`.
+const KERNEL_IDS: [&str; 12] = [
+    consts::AIX,
+    consts::DRAGONFLY,
+    consts::FREEBSD,
+    consts::HAIKU,
+    consts::LINUX,
+    consts::MIDNIGHTBSD,
+    consts::MINIX,
+    consts::NETBSD,
+    consts::OPENBSD,
+    consts::QNX,
+    consts::REDOX,
+    consts::SUNOS,
+];
+
+/// Returns the operating system ID of a kernel name, e.g. "freebsd" for "FreeBSD", or the
+/// lowercase kernel name if it has none in `consts`.
+fn kernel_id(sysname: &str) -> String {
+    let sysname = sysname.to_ascii_lowercase();
+    let id = match sysname.as_str() {
+        "gnu" => consts::HURD,
+        "gnu/kfreebsd" => consts::KFREEBSD,
+        // Followed by the Windows version, e.g. "CYGWIN_NT-10.0-19045".
+        name if name.starts_with("cygwin_nt") => consts::CYGWIN,
+        name if name.starts_with("msys_nt") => consts::MSYS,
+        name if name.starts_with("mingw32_nt") || name.starts_with("mingw64_nt") => consts::MINGW,
+        name => match KERNEL_IDS.into_iter().find(|&id| id == name) {
+            Some(id) => id,
+            None => return sysname,
+        },
+    };
+    String::from(id)
+}
+
+/// Parses the output of `uname -s -r`, e.g. "OpenBSD 7.5\n".
+fn parse(output: &str) -> Option<Uname> {
+    let mut words = output.split_whitespace();
+    let sysname = words.next()?.to_string();
+    let release = words.next()?.to_string();
+    Some(Uname { sysname, release })
+}
+
+/// Parses the numeric part of a kernel release string, e.g. "5.15" for "5.15.0-91-generic" or
+/// "14.0" for "14.0-RELEASE".
+pub fn parse_release(release: &str) -> Option<Version> {
+    let release = release.trim();
+    let end = release
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(release.len());
+    match Version::from_string(&release[..end]) {
+        Version::Semantic(major, minor, build, _) => Some(Version::Semantic(major, minor, build, 0)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linux::tests::root_with;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn kernel_release() {
+        let data = [
+            ("5.15.0-91-generic", Some(Version::Semantic(5, 15, 0, 0))),
+            ("6.8.9-arch1-1\n", Some(Version::Semantic(6, 8, 9, 0))),
+            ("3.10.0-1160.el7.x86_64", Some(Version::Semantic(3, 10, 0, 0))),
+            ("5.15.146.1-microsoft-standard-WSL2", Some(Version::Semantic(5, 15, 146, 0))),
+            ("14.0-RELEASE-p6", Some(Version::Semantic(14, 0, 0, 0))),
+            ("5.11", Some(Version::Semantic(5, 11, 0, 0))),
+            ("", None),
+            ("unknown", None),
+        ];
+
+        for (release, expected) in &data {
+            assert_eq!(*expected, parse_release(release));
+        }
+    }
+
+    #[test]
+    fn uname_output() {
+        let data = [
+            ("FreeBSD 14.0-RELEASE-p6\n", Some(("FreeBSD", "14.0-RELEASE-p6"))),
+            ("SunOS 5.11\n", Some(("SunOS", "5.11"))),
+            ("Haiku\n", None),
+            ("", None),
+        ];
+
+        for (output, expected) in data {
+            let expected = expected.map(|(sysname, release)| Uname {
+                sysname: sysname.to_string(),
+                release: release.to_string(),
+            });
+            assert_eq!(expected, parse(output));
+        }
+    }
+
+    #[test]
+    fn os_info() {
+        let uname = Uname {
+            sysname: String::from("NetBSD"),
+            release: String::from("10.0"),
+        };
+        let info = uname.to_os_info("uname");
+        assert_eq!("netbsd", info.id());
+        assert_eq!("NetBSD", info.name());
+        assert_eq!(&Version::Semantic(10, 0, 0, 0), info.version());
+        assert_eq!(Some("uname"), info.source_of(FieldName::Version));
+        assert!(!info.is_linux());
+
+        let uname = Uname {
+            sysname: String::from("DragonFly"),
+            release: String::from("snapshot"),
+        };
+        assert_eq!(&Version::Custom(String::from("snapshot")), uname.to_os_info("uname").version());
+    }
+
+    #[test]
+    fn kernel_ids() {
+        let data = [
+            ("FreeBSD", consts::FREEBSD),
+            ("SunOS", consts::SUNOS),
+            ("Linux", consts::LINUX),
+            ("GNU", consts::HURD),
+            ("CYGWIN_NT-10.0-19045", consts::CYGWIN),
+            ("MSYS_NT-10.0-19045", consts::MSYS),
+            ("MINGW64_NT-10.0-19045", consts::MINGW),
+            ("GNU/kFreeBSD", consts::KFREEBSD),
+            ("Haiku", consts::HAIKU),
+            ("Minix", consts::MINIX),
+            ("QNX", consts::QNX),
+            // Kernels without an ID in `consts` keep their name.
+            ("SerenityOS", "serenityos"),
+        ];
+
+        for (sysname, expected) in data {
+            assert_eq!(expected, kernel_id(sysname), "{sysname}");
+        }
+    }
+
+    #[test]
+    fn proc_files() {
+        let root = root_with(
+            "uname-proc",
+            &[("proc/sys/kernel/ostype", "Linux\n"), ("proc/sys/kernel/osrelease", "6.8.9-arch1-1\n")],
+        );
+        let mut diagnostics = Vec::new();
+        let expected = Uname {
+            sysname: String::from("Linux"),
+            release: String::from("6.8.9-arch1-1"),
+        };
+        assert_eq!(Some(expected), Uname::from_proc(&root, &mut diagnostics));
+        assert_eq!(2, diagnostics.len());
+        fs::remove_dir_all(root).unwrap();
+
+        let root = root_with("uname-no-proc", &[("etc/hostname", "box\n")]);
+        let mut diagnostics = Vec::new();
+        assert_eq!(None, Uname::from_proc(&root, &mut diagnostics));
+        assert_eq!(
+            vec![Diagnostic::source_missing(root.join("proc/sys/kernel/ostype").display())],
+            diagnostics
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+}
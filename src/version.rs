@@ -1,13 +1,19 @@
-use std::fmt::{self, Display, Formatter};
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+};
+use core::fmt::{self, Display, Formatter};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Operating system version.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Version {
     /// Unknown version.
+    #[default]
     Unknown,
     /// Semantic version (major.minor.build.release).
     Semantic(u32, u32, u32, u32),
@@ -21,7 +27,8 @@ impl Version {
     /// Constructs `VersionType` from the given string.
     ///
     /// Returns `VersionType::Unknown` if the string is empty. If it can be parsed as a semantic
-    /// version, then `VersionType::Semantic`, otherwise `VersionType::Custom`.
+    /// version, then `VersionType::Semantic`, otherwise `VersionType::Custom`. This never panics,
+    /// whatever the input.
     ///
     /// # Examples
     ///
@@ -43,14 +50,243 @@ impl Version {
             Self::Custom(s.into())
         }
     }
+
+    /// Parses a semantic version of one to four dot-separated decimal numbers, e.g. "1.2.3",
+    /// ignoring surrounding whitespace.
+    ///
+    /// Unlike `from_string`, which falls back to `Version::Custom`, anything else is an error,
+    /// including trailing characters: "1.2.3abc" is rejected rather than kept as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osinfo::{Version, VersionParseError};
+    ///
+    /// assert_eq!(Ok(Version::Semantic(1, 2, 3, 0)), Version::parse_strict("1.2.3"));
+    /// assert_eq!(
+    ///     Err(VersionParseError::InvalidComponent { index: 2, component: "3abc".to_owned() }),
+    ///     Version::parse_strict("1.2.3abc")
+    /// );
+    /// ```
+    pub fn parse_strict(s: &str) -> Result<Self, VersionParseError> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(VersionParseError::Empty);
+        }
+
+        let mut components = [0; 4];
+        for (index, component) in s.split('.').enumerate() {
+            if index >= components.len() {
+                return Err(VersionParseError::TooManyComponents(s.split('.').count()));
+            }
+            // `u32::from_str` accepts a leading '+'.
+            if !component.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(VersionParseError::invalid_component(index, component));
+            }
+            components[index] = component
+                .parse()
+                .map_err(|_| VersionParseError::invalid_component(index, component))?;
+        }
+
+        let [major, minor, build, release] = components;
+        Ok(Self::Semantic(major, minor, build, release))
+    }
+
+    /// Extracts the first numeric version from a noisy string, e.g. 8.1.1911 from
+    /// "8.1.1911 (Core)" or 7.9 from "release 7.9", ignoring components beyond the fourth.
+    ///
+    /// Returns `Version::Unknown` if the string is empty and `Version::Custom` if it contains no
+    /// number that fits in `u32`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osinfo::Version;
+    ///
+    /// assert_eq!(Version::Semantic(8, 1, 1911, 0), Version::parse_lenient("8.1.1911 (Core)"));
+    /// assert_eq!(Version::Semantic(7, 9, 0, 0), Version::parse_lenient("release 7.9"));
+    /// assert_eq!(Version::Custom("sid".to_owned()), Version::parse_lenient("sid"));
+    /// ```
+    pub fn parse_lenient(s: &str) -> Self {
+        let trimmed = s.trim();
+        if trimmed.is_empty() {
+            return Self::Unknown;
+        }
+
+        let Some(start) = trimmed.find(|c: char| c.is_ascii_digit()) else {
+            return Self::Custom(trimmed.to_owned());
+        };
+        let rest = &trimmed[start..];
+        let end = rest.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(rest.len());
+
+        let mut components = [0; 4];
+        let numbers = rest[..end].split('.').take(components.len()).map_while(|n| n.parse().ok());
+        let mut count = 0;
+        for (component, number) in components.iter_mut().zip(numbers) {
+            *component = number;
+            count += 1;
+        }
+        if count == 0 {
+            return Self::Custom(trimmed.to_owned());
+        }
+
+        let [major, minor, build, release] = components;
+        Self::Semantic(major, minor, build, release)
+    }
+
+    /// Returns the date of a rolling release, if it is one and its date is given as
+    /// "YYYYMMDD" (e.g. the Gentoo snapshot "20240320"), "YYYY-MM-DD" or "YYYY.MM.DD".
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use osinfo::{RollingDate, Version};
+    ///
+    /// let version = Version::Rolling(Some("20240320".to_owned()));
+    /// assert_eq!(RollingDate::new(2024, 3, 20), version.rolling_date());
+    /// assert_eq!(None, Version::Rolling(Some("trixie/sid".to_owned())).rolling_date());
+    /// assert_eq!(None, Version::Semantic(2024, 3, 20, 0).rolling_date());
+    /// ```
+    pub fn rolling_date(&self) -> Option<RollingDate> {
+        let Self::Rolling(Some(date)) = self else {
+            return None;
+        };
+        let date = date.trim();
+        let (year, month, day) = if date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit()) {
+            (&date[..4], &date[4..6], &date[6..])
+        } else {
+            let separator = date.chars().find(|c| *c == '-' || *c == '.')?;
+            let mut parts = date.split(separator);
+            let parts = (parts.next()?, parts.next()?, parts.next()?, parts.next());
+            match parts {
+                (year, month, day, None) if year.len() == 4 && month.len() == 2 && day.len() == 2 => (year, month, day),
+                _ => return None,
+            }
+        };
+        let number = |s: &str| s.bytes().all(|b| b.is_ascii_digit()).then(|| s.parse().ok()).flatten();
+        RollingDate::new(number(year)?, u8::try_from(number(month)?).ok()?, u8::try_from(number(day)?).ok()?)
+    }
+}
+
+/// Calendar date of a rolling release, see `Version::rolling_date`.
+///
+/// Dates are ordered chronologically, and `days_since_epoch` allows computing their age, e.g.
+/// to flag snapshots older than 90 days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RollingDate {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl RollingDate {
+    /// Creates a date, or returns `None` if it doesn't exist in the Gregorian calendar.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::RollingDate;
+    /// assert!(RollingDate::new(2024, 2, 29).is_some());
+    /// assert_eq!(None, RollingDate::new(2023, 2, 29));
+    /// ```
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+        let days_in_month = match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if leap => 29,
+            2 => 28,
+            _ => return None,
+        };
+        (1..=days_in_month).contains(&day).then_some(Self { year, month, day })
+    }
+
+    /// Returns the year, e.g. 2024.
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month, from 1 to 12.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, from 1 to 31.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Returns the number of days since 1970-01-01, negative for earlier dates.
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::SystemTime;
+    /// use osinfo::{RollingDate, Version};
+    ///
+    /// let snapshot = Version::Rolling(Some("20240320".to_owned())).rolling_date().unwrap();
+    /// let now = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH).unwrap();
+    /// let age = now.as_secs() as i64 / 86400 - snapshot.days_since_epoch();
+    /// assert!(age > 90);
+    /// ```
+    pub fn days_since_epoch(&self) -> i64 {
+        // Counts from March 1st of year 0, so that leap days end the year.
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month_from_march = (i64::from(self.month) + 9) % 12;
+        let day_of_year = (153 * month_from_march + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
 }
 
-impl Default for Version {
-    fn default() -> Self {
-        Version::Unknown
+impl Display for RollingDate {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
     }
 }
 
+/// Error returned by `Version::parse_strict`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum VersionParseError {
+    /// The string is empty or only contains whitespace.
+    Empty,
+    /// A component isn't a decimal number fitting in `u32`, e.g. "3abc" in "1.2.3abc" or the
+    /// empty component in "1..2".
+    InvalidComponent {
+        /// Position of the component, starting at 0 for the major version.
+        index: usize,
+        /// The invalid component.
+        component: String,
+    },
+    /// The version has more than four components; contains their number.
+    TooManyComponents(usize),
+}
+
+impl VersionParseError {
+    fn invalid_component(index: usize, component: &str) -> Self {
+        Self::InvalidComponent {
+            index,
+            component: component.to_string(),
+        }
+    }
+}
+
+impl Display for VersionParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty version"),
+            Self::InvalidComponent { index, component } => {
+                write!(f, "invalid version component {index}: {component:?}")
+            }
+            Self::TooManyComponents(count) => write!(f, "too many version components: {count} (at most 4)"),
+        }
+    }
+}
+
+impl core::error::Error for VersionParseError {}
+
 impl Display for Version {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         match *self {
@@ -87,6 +323,39 @@ fn parse_version(s: &str) -> Option<(u32, u32, u32, u32)> {
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
+    use proptest::prelude::*;
+
+    #[test]
+    fn rolling_dates() {
+        let data = [
+            ("20240320", RollingDate::new(2024, 3, 20)),
+            ("2024-03-20", RollingDate::new(2024, 3, 20)),
+            (" 2024.02.29\n", RollingDate::new(2024, 2, 29)),
+            ("20230229", None),
+            ("2024-3-20", None),
+            ("2024-03-20-01", None),
+            ("2024.03-20", None),
+            ("+2024-03-20", None),
+            ("trixie/sid", None),
+            ("", None),
+        ];
+
+        for (date, expected) in data {
+            assert_eq!(expected, Version::Rolling(Some(date.to_owned())).rolling_date(), "{date:?}");
+        }
+        assert_eq!(None, Version::Rolling(None).rolling_date());
+    }
+
+    #[test]
+    fn days_since_epoch() {
+        let data = [((1970, 1, 1), 0), ((1969, 12, 31), -1), ((2000, 2, 29), 11016), ((2024, 3, 20), 19802)];
+
+        for ((year, month, day), expected) in data {
+            let date = RollingDate::new(year, month, day).unwrap();
+            assert_eq!(expected, date.days_since_epoch(), "{date}");
+        }
+        assert!(RollingDate::new(2023, 12, 31) < RollingDate::new(2024, 1, 1));
+    }
 
     #[test]
     fn parse_semantic_version() {
@@ -132,6 +401,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parse_strict() {
+        let data = [
+            ("1", Ok(Version::Semantic(1, 0, 0, 0))),
+            (" 1.2.3.4\n", Ok(Version::Semantic(1, 2, 3, 4))),
+            ("", Err(VersionParseError::Empty)),
+            ("  ", Err(VersionParseError::Empty)),
+            ("1.2.3abc", Err(VersionParseError::invalid_component(2, "3abc"))),
+            ("1.", Err(VersionParseError::invalid_component(1, ""))),
+            ("1..2", Err(VersionParseError::invalid_component(1, ""))),
+            ("+1", Err(VersionParseError::invalid_component(0, "+1"))),
+            ("4294967296", Err(VersionParseError::invalid_component(0, "4294967296"))),
+            ("1.2.3.4.5", Err(VersionParseError::TooManyComponents(5))),
+        ];
+
+        for (s, expected) in data {
+            assert_eq!(expected, Version::parse_strict(s), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn parse_lenient() {
+        let data = [
+            ("8.1.1911 (Core)", Version::Semantic(8, 1, 1911, 0)),
+            ("CentOS Linux release 7.9.2009 (Core)", Version::Semantic(7, 9, 2009, 0)),
+            ("v2.0-rc1", Version::Semantic(2, 0, 0, 0)),
+            ("1.2.3.4.5", Version::Semantic(1, 2, 3, 4)),
+            ("1.2.", Version::Semantic(1, 2, 0, 0)),
+            ("", Version::Unknown),
+            ("bookworm/sid", Version::Custom("bookworm/sid".to_owned())),
+            ("99999999999", Version::Custom("99999999999".to_owned())),
+        ];
+
+        for (s, expected) in data {
+            assert_eq!(expected, Version::parse_lenient(s), "{s:?}");
+        }
+    }
+
+    #[test]
+    fn parse_error_display() {
+        assert_eq!(
+            "invalid version component 2: \"3abc\"",
+            VersionParseError::invalid_component(2, "3abc").to_string()
+        );
+        assert_eq!("too many version components: 5 (at most 4)", VersionParseError::TooManyComponents(5).to_string());
+    }
+
     #[test]
     fn default() {
         assert_eq!(Version::Unknown, Version::default());
@@ -153,4 +469,23 @@ mod tests {
             assert_eq!(expected, &version.to_string());
         }
     }
+
+    proptest! {
+        #[test]
+        fn from_string_never_panics(s in any::<String>()) {
+            Version::from_string(s);
+        }
+
+        #[test]
+        fn parse_never_panics(s in any::<String>()) {
+            let _ = Version::parse_strict(&s);
+            Version::parse_lenient(&s);
+        }
+
+        #[test]
+        fn semantic_round_trip(major: u32, minor: u32, build: u32, release: u32) {
+            let version = Version::Semantic(major, minor, build, release);
+            prop_assert_eq!(version.clone(), Version::from_string(version.to_string()));
+        }
+    }
 }
@@ -0,0 +1,173 @@
+//! Background thread detecting the operating system periodically and reporting its changes, for
+//! agents that must notice in-place upgrades without restarting, see `watch`.
+
+use std::{
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use crate::{
+    FieldChange, OSInfo,
+    global::{GLOBAL, Global},
+};
+
+/// Change of the operating system information noticed by `watch`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchEvent {
+    /// Information detected before the change.
+    pub previous: OSInfo,
+    /// Information detected after the change.
+    pub current: OSInfo,
+    /// Fields that differ between `previous` and `current`, see `OSInfo::diff`.
+    pub changes: Vec<FieldChange>,
+}
+
+/// Handle of the thread started by `watch`.
+///
+/// Dropping the handle also stops the thread, without waiting for it.
+#[derive(Debug)]
+pub struct Watcher {
+    stop: Sender<()>,
+    thread: JoinHandle<()>,
+}
+
+impl Watcher {
+    /// Stops watching, and waits for a detection or callback in progress to finish.
+    pub fn stop(self) {
+        drop(self.stop);
+        let _ = self.thread.join();
+    }
+}
+
+/// Calls `refresh` every `interval` on a background thread, and calls `callback` whenever it
+/// detects that the operating system id or version changed, e.g. after a Windows cumulative
+/// update, a macOS Rapid Security Response or a distribution upgrade.
+///
+/// The changes are compared with and stored as the value of `global`, which is detected when
+/// the thread starts if it wasn't already, and the subscribers registered with
+/// `subscribe_refresh` are notified too. The thread runs until `Watcher::stop` is called or the
+/// watcher is dropped.
+///
+/// Changes are found by polling, as release files can be replaced in many ways (package
+/// upgrades, image-based updates, bind mounts) that no single notification covers; detection is
+/// cheap, so an interval of a few minutes is usually fine.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let watcher = osinfo::watch(Duration::from_secs(300), |event| {
+///     for change in &event.changes {
+///         println!("Operating system {change}");
+///     }
+/// });
+/// watcher.stop();
+/// ```
+pub fn watch<F>(interval: Duration, callback: F) -> Watcher
+where
+    F: FnMut(&WatchEvent) + Send + 'static,
+{
+    watch_with(interval, &GLOBAL, crate::get, callback)
+}
+
+fn watch_with<D, F>(interval: Duration, global: &'static Global, mut detect: D, mut callback: F) -> Watcher
+where
+    D: FnMut() -> OSInfo + Send + 'static,
+    F: FnMut(&WatchEvent) + Send + 'static,
+{
+    let (stop, stopped) = mpsc::channel();
+    let thread = thread::spawn(move || {
+        global.get(&mut detect);
+        // Nothing is ever sent: the watcher stops when the sender is dropped.
+        while let Err(RecvTimeoutError::Timeout) = stopped.recv_timeout(interval) {
+            let Some((previous, current)) = global.refresh_change(&mut detect) else {
+                continue;
+            };
+
+            callback(&WatchEvent {
+                previous: previous.clone(),
+                current: current.clone(),
+                changes: previous.diff(current),
+            });
+        }
+    });
+    Watcher { stop, thread }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{FieldName, Version};
+    use pretty_assertions::assert_eq;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn reports_changes() {
+        static GLOBAL: Global = Global::new();
+        let versions = [(22, 4), (22, 4), (24, 4), (24, 4), (24, 10)];
+        let mut detections = versions.into_iter().chain(core::iter::repeat((24, 10))).map(|(major, minor)| {
+            OSInfo::builder()
+                .id("ubuntu")
+                .version(Version::Semantic(major, minor, 0, 0))
+                .build()
+        });
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&events);
+
+        let watcher = watch_with(
+            Duration::from_millis(1),
+            &GLOBAL,
+            move || detections.next().unwrap(),
+            move |event: &WatchEvent| received.lock().unwrap().push(event.clone()),
+        );
+        while events.lock().unwrap().len() < 2 {
+            thread::sleep(Duration::from_millis(1));
+        }
+        watcher.stop();
+
+        let events = events.lock().unwrap();
+        assert_eq!(2, events.len());
+        assert_eq!(&Version::Semantic(22, 4, 0, 0), events[0].previous.version());
+        assert_eq!(&Version::Semantic(24, 4, 0, 0), events[0].current.version());
+        assert_eq!(vec![FieldName::Version], events[0].changes.iter().map(|change| change.field).collect::<Vec<_>>());
+        assert_eq!(Some("24.10.0.0"), events[1].changes[0].new.as_deref());
+        assert_eq!(&events[1].current, GLOBAL.get(|| unreachable!()));
+    }
+
+    #[test]
+    fn ignores_other_fields() {
+        static GLOBAL: Global = Global::new();
+        let codenames = ["jammy", "Jammy Jellyfish"];
+        let mut detections = codenames.into_iter().chain(core::iter::repeat("Jammy Jellyfish")).map(|codename| {
+            OSInfo::builder()
+                .id("ubuntu")
+                .version(Version::Semantic(22, 4, 0, 0))
+                .codename(codename)
+                .build()
+        });
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = Arc::clone(&events);
+
+        let watcher = watch_with(
+            Duration::from_millis(1),
+            &GLOBAL,
+            move || detections.next().unwrap(),
+            move |event: &WatchEvent| received.lock().unwrap().push(event.clone()),
+        );
+        thread::sleep(Duration::from_millis(20));
+        watcher.stop();
+
+        // Like `refresh`, only id and version changes are reported and stored.
+        assert_eq!(Vec::<WatchEvent>::new(), *events.lock().unwrap());
+        assert_eq!(Some("jammy"), GLOBAL.get(|| unreachable!()).codename());
+    }
+
+    #[test]
+    fn stops() {
+        let watcher = watch(Duration::from_secs(3600), |_| panic!("No change expected"));
+        watcher.stop();
+    }
+}
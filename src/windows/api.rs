@@ -1,72 +1,240 @@
 #![allow(unsafe_code)]
-use crate::{OSInfo, Version};
-use winreg::{RegKey, enums::*};
+use std::{io, iter, mem, ptr};
 
-pub fn get_os_data() -> OSInfo {
-    current_version_from_reg()
+use crate::{Diagnostic, OSInfo, Version};
+
+use super::{
+    current_version,
+    registry::{LiveRegistry, RegistryKey, RegistryProvider, RegistryValue},
+};
+
+/// Predefined registry keys, sign-extended to the size of a handle like in `winreg.h`.
+pub const HKEY_CURRENT_USER: isize = 0x8000_0001_u32 as i32 as isize;
+pub const HKEY_LOCAL_MACHINE: isize = 0x8000_0002_u32 as i32 as isize;
+const KEY_READ: u32 = 0x20019;
+const ERROR_SUCCESS: i32 = 0;
+const ERROR_MORE_DATA: i32 = 234;
+const ERROR_NO_MORE_ITEMS: i32 = 259;
+
+const FIPS_POLICY_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\Lsa\\FipsAlgorithmPolicy";
+const PRODUCT_UNDEFINED: u32 = 0;
+/// `PACKAGE_FULL_NAME_MAX_LENGTH` plus the terminating NUL.
+const PACKAGE_FULL_NAME_CAPACITY: u32 = 128;
+/// Error returned by `GetCurrentPackageFullName` for unpackaged processes.
+const APPMODEL_ERROR_NO_PACKAGE: i32 = 15700;
+
+/// `GetCurrentPackageFullName`, which only exists since Windows 8 and Windows Server 2012.
+type GetCurrentPackageFullNameFn = unsafe extern "system" fn(length: *mut u32, full_name: *mut u16) -> i32;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn GetProductInfo(os_major: u32, os_minor: u32, sp_major: u32, sp_minor: u32, product_type: *mut u32) -> i32;
+    fn GetModuleHandleW(module_name: *const u16) -> isize;
+    fn GetProcAddress(module: isize, proc_name: *const u8) -> Option<unsafe extern "system" fn() -> isize>;
+}
+
+#[link(name = "advapi32")]
+unsafe extern "system" {
+    fn RegOpenKeyExW(key: isize, sub_key: *const u16, options: u32, sam_desired: u32, result: *mut isize) -> i32;
+    fn RegQueryInfoKeyW(
+        key: isize,
+        class: *mut u16,
+        class_len: *mut u32,
+        reserved: *mut u32,
+        sub_keys: *mut u32,
+        max_sub_key_len: *mut u32,
+        max_class_len: *mut u32,
+        values: *mut u32,
+        max_value_name_len: *mut u32,
+        max_value_len: *mut u32,
+        security_descriptor_len: *mut u32,
+        last_write_time: *mut u64,
+    ) -> i32;
+    fn RegEnumValueW(
+        key: isize,
+        index: u32,
+        value_name: *mut u16,
+        value_name_len: *mut u32,
+        reserved: *mut u32,
+        value_type: *mut u32,
+        data: *mut u8,
+        data_len: *mut u32,
+    ) -> i32;
+    fn RegCloseKey(key: isize) -> i32;
 }
 
+/// Open registry key, closed when dropped.
+struct KeyHandle(isize);
 
-fn current_version_from_reg() -> OSInfo {
-    let mut os_info = OSInfo {
-        id: Some(String::from("windows")),
-        ..Default::default()
+impl Drop for KeyHandle {
+    fn drop(&mut self) {
+        // SAFETY: the handle was opened by `RegOpenKeyExW` and is closed only once.
+        unsafe { RegCloseKey(self.0) };
+    }
+}
+
+/// Reads the values of the key at `path` under the predefined key `root`, e.g.
+/// `HKEY_LOCAL_MACHINE`. Values that can't be read are skipped.
+pub fn read_registry_key(root: isize, path: &str) -> io::Result<RegistryKey> {
+    let path: Vec<u16> = path.encode_utf16().chain(iter::once(0)).collect();
+    let mut handle: isize = 0;
+    // SAFETY: `path` is NUL terminated and `handle` is a valid, writable `HKEY`.
+    let result = unsafe { RegOpenKeyExW(root, path.as_ptr(), 0, KEY_READ, &mut handle) };
+    if result != ERROR_SUCCESS {
+        return Err(io::Error::from_raw_os_error(result));
+    }
+    let key = KeyHandle(handle);
+
+    let (mut max_name_len, mut max_data_len) = (0u32, 0u32);
+    // SAFETY: the lengths are valid, writable `DWORD`s, and the other outputs may be null.
+    let result = unsafe {
+        RegQueryInfoKeyW(
+            key.0,
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            ptr::null_mut(),
+            &mut max_name_len,
+            &mut max_data_len,
+            ptr::null_mut(),
+            ptr::null_mut(),
+        )
     };
-    let current_version = get_registry(RegKey::predef(HKEY_LOCAL_MACHINE), "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion");
-    match current_version {
-        Ok(current_version) => {
-            os_info.version = get_version(&current_version);
+    if result != ERROR_SUCCESS {
+        return Err(io::Error::from_raw_os_error(result));
+    }
 
-            os_info.name = get_registry_value(&current_version, "ProductName");
-            os_info.variant = get_registry_value(&current_version, "InstallationType");
-            os_info.edition = get_registry_value(&current_version, "EditionID");
-            os_info.codename = get_registry_value(&current_version, "DisplayVersion");
-        },
+    // The maximum name length excludes the terminating NUL.
+    let mut name = vec![0u16; max_name_len as usize + 1];
+    let mut data = vec![0u8; max_data_len as usize];
+    let mut values = Vec::new();
+    let mut index = 0;
+    loop {
+        let mut name_len = name.len() as u32;
+        let mut data_len = data.len() as u32;
+        let mut value_type: u32 = 0;
+        // SAFETY: the buffers are valid and writable for the given lengths.
+        let result = unsafe {
+            RegEnumValueW(
+                key.0,
+                index,
+                name.as_mut_ptr(),
+                &mut name_len,
+                ptr::null_mut(),
+                &mut value_type,
+                data.as_mut_ptr(),
+                &mut data_len,
+            )
+        };
+        match result {
+            ERROR_SUCCESS => {
+                let value = RegistryValue::from_raw(value_type, &data[..data_len as usize]);
+                values.push((String::from_utf16_lossy(&name[..name_len as usize]), value));
+            }
+            ERROR_NO_MORE_ITEMS => break,
+            // A value was added or grew since the key was queried: retry with larger buffers.
+            ERROR_MORE_DATA => {
+                name.resize(name.len() * 2, 0);
+                data.resize((data_len as usize).max(data.len() * 2 + 2), 0);
+                continue;
+            }
+            error => crate::logging::trace!("RegEnumValueW failed for value {}: {}", index, error),
+        }
+        index += 1;
+    }
+    Ok(RegistryKey::new(values))
+}
+
+pub fn get_os_data(diagnostics: &mut Vec<Diagnostic>) -> OSInfo {
+    current_version::get_os_data(&LiveRegistry, get_product_sku, diagnostics)
+}
 
+/// Reads the "System cryptography: Use FIPS compliant algorithms" policy.
+pub fn fips_enabled() -> Option<bool> {
+    match LiveRegistry.read_key(FIPS_POLICY_KEY) {
+        Ok(policy) => Some(policy.dword("Enabled").unwrap_or_default() == 1),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Some(false),
         Err(e) => {
-            log::error!("Failed to get registry key: {}", e);
+            crate::logging::error!("Failed to get registry key: {}", e);
+            None
         }
-        
     }
-    
-    os_info
 }
 
-fn get_registry(reg_root: RegKey, path: &str) -> std::io::Result<RegKey> {
-    reg_root.open_subkey(path)
-}
-fn get_version(reg_key: &RegKey) -> Version {
-    let major = reg_key.get_value::<u32, _>("CurrentMajorVersionNumber").unwrap_or_default();
-    let minor = reg_key.get_value::<u32, _>("CurrentMinorVersionNumber").unwrap_or_default();
-    let build = reg_key.get_value::<String, _>("CurrentBuildNumber").unwrap_or_default().parse::<u32>().unwrap_or_default();
-    let ubr = reg_key.get_value::<u32, _>("UBR").unwrap_or_default();
-
-    Version::Semantic(
-        major,
-        minor,
-        build,
-        ubr,
-    )
+/// Returns the product type (SKU) number of the installed edition.
+fn get_product_sku(version: &Version) -> Option<u32> {
+    let Version::Semantic(major, minor, _, _) = *version else {
+        return None;
+    };
+
+    let mut product_type = PRODUCT_UNDEFINED;
+    // SAFETY: `product_type` is a valid, writable `DWORD`.
+    let result = unsafe { GetProductInfo(major, minor, 0, 0, &mut product_type) };
+    if result == 0 || product_type == PRODUCT_UNDEFINED {
+        crate::logging::trace!("GetProductInfo failed: {}, product type {:#x}", result, product_type);
+        return None;
+    }
+
+    Some(product_type)
 }
 
-fn get_registry_value(reg_key: &RegKey, name: &str) -> Option<String> {
-    if let Ok(value) = reg_key.get_value::<String, _>(name) {
-        Some(value)
-    } else {
-        None
+/// Resolves `GetCurrentPackageFullName` at runtime, as linking it would keep the crate from
+/// loading on Windows 7 and Windows Server 2008 R2.
+fn get_current_package_full_name_fn() -> Option<GetCurrentPackageFullNameFn> {
+    let module_name: Vec<u16> = "kernel32.dll".encode_utf16().chain(iter::once(0)).collect();
+    // SAFETY: `module_name` is NUL-terminated; kernel32 is loaded in every process.
+    let module = unsafe { GetModuleHandleW(module_name.as_ptr()) };
+    if module == 0 {
+        crate::logging::trace!("GetModuleHandleW failed: {}", io::Error::last_os_error());
+        return None;
+    }
+    // SAFETY: `module` is a valid module handle and the name is NUL-terminated.
+    let function = unsafe { GetProcAddress(module, c"GetCurrentPackageFullName".as_ptr().cast()) };
+    if function.is_none() {
+        crate::logging::trace!("GetCurrentPackageFullName is not available");
     }
+    function.map(|function| {
+        // SAFETY: `GetCurrentPackageFullName` has the signature of `GetCurrentPackageFullNameFn`.
+        unsafe { mem::transmute::<unsafe extern "system" fn() -> isize, GetCurrentPackageFullNameFn>(function) }
+    })
 }
 
+/// Returns the full name of the package the process runs in, or `None` if it isn't packaged or
+/// the system predates packages.
+pub fn current_package_full_name() -> Option<String> {
+    let get_current_package_full_name = get_current_package_full_name_fn()?;
+    let mut buffer = [0u16; PACKAGE_FULL_NAME_CAPACITY as usize];
+    let mut length = PACKAGE_FULL_NAME_CAPACITY;
+    // SAFETY: `buffer` has room for `length` UTF-16 code units.
+    let result = unsafe { get_current_package_full_name(&mut length, buffer.as_mut_ptr()) };
+    if result != 0 {
+        if result != APPMODEL_ERROR_NO_PACKAGE {
+            crate::logging::trace!("GetCurrentPackageFullName failed: {}", result);
+        }
+        return None;
+    }
+
+    // `length` includes the terminating NUL.
+    let length = (length as usize).saturating_sub(1).min(buffer.len());
+    Some(String::from_utf16_lossy(&buffer[..length]))
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use pretty_assertions::{assert_eq, assert_ne};
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn windows() {
-        let info = get_os_data();
-        assert_eq!(String::from("windows"), info.get_id());
-        assert!(info.get_name().contains("Windows"));
+        let info = get_os_data(&mut Vec::new());
+        assert_eq!("windows", info.id());
+        assert!(info.name().contains("Windows"));
+        assert!(info.product_sku().is_some());
+        // CI runs on Windows 10 or later, which has packages, but tests don't run packaged.
+        assert!(get_current_package_full_name_fn().is_some());
+        assert_eq!(None, current_package_full_name());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,58 @@
+#![allow(unsafe_code)]
+use std::{ffi::OsStr, io, iter, os::windows::ffi::OsStrExt};
+
+use crate::logging::trace;
+
+use super::registry::{LiveRegistry, RegistryProvider};
+
+const EFI_GLOBAL_VARIABLE: &str = "{8BE4DF61-93CA-11D2-AA0D-00E098032B8C}";
+const SECURE_BOOT_STATE_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\SecureBoot\\State";
+/// Returned by `GetFirmwareEnvironmentVariableW` on legacy BIOS systems.
+const ERROR_INVALID_FUNCTION: i32 = 1;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn GetFirmwareEnvironmentVariableW(name: *const u16, guid: *const u16, buffer: *mut u8, size: u32) -> u32;
+}
+
+pub fn secure_boot() -> Option<bool> {
+    let name = wide("SecureBoot");
+    let guid = wide(EFI_GLOBAL_VARIABLE);
+    let mut value = 0u8;
+    // SAFETY: the strings are NUL terminated and `value` is a valid, writable 1 byte buffer.
+    let size = unsafe { GetFirmwareEnvironmentVariableW(name.as_ptr(), guid.as_ptr(), &mut value, 1) };
+    if size == 1 {
+        return Some(value == 1);
+    }
+
+    let error = io::Error::last_os_error();
+    if error.raw_os_error() == Some(ERROR_INVALID_FUNCTION) {
+        trace!("Booted via legacy BIOS");
+        return Some(false);
+    }
+
+    // Reading firmware variables requires the SeSystemEnvironmentPrivilege, which unelevated
+    // processes don't have; Windows mirrors the state in the registry.
+    trace!("GetFirmwareEnvironmentVariableW failed: {:?}", error);
+    LiveRegistry
+        .read_key(SECURE_BOOT_STATE_KEY)
+        .ok()?
+        .dword("UEFISecureBootEnabled")
+        .map(|enabled| enabled == 1)
+}
+
+/// Converts the string to a NUL terminated UTF-16 string.
+fn wide(s: &str) -> Vec<u16> {
+    OsStr::new(s).encode_wide().chain(iter::once(0)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn wide_string() {
+        assert_eq!(vec![0x53, 0x42, 0], wide("SB"));
+    }
+}
@@ -0,0 +1,250 @@
+use crate::{Diagnostic, FieldName, OSInfo, Version, consts, lookup};
+
+use super::{
+    product,
+    registry::{RegistryKey, RegistryProvider},
+};
+
+pub(crate) const CURRENT_VERSION_KEY: &str = "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion";
+
+/// Reads the operating system information from the `CurrentVersion` key of `registry`.
+/// `product_sku` returns the product type (SKU) of the given version, which only the live system
+/// knows.
+pub(crate) fn get_os_data(
+    registry: &dyn RegistryProvider,
+    product_sku: impl FnOnce(&Version) -> Option<u32>,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> OSInfo {
+    let mut os_info = OSInfo {
+        id: Some(String::from(consts::WINDOWS)),
+        ..Default::default()
+    };
+    let source = registry.source(CURRENT_VERSION_KEY);
+    let current_version = match registry.read_key(CURRENT_VERSION_KEY) {
+        Ok(current_version) => current_version,
+        Err(e) => {
+            crate::logging::error!("Failed to get registry key: {}", e);
+            diagnostics.push(Diagnostic::source_unreadable(&source, e));
+            return os_info;
+        }
+    };
+
+    diagnostics.push(Diagnostic::source_used(&source));
+    os_info.version = get_version(&current_version);
+    os_info.product_sku = product_sku(&os_info.version);
+
+    let mut value = |name: &str| {
+        let value = current_version.string(name).map(String::from);
+        if value.is_none() {
+            diagnostics.push(Diagnostic::field_not_found(&source, name));
+        }
+        value
+    };
+    let product_name = value("ProductName");
+    os_info.variant = value("InstallationType");
+    os_info.edition = value("EditionID");
+    os_info.codename = value("DisplayVersion");
+
+    // Device editions report "Client" (or "IoTUAP" on IoT Core) as installation type.
+    let mut variant_source = "InstallationType";
+    if let Some((variant, source)) = product::device_variant(os_info.product_sku, os_info.edition.as_deref()) {
+        os_info.variant = Some(String::from(variant));
+        variant_source = source;
+    }
+
+    // `ProductName` is localized (and still says "Windows 10" on Windows 11).
+    let neutral_name = product::neutral_name(&os_info.version, os_info.product_sku, os_info.variant.as_deref());
+    let name_source = if neutral_name.is_some() { "GetProductInfo" } else { "ProductName" };
+    os_info.name = neutral_name.or_else(|| product_name.clone());
+    os_info.localized_name = product_name;
+
+    // Admins refer to server releases by year; `DisplayVersion` alone is ambiguous with the
+    // client releases, and Windows Server 2019 only has the older `ReleaseId`.
+    let mut codename_source = "DisplayVersion";
+    if os_info.variant.as_deref().is_some_and(|variant| variant.starts_with("Server"))
+        && let Version::Semantic(_, _, build, _) = os_info.version
+        && let Some(release) = lookup::windows_server_release(build)
+    {
+        let display_version = os_info.codename.as_deref().or_else(|| current_version.string("ReleaseId"));
+        os_info.codename = Some(match display_version {
+            Some(display_version) => format!("Server {release} {display_version}"),
+            None => format!("Server {release}"),
+        });
+        codename_source = "CurrentBuildNumber";
+    }
+
+    os_info.record_source(&[FieldName::Version], &source);
+    for (field, name) in [
+        (FieldName::Name, name_source),
+        (FieldName::Variant, variant_source),
+        (FieldName::Edition, "EditionID"),
+        (FieldName::Codename, codename_source),
+        (FieldName::ProductSku, "GetProductInfo"),
+        (FieldName::LocalizedName, "ProductName"),
+    ] {
+        let value_source = if name == "GetProductInfo" { String::from(name) } else { format!("{source}\\{name}") };
+        os_info.record_source(&[field], &value_source);
+    }
+
+    os_info
+}
+
+/// Returns the build number and the update build revision (UBR) of the `CurrentVersion` key.
+pub(crate) fn get_build(current_version: &RegistryKey) -> (u32, u32) {
+    let build = current_version
+        .string("CurrentBuildNumber")
+        .and_then(|build| build.parse().ok())
+        .unwrap_or_default();
+    (build, current_version.dword("UBR").unwrap_or_default())
+}
+
+fn get_version(current_version: &RegistryKey) -> Version {
+    let major = current_version.dword("CurrentMajorVersionNumber").unwrap_or_default();
+    let minor = current_version.dword("CurrentMinorVersionNumber").unwrap_or_default();
+    let (build, ubr) = get_build(current_version);
+
+    Version::Semantic(major, minor, build, ubr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::windows::{
+        hive::{HiveRegistry, tests::hive_with},
+        registry::{MemoryRegistry, RegistryValue},
+    };
+    use pretty_assertions::assert_eq;
+
+    fn string(value: &str) -> RegistryValue {
+        RegistryValue::String(String::from(value))
+    }
+
+    fn windows_11_pro() -> RegistryKey {
+        RegistryKey::new([
+            ("CurrentMajorVersionNumber", RegistryValue::Dword(10)),
+            ("CurrentMinorVersionNumber", RegistryValue::Dword(0)),
+            ("CurrentBuildNumber", string("22631")),
+            ("UBR", RegistryValue::Dword(3296)),
+            ("ProductName", string("Windows 10 Pro")),
+            ("InstallationType", string("Client")),
+            ("EditionID", string("Professional")),
+            ("DisplayVersion", string("23H2")),
+        ])
+    }
+
+    #[test]
+    fn memory_registry() {
+        let registry = MemoryRegistry::new().with_key(CURRENT_VERSION_KEY, windows_11_pro());
+        let mut diagnostics = Vec::new();
+
+        let info = get_os_data(&registry, |_| Some(0x30), &mut diagnostics);
+        assert_eq!("windows", info.id());
+        assert_eq!("Windows 11 Pro", info.name());
+        assert_eq!("Windows 10 Pro", info.localized_name());
+        assert_eq!(&Version::Semantic(10, 0, 22631, 3296), info.version());
+        assert_eq!(Some("Client"), info.variant());
+        assert_eq!(Some("Professional"), info.edition());
+        assert_eq!(Some("23H2"), info.codename());
+        assert_eq!(Some("GetProductInfo"), info.source_of(FieldName::Name));
+        assert_eq!(
+            Some("HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\EditionID"),
+            info.source_of(FieldName::Edition)
+        );
+        assert_eq!(vec![Diagnostic::source_used(registry.source(CURRENT_VERSION_KEY))], diagnostics);
+
+        assert_eq!(
+            Some("HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\DisplayVersion"),
+            info.source_of(FieldName::Codename)
+        );
+
+        // Without the product type, the localized name is all there is.
+        let info = get_os_data(&registry, |_| None, &mut Vec::new());
+        assert_eq!("Windows 10 Pro", info.name());
+        assert_eq!(Some("HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\ProductName"), info.source_of(FieldName::Name));
+    }
+
+    #[test]
+    fn variant_source() {
+        let registry = |edition_id: &str| {
+            let key = RegistryKey::new(windows_11_pro().values().map(|(name, value)| match name {
+                "EditionID" => (name.to_string(), string(edition_id)),
+                _ => (name.to_string(), value.clone()),
+            }));
+            MemoryRegistry::new().with_key(CURRENT_VERSION_KEY, key)
+        };
+        let source = |name: &str| Some(format!("HKLM\\SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion\\{name}"));
+
+        // IoT Enterprise LTSC reports the product type of Enterprise LTSC.
+        let info = get_os_data(&registry("IoTEnterpriseS"), |_| Some(0x7D), &mut Vec::new());
+        assert_eq!(Some("IoT"), info.variant());
+        assert_eq!(source("EditionID").as_deref(), info.source_of(FieldName::Variant));
+
+        let info = get_os_data(&registry("IoTEnterprise"), |_| Some(0xBC), &mut Vec::new());
+        assert_eq!(Some("IoT"), info.variant());
+        assert_eq!(Some("GetProductInfo"), info.source_of(FieldName::Variant));
+
+        let info = get_os_data(&registry("Professional"), |_| Some(0x30), &mut Vec::new());
+        assert_eq!(source("InstallationType").as_deref(), info.source_of(FieldName::Variant));
+    }
+
+    #[test]
+    fn server_codename() {
+        let key = |build: &str, values: &[(&str, &str)]| {
+            let mut key = vec![
+                ("CurrentMajorVersionNumber", RegistryValue::Dword(10)),
+                ("CurrentBuildNumber", string(build)),
+                ("InstallationType", string("Server")),
+            ];
+            key.extend(values.iter().map(|&(name, value)| (name, string(value))));
+            MemoryRegistry::new().with_key(CURRENT_VERSION_KEY, RegistryKey::new(key))
+        };
+        let codename = |registry: MemoryRegistry| get_os_data(&registry, |_| None, &mut Vec::new()).codename;
+
+        assert_eq!(Some("Server 2022 21H2"), codename(key("20348", &[("DisplayVersion", "21H2")])).as_deref());
+        assert_eq!(Some("Server 2019 1809"), codename(key("17763", &[("ReleaseId", "1809")])).as_deref());
+        assert_eq!(Some("Server 2025"), codename(key("26100", &[])).as_deref());
+        // Before Windows Server 2016, or on clients, the display version is kept as is.
+        assert_eq!(Some("1507"), codename(key("10240", &[("DisplayVersion", "1507")])).as_deref());
+        let client = MemoryRegistry::new().with_key(CURRENT_VERSION_KEY, windows_11_pro());
+        assert_eq!(Some("23H2"), codename(client).as_deref());
+    }
+
+    #[test]
+    fn missing_key() {
+        let mut diagnostics = Vec::new();
+        let info = get_os_data(&MemoryRegistry::new(), |_| None, &mut diagnostics);
+        assert_eq!("windows", info.id());
+        assert_eq!(&Version::Unknown, info.version());
+        assert!(matches!(diagnostics[..], [Diagnostic::SourceUnreadable { .. }]));
+    }
+
+    #[test]
+    fn offline_hive() {
+        let values = [
+            ("CurrentMajorVersionNumber", RegistryValue::Dword(10)),
+            ("CurrentMinorVersionNumber", RegistryValue::Dword(0)),
+            ("CurrentBuildNumber", string("20348")),
+            ("UBR", RegistryValue::Dword(2340)),
+            ("ProductName", string("Windows Server 2022 Datacenter")),
+            ("InstallationType", string("Server Core")),
+            ("EditionID", string("ServerDatacenter")),
+        ];
+        let hive = HiveRegistry::from_bytes(hive_with(&[("Microsoft\\Windows NT\\CurrentVersion", &values)]), "SOFTWARE").unwrap();
+        let mut diagnostics = Vec::new();
+
+        let info = get_os_data(&hive, |_| None, &mut diagnostics);
+        assert_eq!("Windows Server 2022 Datacenter", info.name());
+        assert_eq!(&Version::Semantic(10, 0, 20348, 2340), info.version());
+        assert_eq!(Some("Server Core"), info.variant());
+        assert_eq!(Some("Server 2022"), info.codename());
+        assert_eq!(Some("hive:Microsoft\\Windows NT\\CurrentVersion"), info.source_of(FieldName::Version));
+        assert_eq!(
+            Some("hive:Microsoft\\Windows NT\\CurrentVersion\\CurrentBuildNumber"),
+            info.source_of(FieldName::Codename)
+        );
+        assert_eq!(
+            Diagnostic::field_not_found("hive:Microsoft\\Windows NT\\CurrentVersion", "DisplayVersion"),
+            diagnostics[1]
+        );
+    }
+}
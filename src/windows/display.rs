@@ -0,0 +1,30 @@
+#![allow(unsafe_code)]
+
+use crate::{DisplayServer, DisplayServerKind, SessionKind};
+
+/// `SM_CMONITORS` index of `GetSystemMetrics`: the number of display monitors on the desktop.
+const SM_CMONITORS: i32 = 80;
+
+#[link(name = "user32")]
+unsafe extern "system" {
+    fn GetSystemMetrics(index: i32) -> i32;
+}
+
+pub fn get_display_server(session: SessionKind) -> DisplayServer {
+    // Services run in session 0, which has no interactive desktop.
+    if session == SessionKind::Service {
+        return DisplayServer {
+            kind: DisplayServerKind::Headless,
+            compositor: None,
+            display_attached: None,
+        };
+    }
+
+    // SAFETY: GetSystemMetrics has no preconditions.
+    let monitors = unsafe { GetSystemMetrics(SM_CMONITORS) };
+    DisplayServer {
+        kind: DisplayServerKind::Windows,
+        compositor: Some(String::from("DWM")),
+        display_attached: Some(monitors > 0),
+    }
+}
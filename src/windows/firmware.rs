@@ -0,0 +1,55 @@
+#![allow(unsafe_code)]
+use crate::logging::trace;
+
+use super::registry::{LiveRegistry, RegistryProvider};
+use crate::{Firmware, FirmwareType};
+
+const BIOS_KEY: &str = "HARDWARE\\DESCRIPTION\\System\\BIOS";
+
+/// `FIRMWARE_TYPE` values from `winnt.h`.
+const FIRMWARE_TYPE_BIOS: u32 = 1;
+const FIRMWARE_TYPE_UEFI: u32 = 2;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn GetFirmwareType(firmware_type: *mut u32) -> i32;
+}
+
+pub fn get_firmware() -> Firmware {
+    let bios = LiveRegistry.read_key(BIOS_KEY);
+    if let Err(ref e) = bios {
+        trace!("Unable to open {}: {:?}", BIOS_KEY, e);
+    }
+    let value = |name: &str| bios.as_ref().ok()?.string(name).map(String::from);
+
+    Firmware {
+        firmware_type: firmware_type(),
+        vendor: value("BIOSVendor"),
+        version: value("BIOSVersion"),
+    }
+}
+
+fn firmware_type() -> Option<FirmwareType> {
+    let mut firmware_type = 0;
+    // SAFETY: `firmware_type` is a valid, writable `FIRMWARE_TYPE`.
+    if unsafe { GetFirmwareType(&mut firmware_type) } == 0 {
+        trace!("GetFirmwareType failed");
+        return None;
+    }
+
+    match firmware_type {
+        FIRMWARE_TYPE_BIOS => Some(FirmwareType::Bios),
+        FIRMWARE_TYPE_UEFI => Some(FirmwareType::Uefi),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current() {
+        assert!(get_firmware().firmware_type.is_some());
+    }
+}
@@ -0,0 +1,411 @@
+//! Reader of registry hive files (the "regf" format), e.g. `Windows\System32\config\SOFTWARE`
+//! of a mounted Windows image.
+
+use std::{fs, io, path::Path};
+
+use super::registry::{RegistryKey, RegistryProvider, RegistryValue};
+
+/// Size of the base block preceding the hive bins, to which cell offsets are relative.
+const BASE_BLOCK_SIZE: usize = 4096;
+/// Offset of the root key cell offset in the base block.
+const ROOT_CELL_OFFSET: usize = 0x24;
+/// Flag of key nodes whose name is stored as Latin-1 rather than UTF-16LE.
+const KEY_COMP_NAME: u16 = 0x0020;
+/// Flag of values whose name is stored as Latin-1 rather than UTF-16LE.
+const VALUE_COMP_NAME: u16 = 0x0001;
+/// Bit of the data size of values stored in the data offset field.
+const DATA_INLINE: u32 = 0x8000_0000;
+/// Maximum depth of nested subkey index lists ("ri" records).
+const MAX_INDEX_DEPTH: usize = 4;
+
+/// Registry hive file mounted at a key of `HKEY_LOCAL_MACHINE`, e.g. the `SOFTWARE` hive of an
+/// offline Windows image mounted at `HKLM\SOFTWARE`.
+///
+/// Values larger than 16 KiB, which are split into "big data" records, are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// use osinfo::windows::HiveRegistry;
+///
+/// let hive = HiveRegistry::open("/mnt/windows/Windows/System32/config/SOFTWARE", "SOFTWARE")?;
+/// let info = osinfo::windows::get_from_registry(&hive);
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct HiveRegistry {
+    data: Vec<u8>,
+    mount_point: String,
+    source: String,
+}
+
+impl HiveRegistry {
+    /// Reads the hive file at `path`, mounted at the key `mount_point` (e.g. "SOFTWARE" or
+    /// "SYSTEM").
+    pub fn open(path: impl AsRef<Path>, mount_point: impl Into<String>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let hive = Self::from_bytes(fs::read(path)?, mount_point)?;
+        Ok(Self {
+            source: path.display().to_string(),
+            ..hive
+        })
+    }
+
+    /// Parses the contents of a hive file mounted at the key `mount_point`.
+    pub fn from_bytes(data: Vec<u8>, mount_point: impl Into<String>) -> io::Result<Self> {
+        if data.len() < BASE_BLOCK_SIZE || !data.starts_with(b"regf") {
+            return Err(invalid("not a registry hive"));
+        }
+        Ok(Self {
+            data,
+            mount_point: mount_point.into(),
+            source: String::from("hive"),
+        })
+    }
+
+    /// Returns the data of the cell at `offset`, relative to the hive bins.
+    fn cell(&self, offset: u32) -> io::Result<&[u8]> {
+        // Offsets come from the hive, and may overflow `usize` on 32-bit targets.
+        let out_of_bounds = || invalid("cell out of bounds");
+        let start = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| BASE_BLOCK_SIZE.checked_add(offset))
+            .ok_or_else(out_of_bounds)?;
+        let size = u32_at(&self.data, start)? as i32;
+        // Allocated cells have a negative size, which includes the size field.
+        let size = size.checked_neg().filter(|&size| size >= 4).ok_or_else(|| invalid("free or empty cell"))? as usize;
+        let end = start.checked_add(size).ok_or_else(out_of_bounds)?;
+        self.data.get(start + 4..end).ok_or_else(out_of_bounds)
+    }
+
+    /// Returns the key node ("nk" record) at `offset`.
+    fn key_node(&self, offset: u32) -> io::Result<&[u8]> {
+        let node = self.cell(offset)?;
+        if !node.starts_with(b"nk") {
+            return Err(invalid("expected a key node"));
+        }
+        Ok(node)
+    }
+
+    /// Finds the subkey named `name` of the key node `node`.
+    fn subkey(&self, node: &[u8], name: &str) -> io::Result<Option<u32>> {
+        let count = u32_at(node, 0x14)?;
+        if count == 0 {
+            return Ok(None);
+        }
+        let list = u32_at(node, 0x1C)?;
+        self.find_in_index(list, name, 0)
+    }
+
+    /// Finds the key named `name` in the subkey index list at `offset` ("lf", "lh", "li" or
+    /// "ri" record).
+    fn find_in_index(&self, offset: u32, name: &str, depth: usize) -> io::Result<Option<u32>> {
+        let list = self.cell(offset)?;
+        let count = usize::from(u16_at(list, 2)?);
+        let stride = match &list[..2] {
+            b"lf" | b"lh" => 8,
+            b"li" | b"ri" => 4,
+            _ => return Err(invalid("unknown subkey list")),
+        };
+
+        for index in 0..count {
+            let element = u32_at(list, 4 + index * stride)?;
+            if &list[..2] == b"ri" {
+                if depth >= MAX_INDEX_DEPTH {
+                    return Err(invalid("subkey lists nested too deeply"));
+                }
+                if let Some(found) = self.find_in_index(element, name, depth + 1)? {
+                    return Ok(Some(found));
+                }
+            } else if key_name(self.key_node(element)?)?.eq_ignore_ascii_case(name) {
+                return Ok(Some(element));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reads the values of the key node `node`.
+    fn values(&self, node: &[u8]) -> io::Result<RegistryKey> {
+        let count = u32_at(node, 0x24)? as usize;
+        if count == 0 {
+            return Ok(RegistryKey::default());
+        }
+        let list = self.cell(u32_at(node, 0x28)?)?;
+
+        let mut values = Vec::with_capacity(count.min(list.len() / 4));
+        for index in 0..count {
+            let value = self.cell(u32_at(list, index * 4)?)?;
+            if !value.starts_with(b"vk") {
+                return Err(invalid("expected a value"));
+            }
+            let name_length = usize::from(u16_at(value, 0x02)?);
+            let size = u32_at(value, 0x04)?;
+            let data_offset = u32_at(value, 0x08)?;
+            let vtype = u32_at(value, 0x0C)?;
+            let flags = u16_at(value, 0x10)?;
+            let name = value.get(0x14..0x14 + name_length).ok_or_else(|| invalid("truncated value name"))?;
+
+            let data = if size & DATA_INLINE != 0 {
+                let size = (size & !DATA_INLINE).min(4) as usize;
+                value[0x08..0x08 + size].to_vec()
+            } else {
+                let cell = self.cell(data_offset)?;
+                match cell.get(..size as usize) {
+                    Some(data) if !cell.starts_with(b"db") => data.to_vec(),
+                    // Big data record or truncated data.
+                    _ => continue,
+                }
+            };
+            values.push((decode_name(name, flags & VALUE_COMP_NAME != 0), RegistryValue::from_raw(vtype, &data)));
+        }
+        Ok(RegistryKey::new(values))
+    }
+}
+
+impl RegistryProvider for HiveRegistry {
+    fn read_key(&self, path: &str) -> io::Result<RegistryKey> {
+        let not_found = || io::Error::new(io::ErrorKind::NotFound, format!("no key {path}"));
+        let mut components = path.split('\\').filter(|component| !component.is_empty());
+        if !components.next().is_some_and(|first| first.eq_ignore_ascii_case(&self.mount_point)) {
+            return Err(not_found());
+        }
+
+        let mut node = self.key_node(u32_at(&self.data, ROOT_CELL_OFFSET)?)?;
+        for component in components {
+            let offset = self.subkey(node, component)?.ok_or_else(not_found)?;
+            node = self.key_node(offset)?;
+        }
+        self.values(node)
+    }
+
+    fn source(&self, path: &str) -> String {
+        let relative = path
+            .split_once('\\')
+            .map_or("", |(_, relative)| relative);
+        format!("{}:{relative}", self.source)
+    }
+}
+
+fn key_name(node: &[u8]) -> io::Result<String> {
+    let flags = u16_at(node, 0x02)?;
+    let length = usize::from(u16_at(node, 0x48)?);
+    let name = node.get(0x4C..0x4C + length).ok_or_else(|| invalid("truncated key name"))?;
+    Ok(decode_name(name, flags & KEY_COMP_NAME != 0))
+}
+
+fn u16_at(data: &[u8], offset: usize) -> io::Result<u16> {
+    let bytes = offset
+        .checked_add(2)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| invalid("truncated record"))?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> io::Result<u32> {
+    let bytes = offset
+        .checked_add(4)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| invalid("truncated record"))?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Decodes a key or value name, stored as Latin-1 or UTF-16LE.
+fn decode_name(name: &[u8], latin1: bool) -> String {
+    if latin1 {
+        name.iter().map(|&b| char::from(b)).collect()
+    } else {
+        let units: Vec<u16> = name.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        String::from_utf16_lossy(&units)
+    }
+}
+
+fn invalid(error: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid registry hive: {error}"))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// Builds a hive file containing the given keys, as paths relative to the hive root, and
+    /// their string and `REG_DWORD` values.
+    pub(crate) fn hive_with(keys: &[(&str, &[(&str, RegistryValue)])]) -> Vec<u8> {
+        let mut cells = Cells::default();
+        // Path, cell offset, children and values of each key, starting with the root.
+        let mut nodes = vec![(String::new(), 0, Vec::new(), Vec::new())];
+
+        for &(path, values) in keys {
+            let mut parent = 0;
+            let mut prefix = String::new();
+            for component in path.split('\\') {
+                prefix = if prefix.is_empty() { component.to_string() } else { format!("{prefix}\\{component}") };
+                parent = match nodes.iter().position(|(p, ..)| *p == prefix) {
+                    Some(index) => index,
+                    None => {
+                        let child = nodes.len();
+                        nodes.push((prefix.clone(), 0, Vec::new(), Vec::new()));
+                        nodes[parent].2.push(child);
+                        child
+                    }
+                };
+            }
+            nodes[parent].3 = values.to_vec();
+        }
+
+        // Children before parents, so that subkey lists can refer to them.
+        for index in (0..nodes.len()).rev() {
+            let name = nodes[index].0.rsplit('\\').next().unwrap().to_string();
+            let children: Vec<u32> = nodes[index].2.iter().map(|&child| nodes[child].1).collect();
+            let values = nodes[index].3.clone();
+            nodes[index].1 = cells.key(if index == 0 { "ROOT" } else { &name }, &children, &values);
+        }
+
+        let mut hive = vec![0; BASE_BLOCK_SIZE];
+        hive[..4].copy_from_slice(b"regf");
+        hive[ROOT_CELL_OFFSET..ROOT_CELL_OFFSET + 4].copy_from_slice(&nodes[0].1.to_le_bytes());
+        hive.extend_from_slice(&cells.data);
+        hive
+    }
+
+    #[derive(Default)]
+    struct Cells {
+        data: Vec<u8>,
+    }
+
+    impl Cells {
+        fn push(&mut self, content: &[u8]) -> u32 {
+            let offset = self.data.len() as u32;
+            let size = (content.len() + 4).next_multiple_of(8);
+            self.data.extend_from_slice(&(-(size as i32)).to_le_bytes());
+            self.data.extend_from_slice(content);
+            self.data.resize(offset as usize + size, 0);
+            offset
+        }
+
+        fn key(&mut self, name: &str, children: &[u32], values: &[(&str, RegistryValue)]) -> u32 {
+            let subkeys = if children.is_empty() {
+                u32::MAX
+            } else {
+                let mut list = b"lf".to_vec();
+                list.extend_from_slice(&(children.len() as u16).to_le_bytes());
+                for child in children {
+                    list.extend_from_slice(&child.to_le_bytes());
+                    list.extend_from_slice(&[0; 4]);
+                }
+                self.push(&list)
+            };
+
+            let value_offsets: Vec<u8> = values
+                .iter()
+                .flat_map(|(name, value)| self.value(name, value).to_le_bytes())
+                .collect();
+            let value_list = if values.is_empty() { u32::MAX } else { self.push(&value_offsets) };
+
+            let mut node = vec![0; 0x4C];
+            node[..2].copy_from_slice(b"nk");
+            node[0x02..0x04].copy_from_slice(&KEY_COMP_NAME.to_le_bytes());
+            node[0x14..0x18].copy_from_slice(&(children.len() as u32).to_le_bytes());
+            node[0x1C..0x20].copy_from_slice(&subkeys.to_le_bytes());
+            node[0x24..0x28].copy_from_slice(&(values.len() as u32).to_le_bytes());
+            node[0x28..0x2C].copy_from_slice(&value_list.to_le_bytes());
+            node[0x48..0x4A].copy_from_slice(&(name.len() as u16).to_le_bytes());
+            node.extend_from_slice(name.as_bytes());
+            self.push(&node)
+        }
+
+        fn value(&mut self, name: &str, value: &RegistryValue) -> u32 {
+            let (vtype, data): (u32, Vec<u8>) = match value {
+                RegistryValue::String(s) => (1, s.encode_utf16().chain([0]).flat_map(u16::to_le_bytes).collect()),
+                RegistryValue::MultiString(strings) => {
+                    let units = strings.iter().flat_map(|s| s.encode_utf16().chain([0])).chain([0]);
+                    (7, units.flat_map(u16::to_le_bytes).collect())
+                }
+                RegistryValue::Dword(n) => (4, n.to_le_bytes().to_vec()),
+                RegistryValue::Qword(n) => (11, n.to_le_bytes().to_vec()),
+                RegistryValue::Binary(vtype, data) => (*vtype, data.clone()),
+            };
+
+            let mut record = vec![0; 0x14];
+            record[..2].copy_from_slice(b"vk");
+            record[0x02..0x04].copy_from_slice(&(name.len() as u16).to_le_bytes());
+            if data.len() <= 4 {
+                record[0x04..0x08].copy_from_slice(&(data.len() as u32 | DATA_INLINE).to_le_bytes());
+                record[0x08..0x08 + data.len()].copy_from_slice(&data);
+            } else {
+                let offset = self.push(&data);
+                record[0x04..0x08].copy_from_slice(&(data.len() as u32).to_le_bytes());
+                record[0x08..0x0C].copy_from_slice(&offset.to_le_bytes());
+            }
+            record[0x0C..0x10].copy_from_slice(&vtype.to_le_bytes());
+            record[0x10..0x12].copy_from_slice(&VALUE_COMP_NAME.to_le_bytes());
+            record.extend_from_slice(name.as_bytes());
+            self.push(&record)
+        }
+    }
+
+    #[test]
+    fn read_keys() {
+        let data = hive_with(&[
+            (
+                "Microsoft\\Windows NT\\CurrentVersion",
+                &[
+                    ("EditionID", RegistryValue::String(String::from("Professional"))),
+                    ("UBR", RegistryValue::Dword(3296)),
+                ],
+            ),
+            ("Microsoft\\Windows\\CurrentVersion", &[("ProgramFilesDir", RegistryValue::String(String::from("C:\\Program Files")))]),
+        ]);
+        let hive = HiveRegistry::from_bytes(data, "SOFTWARE").unwrap();
+
+        let key = hive.read_key("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion").unwrap();
+        assert_eq!(Some("Professional"), key.string("EditionID"));
+        assert_eq!(Some(3296), key.dword("ubr"));
+
+        let key = hive.read_key("software\\microsoft\\windows\\currentversion").unwrap();
+        assert_eq!(Some("C:\\Program Files"), key.string("ProgramFilesDir"));
+
+        assert_eq!(RegistryKey::default(), hive.read_key("SOFTWARE\\Microsoft").unwrap());
+        for missing in ["SOFTWARE\\Microsoft\\Office", "SYSTEM\\Setup", ""] {
+            assert_eq!(io::ErrorKind::NotFound, hive.read_key(missing).unwrap_err().kind());
+        }
+        assert_eq!("hive:Microsoft\\Windows NT", hive.source("SOFTWARE\\Microsoft\\Windows NT"));
+    }
+
+    #[test]
+    fn value_types() {
+        let values = [
+            ("Languages", RegistryValue::MultiString(vec![String::from("en-US"), String::from("de-DE")])),
+            ("InstallTime", RegistryValue::Qword(133_497_216_000_000_000)),
+            ("Flags", RegistryValue::Binary(3, vec![1, 2, 3])),
+            ("DigitalProductId", RegistryValue::Binary(3, (0..=20).collect())),
+        ];
+        let hive = HiveRegistry::from_bytes(hive_with(&[("Setup", &values)]), "SYSTEM").unwrap();
+
+        let key = hive.read_key("SYSTEM\\Setup").unwrap();
+        for (name, value) in &values {
+            assert_eq!(Some(value), key.value(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn invalid_hives() {
+        assert_eq!(
+            io::ErrorKind::InvalidData,
+            HiveRegistry::from_bytes(b"regf".to_vec(), "SOFTWARE").unwrap_err().kind()
+        );
+
+        let mut data = hive_with(&[]);
+        // Root cell pointing past the end of the file.
+        data[ROOT_CELL_OFFSET..ROOT_CELL_OFFSET + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        let hive = HiveRegistry::from_bytes(data, "SOFTWARE").unwrap();
+        assert_eq!(io::ErrorKind::InvalidData, hive.read_key("SOFTWARE").unwrap_err().kind());
+
+        // Offsets overflowing `usize` are out of bounds rather than wrapping around.
+        for offset in [u32::MAX, u32::MAX - 4, 0xFFFF_F000] {
+            assert_eq!(io::ErrorKind::InvalidData, hive.cell(offset).unwrap_err().kind());
+        }
+        assert_eq!(io::ErrorKind::InvalidData, u32_at(&hive.data, usize::MAX - 1).unwrap_err().kind());
+        assert_eq!(io::ErrorKind::InvalidData, u16_at(&hive.data, usize::MAX).unwrap_err().kind());
+    }
+}
@@ -0,0 +1,73 @@
+#![allow(unsafe_code)]
+use crate::logging::trace;
+
+use super::registry::{LiveRegistry, RegistryProvider};
+
+const LANGUAGE_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\Nls\\Language";
+/// Maximum length of a locale name, including the terminating NUL.
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn GetSystemDefaultUILanguage() -> u16;
+    fn GetSystemDefaultLocaleName(name: *mut u16, size: i32) -> i32;
+    fn LCIDToLocaleName(locale: u32, name: *mut u16, size: i32, flags: u32) -> i32;
+}
+
+/// Returns the language Windows was installed in, e.g. "de-DE", which stays the same when
+/// language packs are added later.
+pub fn install_language() -> Option<String> {
+    // A hexadecimal LCID, e.g. "0407".
+    let lcid = LiveRegistry
+        .read_key(LANGUAGE_KEY)
+        .ok()
+        .and_then(|key| u32::from_str_radix(key.string("InstallLanguage")?.trim(), 16).ok());
+
+    let lcid = lcid.unwrap_or_else(|| {
+        trace!("InstallLanguage not found, using the system default UI language");
+        // SAFETY: GetSystemDefaultUILanguage has no preconditions.
+        u32::from(unsafe { GetSystemDefaultUILanguage() })
+    });
+    locale_name(lcid)
+}
+
+/// Returns the system locale (the "language for non-Unicode programs"), e.g. "en-US".
+pub fn system_locale() -> Option<String> {
+    let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+    // SAFETY: `name` is a valid, writable buffer of the given length.
+    let length = unsafe { GetSystemDefaultLocaleName(name.as_mut_ptr(), name.len() as i32) };
+    from_wide(&name, length)
+}
+
+/// Converts the locale identifier to its name, e.g. 0x0409 to "en-US".
+fn locale_name(lcid: u32) -> Option<String> {
+    let mut name = [0u16; LOCALE_NAME_MAX_LENGTH];
+    // SAFETY: `name` is a valid, writable buffer of the given length.
+    let length = unsafe { LCIDToLocaleName(lcid, name.as_mut_ptr(), name.len() as i32, 0) };
+    from_wide(&name, length)
+}
+
+/// Converts the name returned by the locale functions, whose length includes the terminating
+/// NUL and is 0 on failure.
+fn from_wide(name: &[u16], length: i32) -> Option<String> {
+    let length = usize::try_from(length).ok().filter(|&length| length > 1)?;
+    String::from_utf16(&name[..length - 1]).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn wide_name() {
+        let name = [0x65, 0x6E, 0x2D, 0x55, 0x53, 0, 0];
+        assert_eq!(Some(String::from("en-US")), from_wide(&name, 6));
+        assert_eq!(None, from_wide(&name, 0));
+    }
+
+    #[test]
+    fn english() {
+        assert_eq!(Some(String::from("en-US")), locale_name(0x0409));
+    }
+}
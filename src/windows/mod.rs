@@ -1,25 +1,224 @@
+//! Windows specific information.
+//!
+//! The registry is read through the `RegistryProvider` trait, so an offline hive, e.g. of a
+//! mounted Windows image, can be inspected on every platform (see `get_from_hive`).
+
+// Reading the registry and the product tables are platform-independent; the other modules call
+// the Windows API.
+mod current_version;
+mod hive;
+mod package;
+mod product;
+mod registry;
+mod update;
+
+#[cfg(windows)]
 mod api;
+#[cfg(windows)]
+mod boot;
+#[cfg(windows)]
+mod display;
+#[cfg(windows)]
+mod firmware;
+#[cfg(windows)]
+mod language;
+#[cfg(windows)]
+mod network;
+#[cfg(windows)]
+mod reboot;
+#[cfg(windows)]
+mod security;
+#[cfg(windows)]
+mod session;
+#[cfg(windows)]
+mod user;
+
+pub use hive::HiveRegistry;
+pub use package::PackageContext;
+pub use registry::{MemoryRegistry, RegistryKey, RegistryProvider, RegistryValue};
+pub use update::{CumulativeUpdate, UPDATE_TABLE_DATE, UpdateLevel, UpdateStatus, update_level_from};
 
-use log::trace;
+#[cfg(windows)]
+pub use package::package_context;
+#[cfg(windows)]
+pub use update::update_level;
+
+use std::{io, path::Path};
+
+#[cfg(windows)]
+use crate::logging::trace;
 
 use crate::OSInfo;
+#[cfg(windows)]
+use crate::{Cgroups, Config, Deployment, Diagnostic, DisplayServer, Firmware, LibcInfo, LibcKind, NetworkEnv, Sandbox, SecurityProfile, SessionKind, Version, boot::BootStatus};
+
+/// Returns the Windows release described by `registry`. The product type (SKU) is unknown, so
+/// the name is the localized `ProductName`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::windows::{MemoryRegistry, RegistryKey, RegistryValue};
+///
+/// let registry = MemoryRegistry::new().with_key(
+///     "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion",
+///     RegistryKey::new([("DisplayVersion", RegistryValue::String("23H2".to_owned()))]),
+/// );
+/// let info = osinfo::windows::get_from_registry(&registry);
+/// assert_eq!(Some("23H2"), info.codename());
+/// ```
+pub fn get_from_registry(registry: &dyn RegistryProvider) -> OSInfo {
+    current_version::get_os_data(registry, |_| None, &mut Vec::new())
+}
+
+/// Returns the Windows release of an offline image from its `SOFTWARE` hive file, e.g.
+/// `Windows/System32/config/SOFTWARE` of a mounted disk image.
+///
+/// # Examples
+///
+/// ```no_run
+/// let info = osinfo::windows::get_from_hive("/mnt/image/Windows/System32/config/SOFTWARE")?;
+/// println!("Image OS information: {info}");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn get_from_hive(path: impl AsRef<Path>) -> io::Result<OSInfo> {
+    Ok(get_from_registry(&HiveRegistry::open(path, "SOFTWARE")?))
+}
 
-pub fn get_info() -> OSInfo {
+/// Reads the key at `path` of the live registry, relative to `HKEY_LOCAL_MACHINE`.
+#[cfg(windows)]
+pub(crate) fn read_live_key(path: &str) -> io::Result<RegistryKey> {
+    registry::LiveRegistry.read_key(path)
+}
+
+#[cfg(windows)]
+pub(crate) fn get_info(_config: &Config, diagnostics: &mut Vec<Diagnostic>) -> OSInfo {
     trace!("windows::get_info is called");
-    let info = api::get_os_data();
+    let info = api::get_os_data(diagnostics);
     trace!("Returning {:?}", info);
     info
 }
 
-#[cfg(test)]
+#[cfg(windows)]
+pub(crate) fn get_kernel_version() -> Option<Version> {
+    // The NT kernel version is the operating system version; there is no separate userland.
+    None
+}
+
+#[cfg(windows)]
+pub(crate) fn get_boot_status() -> BootStatus {
+    trace!("windows::get_boot_status is called");
+    BootStatus {
+        secure_boot: boot::secure_boot(),
+        ..Default::default()
+    }
+}
+
+#[cfg(windows)]
+pub(crate) fn get_firmware() -> Firmware {
+    trace!("windows::get_firmware is called");
+    firmware::get_firmware()
+}
+
+#[cfg(windows)]
+pub(crate) fn fips_enabled() -> Option<bool> {
+    trace!("windows::fips_enabled is called");
+    api::fips_enabled()
+}
+
+#[cfg(windows)]
+pub(crate) fn get_deployment() -> Option<Deployment> {
+    // Windows has no image-based deployments.
+    None
+}
+
+#[cfg(windows)]
+pub(crate) fn install_language() -> Option<String> {
+    trace!("windows::install_language is called");
+    language::install_language()
+}
+
+#[cfg(windows)]
+pub(crate) fn system_locale() -> Option<String> {
+    trace!("windows::system_locale is called");
+    language::system_locale()
+}
+
+#[cfg(windows)]
+pub(crate) fn pending_reboot() -> Option<bool> {
+    trace!("windows::pending_reboot is called");
+    reboot::pending_reboot()
+}
+
+#[cfg(windows)]
+pub(crate) fn get_session_kind() -> SessionKind {
+    trace!("windows::get_session_kind is called");
+    session::get_session_kind()
+}
+
+#[cfg(windows)]
+pub(crate) fn network_env() -> NetworkEnv {
+    trace!("windows::network_env is called");
+    network::network_env()
+}
+
+#[cfg(windows)]
+pub(crate) fn get_display_server() -> DisplayServer {
+    trace!("windows::get_display_server is called");
+    display::get_display_server(session::get_session_kind())
+}
+
+#[cfg(windows)]
+pub(crate) fn is_elevated() -> bool {
+    trace!("windows::is_elevated is called");
+    user::is_elevated()
+}
+
+#[cfg(windows)]
+pub(crate) fn current_user() -> Option<String> {
+    trace!("windows::current_user is called");
+    user::current_user()
+}
+
+#[cfg(windows)]
+pub(crate) fn get_sandbox(_config: &Config) -> Option<Sandbox> {
+    // MSIX packages see the host's version, see `package_context` for their identity.
+    None
+}
+
+#[cfg(windows)]
+pub(crate) fn get_cgroups() -> Option<Cgroups> {
+    // Job objects limit processes instead, which aren't reported.
+    None
+}
+
+#[cfg(windows)]
+pub(crate) fn get_libc() -> Option<LibcInfo> {
+    // The Universal CRT is part of the operating system since Windows 10 and versioned with it.
+    Some(LibcInfo {
+        kind: LibcKind::Msvcrt,
+        version: Version::Unknown,
+    })
+}
+
+#[cfg(windows)]
+pub(crate) fn get_security_profile() -> SecurityProfile {
+    trace!("windows::get_security_profile is called");
+    SecurityProfile {
+        security_processor: security::get_security_processor(),
+        disk_encryption: security::get_disk_encryption(),
+    }
+}
+
+#[cfg(all(test, windows))]
 mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
     #[test]
     fn windows() {
-        let info = api::get_os_data();
-        assert_eq!(String::from("windows"), info.get_id());
-        assert!(info.get_name().contains("Windows"));
+        let info = api::get_os_data(&mut Vec::new());
+        assert_eq!("windows", info.id());
+        assert!(info.name().contains("Windows"));
     }
 }
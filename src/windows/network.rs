@@ -0,0 +1,150 @@
+use std::env;
+
+use crate::logging::trace;
+
+use super::registry::{LiveRegistry, RegistryProvider, RegistryValue, read_current_user_key};
+use crate::{NetworkEnv, network::split_hosts};
+
+/// The WinINET settings of the current user, used by browsers and most applications.
+const INTERNET_SETTINGS_KEY: &str = "Software\\Microsoft\\Windows\\CurrentVersion\\Internet Settings";
+/// The machine-wide WinHTTP settings (`netsh winhttp set proxy`), used by services.
+const WINHTTP_CONNECTIONS_KEY: &str = "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Internet Settings\\Connections";
+/// `WINHTTP_ACCESS_TYPE_NAMED_PROXY` flag of the WinHTTP settings.
+const WINHTTP_NAMED_PROXY: u32 = 0x02;
+
+/// Returns the proxy configuration from the environment, the WinINET settings of the current
+/// user or the WinHTTP settings, in this order.
+pub fn network_env() -> NetworkEnv {
+    let from_env = NetworkEnv::from_env(|name| env::var(name).ok());
+    if !from_env.is_direct() {
+        return from_env;
+    }
+
+    let wininet = wininet_settings();
+    if !wininet.is_direct() {
+        return wininet;
+    }
+
+    match LiveRegistry.read_key(WINHTTP_CONNECTIONS_KEY) {
+        Ok(key) => match key.value("WinHttpSettings") {
+            Some(RegistryValue::Binary(_, bytes)) => parse_winhttp_settings(bytes).unwrap_or_default(),
+            _ => NetworkEnv::default(),
+        },
+        Err(e) => {
+            trace!("Unable to read the WinHTTP settings: {:?}", e);
+            NetworkEnv::default()
+        }
+    }
+}
+
+fn wininet_settings() -> NetworkEnv {
+    let settings = match read_current_user_key(INTERNET_SETTINGS_KEY) {
+        Ok(settings) => settings,
+        Err(e) => {
+            trace!("Unable to open {}: {:?}", INTERNET_SETTINGS_KEY, e);
+            return NetworkEnv::default();
+        }
+    };
+
+    let auto_config_url = settings.string("AutoConfigURL").filter(|url| !url.is_empty()).map(String::from);
+    if settings.dword("ProxyEnable").unwrap_or_default() != 1 {
+        return NetworkEnv {
+            auto_config_url,
+            ..Default::default()
+        };
+    }
+
+    let server = settings.string("ProxyServer").unwrap_or_default();
+    let bypass = settings.string("ProxyOverride").unwrap_or_default();
+    NetworkEnv {
+        auto_config_url,
+        ..parse_proxy_settings(server, bypass)
+    }
+}
+
+/// Parses a proxy server list, either a single proxy for all protocols ("proxy:8080") or one
+/// per protocol ("http=proxy:8080;https=proxy:8443"), and a bypass list separated by semicolons
+/// or whitespace ("<local>;*.example.com").
+fn parse_proxy_settings(server: &str, bypass: &str) -> NetworkEnv {
+    let with_scheme = |proxy: &str| {
+        let proxy = proxy.trim();
+        (!proxy.is_empty()).then(|| if proxy.contains("://") { proxy.to_string() } else { format!("http://{proxy}") })
+    };
+    let for_protocol = |protocol: &str| {
+        server.split(';').find_map(|entry| {
+            let (name, proxy) = entry.split_once('=')?;
+            name.trim().eq_ignore_ascii_case(protocol).then(|| with_scheme(proxy)).flatten()
+        })
+    };
+
+    let (http_proxy, https_proxy) = if server.contains('=') {
+        (for_protocol("http"), for_protocol("https"))
+    } else {
+        (with_scheme(server), with_scheme(server))
+    };
+
+    NetworkEnv {
+        http_proxy,
+        https_proxy,
+        no_proxy: split_hosts(&bypass.replace(char::is_whitespace, ";"), ';'),
+        auto_config_url: None,
+    }
+}
+
+/// Parses the `WinHttpSettings` value: a structure version, a change counter, the access type
+/// flags, then the length-prefixed proxy server list and bypass list.
+fn parse_winhttp_settings(bytes: &[u8]) -> Option<NetworkEnv> {
+    let dword = |offset: usize| Some(u32::from_le_bytes(bytes.get(offset..offset + 4)?.try_into().ok()?));
+    let string = |offset: usize| {
+        let length = dword(offset)? as usize;
+        let string = bytes.get(offset + 4..offset + 4 + length)?;
+        Some((String::from_utf8_lossy(string).into_owned(), offset + 4 + length))
+    };
+
+    if dword(8)? & WINHTTP_NAMED_PROXY == 0 {
+        return Some(NetworkEnv::default());
+    }
+    let (server, next) = string(12)?;
+    let (bypass, _) = string(next).unwrap_or_default();
+    Some(parse_proxy_settings(&server, &bypass))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn proxy_settings() {
+        let expected = NetworkEnv {
+            http_proxy: Some(String::from("http://proxy.example.com:8080")),
+            https_proxy: Some(String::from("http://proxy.example.com:8443")),
+            no_proxy: vec![String::from("<local>"), String::from("*.example.com")],
+            auto_config_url: None,
+        };
+        let parsed = parse_proxy_settings("http=proxy.example.com:8080;https=proxy.example.com:8443", "<local>;*.example.com");
+        assert_eq!(expected, parsed);
+
+        let parsed = parse_proxy_settings("proxy.example.com:8080", "");
+        assert_eq!(Some(String::from("http://proxy.example.com:8080")), parsed.https_proxy);
+    }
+
+    #[test]
+    fn winhttp_settings() {
+        let mut bytes = vec![0x28, 0, 0, 0, 5, 0, 0, 0, 3, 0, 0, 0];
+        bytes.extend_from_slice(&(16u32).to_le_bytes());
+        bytes.extend_from_slice(b"proxy.corp:3128 ");
+        bytes.extend_from_slice(&(7u32).to_le_bytes());
+        bytes.extend_from_slice(b"<local>");
+
+        let expected = NetworkEnv {
+            http_proxy: Some(String::from("http://proxy.corp:3128")),
+            https_proxy: Some(String::from("http://proxy.corp:3128")),
+            no_proxy: vec![String::from("<local>")],
+            auto_config_url: None,
+        };
+        assert_eq!(Some(expected), parse_winhttp_settings(&bytes));
+        assert_eq!(Some(NetworkEnv::default()), parse_winhttp_settings(&[0x28, 0, 0, 0, 1, 0, 0, 0, 1, 0, 0, 0]));
+        assert_eq!(None, parse_winhttp_settings(&[0x28]));
+    }
+}
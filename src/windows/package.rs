@@ -0,0 +1,122 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::Version;
+
+/// Identity of the MSIX (or APPX) package the process runs in.
+///
+/// Packaged applications see a virtualized view of parts of the registry and the file system,
+/// e.g. their writes to `HKEY_CURRENT_USER\Software` and `%AppData%` are redirected, so values
+/// the detection reads may differ from what unpackaged processes see.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PackageContext {
+    /// Package full name, e.g. "Microsoft.WindowsTerminal_1.19.10573.0_x64__8wekyb3d8bbwe".
+    pub full_name: String,
+    /// Package name, e.g. "Microsoft.WindowsTerminal".
+    pub name: String,
+    /// Package version, e.g. 1.19.10573.0.
+    pub version: Version,
+    /// Processor architecture of the package: "x86", "x64", "arm", "arm64" or "neutral".
+    pub architecture: String,
+    /// Resource ID, which is empty for most packages.
+    pub resource_id: String,
+    /// Hash of the publisher, e.g. "8wekyb3d8bbwe" for Microsoft.
+    pub publisher_id: String,
+    /// Whether the process runs in an AppContainer, the sandbox of UWP applications, rather
+    /// than with the full rights of the user (packaged desktop applications).
+    pub app_container: bool,
+}
+
+impl PackageContext {
+    /// Parses a package full name, "Name_Version_Architecture_ResourceId_PublisherId". The
+    /// AppContainer isn't part of it and is left `false`.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::{Version, windows::PackageContext};
+    ///
+    /// let package = PackageContext::from_full_name("Microsoft.WindowsTerminal_1.19.10573.0_x64__8wekyb3d8bbwe").unwrap();
+    /// assert_eq!("Microsoft.WindowsTerminal", package.name);
+    /// assert_eq!(Version::Semantic(1, 19, 10573, 0), package.version);
+    /// assert_eq!("Microsoft.WindowsTerminal_8wekyb3d8bbwe", package.family_name());
+    /// ```
+    pub fn from_full_name(full_name: &str) -> Option<Self> {
+        let parts: Vec<&str> = full_name.split('_').collect();
+        let [name, version, architecture, resource_id, publisher_id] = parts[..] else {
+            return None;
+        };
+        if name.is_empty() || publisher_id.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            full_name: full_name.to_string(),
+            name: name.to_string(),
+            version: Version::from_string(version),
+            architecture: architecture.to_string(),
+            resource_id: resource_id.to_string(),
+            publisher_id: publisher_id.to_string(),
+            app_container: false,
+        })
+    }
+
+    /// Returns the package family name, "Name_PublisherId", which identifies the application
+    /// across versions and architectures.
+    pub fn family_name(&self) -> String {
+        format!("{}_{}", self.name, self.publisher_id)
+    }
+}
+
+/// Returns the MSIX package the process runs in, or `None` if it isn't packaged, e.g. to warn
+/// that the registry and file system views are virtualized.
+///
+/// # Examples
+///
+/// ```
+/// if let Some(package) = osinfo::windows::package_context() {
+///     println!("Running in package {} (AppContainer: {})", package.family_name(), package.app_container);
+/// }
+/// ```
+#[cfg(windows)]
+pub fn package_context() -> Option<PackageContext> {
+    let full_name = super::api::current_package_full_name()?;
+    let package = PackageContext::from_full_name(&full_name);
+    if package.is_none() {
+        crate::logging::trace!("Unexpected package full name {:?}", full_name);
+    }
+    Some(PackageContext {
+        app_container: super::user::is_app_container(),
+        ..package?
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn full_names() {
+        let package = PackageContext::from_full_name("Microsoft.WindowsCalculator_11.2401.0.0_x64__8wekyb3d8bbwe").unwrap();
+        assert_eq!(
+            PackageContext {
+                full_name: String::from("Microsoft.WindowsCalculator_11.2401.0.0_x64__8wekyb3d8bbwe"),
+                name: String::from("Microsoft.WindowsCalculator"),
+                version: Version::Semantic(11, 2401, 0, 0),
+                architecture: String::from("x64"),
+                resource_id: String::new(),
+                publisher_id: String::from("8wekyb3d8bbwe"),
+                app_container: false,
+            },
+            package
+        );
+
+        let resource = PackageContext::from_full_name("Microsoft.LanguageExperiencePackfr-FR_22621.1.2.0_neutral_fr-FR_8wekyb3d8bbwe").unwrap();
+        assert_eq!("fr-FR", resource.resource_id);
+        assert_eq!("Microsoft.LanguageExperiencePackfr-FR_8wekyb3d8bbwe", resource.family_name());
+
+        assert_eq!(None, PackageContext::from_full_name("Microsoft.WindowsTerminal_8wekyb3d8bbwe"));
+        assert_eq!(None, PackageContext::from_full_name(""));
+    }
+}
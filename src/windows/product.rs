@@ -0,0 +1,162 @@
+//! Locale-neutral Windows product names, derived from the build number and the product type
+//! (SKU) rather than the localized `ProductName` registry value.
+
+use crate::{Version, lookup};
+
+/// Windows 11 reports itself as version 10.0 too; it starts with this build.
+const WINDOWS_11_BUILD: u32 = 22000;
+
+/// Product types returned by `GetProductInfo` and the edition names used in product names.
+static EDITIONS: [(u32, &str); 37] = [
+    (0x04, "Enterprise"),                // PRODUCT_ENTERPRISE
+    (0x07, "Standard"),                  // PRODUCT_STANDARD_SERVER
+    (0x08, "Datacenter"),                // PRODUCT_DATACENTER_SERVER
+    (0x0A, "Enterprise"),                // PRODUCT_ENTERPRISE_SERVER
+    (0x0C, "Datacenter"),                // PRODUCT_DATACENTER_SERVER_CORE
+    (0x0D, "Standard"),                  // PRODUCT_STANDARD_SERVER_CORE
+    (0x11, "Web Server"),                // PRODUCT_WEB_SERVER
+    (0x1B, "Enterprise N"),              // PRODUCT_ENTERPRISE_N
+    (0x41, "Embedded Standard"),         // PRODUCT_EMBEDDED
+    (0x59, "Embedded Industry"),         // PRODUCT_EMBEDDED_INDUSTRY
+    (0x30, "Pro"),                       // PRODUCT_PROFESSIONAL
+    (0x31, "Pro N"),                     // PRODUCT_PROFESSIONAL_N
+    (0x48, "Enterprise Evaluation"),     // PRODUCT_ENTERPRISE_EVALUATION
+    (0x4F, "Standard Evaluation"),       // PRODUCT_STANDARD_EVALUATION_SERVER
+    (0x50, "Datacenter Evaluation"),     // PRODUCT_DATACENTER_EVALUATION_SERVER
+    (0x62, "Home N"),                    // PRODUCT_CORE_N
+    (0x63, "Home China"),                // PRODUCT_CORE_COUNTRYSPECIFIC
+    (0x64, "Home Single Language"),      // PRODUCT_CORE_SINGLELANGUAGE
+    (0x65, "Home"),                      // PRODUCT_CORE
+    (0x77, "Team"),                      // PRODUCT_PPI_PRO
+    (0x79, "Education"),                 // PRODUCT_EDUCATION
+    (0x7A, "Education N"),               // PRODUCT_EDUCATION_N
+    (0x7B, "IoT Core"),                  // PRODUCT_IOTUAP
+    (0x7D, "Enterprise LTSC"),           // PRODUCT_ENTERPRISE_S
+    (0x7E, "Enterprise N LTSC"),         // PRODUCT_ENTERPRISE_S_N
+    (0x83, "IoT Core Commercial"),       // PRODUCT_IOTUAPCOMMERCIAL
+    (0x87, "Holographic"),               // PRODUCT_HOLOGRAPHIC
+    (0x88, "Holographic for Business"),  // PRODUCT_HOLOGRAPHIC_BUSINESS
+    (0x91, "Datacenter"),                // PRODUCT_DATACENTER_A_SERVER_CORE
+    (0x92, "Standard"),                  // PRODUCT_STANDARD_A_SERVER_CORE
+    (0xA1, "Pro for Workstations"),      // PRODUCT_PRO_WORKSTATION
+    (0xA2, "Pro for Workstations N"),    // PRODUCT_PRO_WORKSTATION_N
+    (0xA4, "Pro Education"),             // PRODUCT_PRO_FOR_EDUCATION
+    (0xA5, "Pro Education N"),           // PRODUCT_PRO_FOR_EDUCATION_N
+    (0xAF, "Enterprise multi-session"),  // PRODUCT_SERVERRDSH
+    (0xBC, "IoT Enterprise"),            // PRODUCT_IOTENTERPRISE
+    (0xBF, "IoT Enterprise LTSC"),       // PRODUCT_IOTENTERPRISES
+];
+
+/// Product types of device editions and their variants. These editions report "Client" (or
+/// "IoTUAP") as their installation type.
+static DEVICE_VARIANTS: [(u32, &str); 9] = [
+    (0x41, "Embedded"),    // PRODUCT_EMBEDDED
+    (0x59, "Embedded"),    // PRODUCT_EMBEDDED_INDUSTRY
+    (0x77, "Team"),        // PRODUCT_PPI_PRO
+    (0x7B, "IoT"),         // PRODUCT_IOTUAP
+    (0x83, "IoT"),         // PRODUCT_IOTUAPCOMMERCIAL
+    (0x87, "Holographic"), // PRODUCT_HOLOGRAPHIC
+    (0x88, "Holographic"), // PRODUCT_HOLOGRAPHIC_BUSINESS
+    (0xBC, "IoT"),         // PRODUCT_IOTENTERPRISE
+    (0xBF, "IoT"),         // PRODUCT_IOTENTERPRISES
+];
+
+/// Edition IDs (the `EditionID` registry value) of device editions and their variants, used when
+/// the product type isn't available.
+static DEVICE_EDITION_IDS: [(&str, &str); 4] = [
+    ("IoT", "IoT"),
+    ("Embedded", "Embedded"),
+    ("PPIPro", "Team"),
+    ("Holographic", "Holographic"),
+];
+
+/// Returns the variant of IoT, embedded, Surface Hub ("Team") and HoloLens ("Holographic")
+/// editions and the source it was derived from ("GetProductInfo" for the product type or
+/// "EditionID"), or `None` for desktop and server editions.
+pub fn device_variant(product_sku: Option<u32>, edition_id: Option<&str>) -> Option<(&'static str, &'static str)> {
+    if let Some(&(_, variant)) = DEVICE_VARIANTS.iter().find(|&&(sku, _)| Some(sku) == product_sku) {
+        return Some((variant, "GetProductInfo"));
+    }
+
+    let edition_id = edition_id?;
+    DEVICE_EDITION_IDS
+        .iter()
+        .find(|&&(prefix, _)| edition_id.starts_with(prefix))
+        .map(|&(_, variant)| (variant, "EditionID"))
+}
+
+/// Returns the locale-neutral product name, e.g. "Windows 11 Pro" or "Windows Server 2022
+/// Datacenter", or `None` if the release or the edition isn't known.
+pub fn neutral_name(version: &Version, product_sku: Option<u32>, installation_type: Option<&str>) -> Option<String> {
+    let Version::Semantic(10, 0, build, _) = *version else {
+        return None;
+    };
+    let edition = EDITIONS.iter().find(|&&(sku, _)| Some(sku) == product_sku)?.1;
+
+    // HoloLens runs Windows Holographic, which shares its build numbers with Windows 10.
+    if edition.starts_with("Holographic") {
+        return Some(format!("Windows {edition}"));
+    }
+
+    let product = if installation_type.is_some_and(|t| t.starts_with("Server")) {
+        // Servers keep the name of the release they are based on until the next one.
+        let release = lookup::windows_server_release(build)?;
+        format!("Windows Server {release}")
+    } else if build >= WINDOWS_11_BUILD {
+        String::from("Windows 11")
+    } else {
+        String::from("Windows 10")
+    };
+
+    Some(format!("{product} {edition}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn neutral_names() {
+        let data = [
+            (Version::Semantic(10, 0, 22631, 3296), Some(0x30), Some("Client"), Some("Windows 11 Pro")),
+            (Version::Semantic(10, 0, 19045, 4170), Some(0x65), Some("Client"), Some("Windows 10 Home")),
+            (Version::Semantic(10, 0, 17763, 5576), Some(0x7D), Some("Client"), Some("Windows 10 Enterprise LTSC")),
+            (Version::Semantic(10, 0, 20348, 2340), Some(0x08), Some("Server"), Some("Windows Server 2022 Datacenter")),
+            (Version::Semantic(10, 0, 17763, 5576), Some(0x0D), Some("Server Core"), Some("Windows Server 2019 Standard")),
+            (Version::Semantic(10, 0, 10240, 0), Some(0x07), Some("Server"), None),
+            (Version::Semantic(10, 0, 22631, 0), None, Some("Client"), None),
+            (Version::Semantic(6, 1, 7601, 0), Some(0x30), Some("Client"), None),
+            (Version::Semantic(10, 0, 19044, 4170), Some(0xBF), Some("Client"), Some("Windows 10 IoT Enterprise LTSC")),
+            (Version::Semantic(10, 0, 17763, 107), Some(0x7B), Some("IoTUAP"), Some("Windows 10 IoT Core")),
+            (Version::Semantic(10, 0, 19045, 0), Some(0x77), Some("Client"), Some("Windows 10 Team")),
+            (Version::Semantic(10, 0, 20348, 1), Some(0x88), Some("Client"), Some("Windows Holographic for Business")),
+        ];
+
+        for (version, sku, installation_type, expected) in data {
+            assert_eq!(expected.map(String::from), neutral_name(&version, sku, installation_type));
+        }
+    }
+
+    #[test]
+    fn device_variants() {
+        let data = [
+            (Some(0xBC), Some("IoTEnterprise"), Some(("IoT", "GetProductInfo"))),
+            (Some(0x7B), Some("IoTUAP"), Some(("IoT", "GetProductInfo"))),
+            (None, Some("IoTEnterpriseS"), Some(("IoT", "EditionID"))),
+            // A SKU without a device variant, e.g. a newer IoT one, falls back to the edition ID.
+            (Some(0x30), Some("IoTEnterpriseK"), Some(("IoT", "EditionID"))),
+            (Some(0x41), Some("Embedded"), Some(("Embedded", "GetProductInfo"))),
+            (Some(0x77), Some("PPIPro"), Some(("Team", "GetProductInfo"))),
+            (None, Some("PPIPro"), Some(("Team", "EditionID"))),
+            (Some(0x87), Some("Holographic"), Some(("Holographic", "GetProductInfo"))),
+            (Some(0x30), Some("Professional"), None),
+            (Some(0x08), Some("ServerDatacenter"), None),
+            (None, None, None),
+        ];
+
+        for (sku, edition_id, expected) in data {
+            assert_eq!(expected, device_variant(sku, edition_id));
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::logging::trace;
+
+use super::registry::{LiveRegistry, RegistryProvider, RegistryValue};
+
+/// Keys Windows Update and Component Based Servicing create while a reboot is pending.
+const REBOOT_KEYS: &[&str] = &[
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\WindowsUpdate\\Auto Update\\RebootRequired",
+    "SOFTWARE\\Microsoft\\Windows\\CurrentVersion\\Component Based Servicing\\RebootPending",
+];
+
+const SESSION_MANAGER_KEY: &str = "SYSTEM\\CurrentControlSet\\Control\\Session Manager";
+
+pub fn pending_reboot() -> Option<bool> {
+    if let Some(key) = REBOOT_KEYS.iter().find(|key| LiveRegistry.read_key(key).is_ok()) {
+        trace!("Reboot pending: {} exists", key);
+        return Some(true);
+    }
+
+    // Files in use by installers are replaced on the next boot.
+    match LiveRegistry.read_key(SESSION_MANAGER_KEY) {
+        Ok(session_manager) => match session_manager.value("PendingFileRenameOperations") {
+            Some(RegistryValue::MultiString(renames)) => Some(renames.iter().any(|rename| !rename.is_empty())),
+            _ => Some(false),
+        },
+        Err(e) => {
+            trace!("Unable to open {}: {:?}", SESSION_MANAGER_KEY, e);
+            None
+        }
+    }
+}
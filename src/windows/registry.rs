@@ -0,0 +1,224 @@
+//! Read access to the Windows registry behind the `RegistryProvider` trait, implemented for the
+//! live registry, offline hive files (see `HiveRegistry`) and in memory (see `MemoryRegistry`).
+
+use std::io;
+
+const REG_SZ: u32 = 1;
+const REG_EXPAND_SZ: u32 = 2;
+const REG_DWORD: u32 = 4;
+const REG_DWORD_BIG_ENDIAN: u32 = 5;
+const REG_MULTI_SZ: u32 = 7;
+const REG_QWORD: u32 = 11;
+
+/// Source of registry keys under `HKEY_LOCAL_MACHINE`.
+///
+/// # Examples
+///
+/// ```
+/// use osinfo::windows::{MemoryRegistry, RegistryKey, RegistryProvider, RegistryValue};
+///
+/// let registry = MemoryRegistry::new().with_key(
+///     "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion",
+///     RegistryKey::new([("CurrentBuildNumber", RegistryValue::String("22631".to_owned()))]),
+/// );
+/// let key = registry.read_key("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion").unwrap();
+/// assert_eq!(Some("22631"), key.string("CurrentBuildNumber"));
+/// ```
+pub trait RegistryProvider {
+    /// Reads the values of the key at `path`, relative to `HKEY_LOCAL_MACHINE`, e.g.
+    /// "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion". Fails with `io::ErrorKind::NotFound`
+    /// if the key doesn't exist.
+    fn read_key(&self, path: &str) -> io::Result<RegistryKey>;
+
+    /// Describes the key at `path` in diagnostics and provenance, e.g. "HKLM\\SOFTWARE\\...".
+    fn source(&self, path: &str) -> String {
+        format!("HKLM\\{path}")
+    }
+}
+
+/// Values of a registry key. Value names are case-insensitive.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RegistryKey {
+    values: Vec<(String, RegistryValue)>,
+}
+
+impl RegistryKey {
+    /// Creates a key with the given values.
+    pub fn new<S: Into<String>>(values: impl IntoIterator<Item = (S, RegistryValue)>) -> Self {
+        Self {
+            values: values.into_iter().map(|(name, value)| (name.into(), value)).collect(),
+        }
+    }
+
+    /// Returns the value with the given name, if any.
+    pub fn value(&self, name: &str) -> Option<&RegistryValue> {
+        self.values
+            .iter()
+            .find(|(value_name, _)| value_name.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the names and values of the key.
+    pub fn values(&self) -> impl Iterator<Item = (&str, &RegistryValue)> {
+        self.values.iter().map(|(name, value)| (name.as_str(), value))
+    }
+
+    /// Returns the string value (`REG_SZ` or `REG_EXPAND_SZ`) with the given name, if any.
+    pub fn string(&self, name: &str) -> Option<&str> {
+        match self.value(name)? {
+            RegistryValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the `REG_DWORD` value with the given name, if any.
+    pub fn dword(&self, name: &str) -> Option<u32> {
+        match *self.value(name)? {
+            RegistryValue::Dword(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Value of a registry key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RegistryValue {
+    /// `REG_SZ` or `REG_EXPAND_SZ` string, whose environment variables aren't expanded.
+    String(String),
+    /// `REG_MULTI_SZ` list of strings.
+    MultiString(Vec<String>),
+    /// `REG_DWORD` (or `REG_DWORD_BIG_ENDIAN`) number.
+    Dword(u32),
+    /// `REG_QWORD` number.
+    Qword(u64),
+    /// Value of another type, e.g. `REG_BINARY`, with its type number.
+    Binary(u32, Vec<u8>),
+}
+
+impl RegistryValue {
+    /// Decodes a value from its type number and its data as stored in the registry. Malformed
+    /// data is kept as binary.
+    pub(crate) fn from_raw(vtype: u32, data: &[u8]) -> Self {
+        match vtype {
+            REG_SZ | REG_EXPAND_SZ => Self::String(utf16_strings(data).swap_remove(0)),
+            REG_MULTI_SZ => Self::MultiString(utf16_strings(data).into_iter().filter(|s| !s.is_empty()).collect()),
+            REG_DWORD if data.len() >= 4 => Self::Dword(u32::from_le_bytes([data[0], data[1], data[2], data[3]])),
+            REG_DWORD_BIG_ENDIAN if data.len() >= 4 => {
+                Self::Dword(u32::from_be_bytes([data[0], data[1], data[2], data[3]]))
+            }
+            REG_QWORD if data.len() >= 8 => {
+                let mut bytes = [0; 8];
+                bytes.copy_from_slice(&data[..8]);
+                Self::Qword(u64::from_le_bytes(bytes))
+            }
+            _ => Self::Binary(vtype, data.to_vec()),
+        }
+    }
+}
+
+/// Splits UTF-16LE data at its NUL characters.
+fn utf16_strings(data: &[u8]) -> Vec<String> {
+    let units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+    units.split(|&unit| unit == 0).map(String::from_utf16_lossy).collect()
+}
+
+/// Registry keys held in memory, e.g. to test code reading the registry on any platform. Key
+/// paths are case-insensitive.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryRegistry {
+    keys: Vec<(String, RegistryKey)>,
+}
+
+impl MemoryRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the key at `path`, relative to `HKEY_LOCAL_MACHINE`.
+    pub fn with_key(mut self, path: impl Into<String>, key: RegistryKey) -> Self {
+        self.keys.push((path.into(), key));
+        self
+    }
+}
+
+impl RegistryProvider for MemoryRegistry {
+    fn read_key(&self, path: &str) -> io::Result<RegistryKey> {
+        self.keys
+            .iter()
+            .find(|(key_path, _)| key_path.eq_ignore_ascii_case(path))
+            .map(|(_, key)| key.clone())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("no key {path}")))
+    }
+}
+
+/// The registry of the running system.
+#[cfg(windows)]
+pub(crate) struct LiveRegistry;
+
+#[cfg(windows)]
+impl RegistryProvider for LiveRegistry {
+    fn read_key(&self, path: &str) -> io::Result<RegistryKey> {
+        super::api::read_registry_key(super::api::HKEY_LOCAL_MACHINE, path)
+    }
+}
+
+/// Reads the key at `path` of the live registry, relative to `HKEY_CURRENT_USER`.
+#[cfg(windows)]
+pub(crate) fn read_current_user_key(path: &str) -> io::Result<RegistryKey> {
+    super::api::read_registry_key(super::api::HKEY_CURRENT_USER, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn utf16(s: &str) -> Vec<u8> {
+        s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn raw_values() {
+        let data = [
+            (REG_SZ, utf16("Professional\0"), RegistryValue::String(String::from("Professional"))),
+            (REG_EXPAND_SZ, utf16("%SystemRoot%"), RegistryValue::String(String::from("%SystemRoot%"))),
+            (
+                REG_MULTI_SZ,
+                utf16("a\0b\0\0"),
+                RegistryValue::MultiString(vec![String::from("a"), String::from("b")]),
+            ),
+            (REG_DWORD, vec![0x20, 0x0D, 0, 0], RegistryValue::Dword(3360)),
+            (REG_DWORD_BIG_ENDIAN, vec![0, 0, 0x0D, 0x20], RegistryValue::Dword(3360)),
+            (REG_QWORD, vec![1, 0, 0, 0, 0, 0, 0, 0], RegistryValue::Qword(1)),
+            (REG_DWORD, vec![1], RegistryValue::Binary(REG_DWORD, vec![1])),
+            (3, vec![1, 2, 3], RegistryValue::Binary(3, vec![1, 2, 3])),
+        ];
+
+        for (vtype, bytes, expected) in data {
+            assert_eq!(expected, RegistryValue::from_raw(vtype, &bytes));
+        }
+    }
+
+    #[test]
+    fn memory() {
+        let registry = MemoryRegistry::new().with_key(
+            "SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion",
+            RegistryKey::new([
+                ("EditionID", RegistryValue::String(String::from("Professional"))),
+                ("UBR", RegistryValue::Dword(3296)),
+            ]),
+        );
+
+        let key = registry.read_key("software\\microsoft\\windows nt\\currentversion").unwrap();
+        assert_eq!(Some("Professional"), key.string("editionid"));
+        assert_eq!(Some(3296), key.dword("UBR"));
+        assert_eq!(None, key.string("UBR"));
+        assert_eq!(None, key.value("ProductName"));
+
+        let error = registry.read_key("SYSTEM\\Setup").unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+        assert_eq!("HKLM\\SYSTEM\\Setup", registry.source("SYSTEM\\Setup"));
+    }
+}
@@ -0,0 +1,125 @@
+#![allow(unsafe_code)]
+use std::{env, ffi::c_void, mem};
+
+use crate::logging::trace;
+
+use crate::{DiskEncryption, SecurityProcessor, Version, exec};
+
+const TBS_SUCCESS: u32 = 0;
+const TPM_VERSION_12: u32 = 1;
+const TPM_VERSION_20: u32 = 2;
+
+/// `TPM_DEVICE_INFO` from `tbs.h`.
+#[repr(C)]
+#[derive(Default)]
+struct TpmDeviceInfo {
+    struct_version: u32,
+    tpm_version: u32,
+    tpm_interface_type: u32,
+    tpm_imp_revision: u32,
+}
+
+#[link(name = "tbs")]
+unsafe extern "system" {
+    fn Tbsi_GetDeviceInfo(size: u32, info: *mut c_void) -> u32;
+}
+
+pub fn get_security_processor() -> Option<SecurityProcessor> {
+    let mut info = TpmDeviceInfo::default();
+    // SAFETY: `info` is a valid, writable `TPM_DEVICE_INFO` of the given size.
+    let result = unsafe {
+        Tbsi_GetDeviceInfo(
+            mem::size_of::<TpmDeviceInfo>() as u32,
+            &mut info as *mut TpmDeviceInfo as *mut c_void,
+        )
+    };
+
+    if result != TBS_SUCCESS {
+        trace!("Tbsi_GetDeviceInfo failed: {:#x}", result);
+        return None;
+    }
+
+    let version = match info.tpm_version {
+        TPM_VERSION_12 => Version::Semantic(1, 2, 0, 0),
+        TPM_VERSION_20 => Version::Semantic(2, 0, 0, 0),
+        _ => Version::Unknown,
+    };
+    Some(SecurityProcessor::Tpm(version))
+}
+
+pub fn get_disk_encryption() -> Option<DiskEncryption> {
+    // The shell property is readable without elevation, unlike `manage-bde` and the WMI provider.
+    let drive = system_drive(env::var("SystemDrive").ok());
+    let script = format!(
+        "(New-Object -ComObject Shell.Application).NameSpace('{drive}\\').Self.ExtendedProperty('System.Volume.BitLockerProtection')"
+    );
+    let output = match exec::run("powershell", &["-NoProfile", "-NonInteractive", "-Command", &script]) {
+        Ok(output) => output,
+        Err(e) => {
+            trace!("Unable to run powershell: {:?}", e);
+            return None;
+        }
+    };
+
+    parse_bitlocker_protection(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Returns the `%SystemDrive%` value if it is a drive letter, or `C:`. The value ends up in a
+/// PowerShell command, so anything else is rejected rather than quoted.
+fn system_drive(value: Option<String>) -> String {
+    match value {
+        Some(drive) if matches!(drive.as_bytes(), [letter, b':'] if letter.is_ascii_alphabetic()) => drive,
+        Some(drive) => {
+            trace!("Ignoring invalid SystemDrive {:?}", drive);
+            String::from("C:")
+        }
+        None => String::from("C:"),
+    }
+}
+
+/// Parses the `System.Volume.BitLockerProtection` shell property: 0 means the volume can't be
+/// encrypted, 2 means BitLocker is off and all other values are (partially) encrypted states.
+fn parse_bitlocker_protection(value: &str) -> Option<DiskEncryption> {
+    match value.trim().parse::<u32>().ok()? {
+        0 | 2 => Some(DiskEncryption::Unencrypted),
+        _ => Some(DiskEncryption::BitLocker),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn bitlocker_protection() {
+        let data = [
+            ("1\r\n", Some(DiskEncryption::BitLocker)),
+            ("2\r\n", Some(DiskEncryption::Unencrypted)),
+            ("5", Some(DiskEncryption::BitLocker)),
+            ("", None),
+        ];
+
+        for (value, expected) in &data {
+            assert_eq!(*expected, parse_bitlocker_protection(value));
+        }
+    }
+
+    #[test]
+    fn system_drives() {
+        let data = [
+            (Some("D:"), "D:"),
+            (Some("c:"), "c:"),
+            (None, "C:"),
+            (Some(""), "C:"),
+            (Some("C:\\"), "C:"),
+            (Some("C:') ; Remove-Item -Recurse ~ ; ('"), "C:"),
+            (Some("1:"), "C:"),
+            (Some("é:"), "C:"),
+        ];
+
+        for (value, expected) in data {
+            assert_eq!(expected, system_drive(value.map(String::from)));
+        }
+    }
+}
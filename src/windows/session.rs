@@ -0,0 +1,49 @@
+#![allow(unsafe_code)]
+use std::{env, process};
+
+use crate::logging::trace;
+
+use crate::SessionKind;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn ProcessIdToSessionId(process_id: u32, session_id: *mut u32) -> i32;
+}
+
+pub fn get_session_kind() -> SessionKind {
+    let mut session_id = 0;
+    // SAFETY: `session_id` is a valid, writable `DWORD`.
+    if unsafe { ProcessIdToSessionId(process::id(), &mut session_id) } == 0 {
+        trace!("ProcessIdToSessionId failed: {:?}", std::io::Error::last_os_error());
+        return SessionKind::Unknown;
+    }
+
+    session_kind(session_id, env::var("SESSIONNAME").ok().as_deref())
+}
+
+/// Classifies the session from its ID and name, e.g. "Console" or "RDP-Tcp#3". Services run
+/// in session 0, which has no desktop since Windows Vista.
+fn session_kind(session_id: u32, session_name: Option<&str>) -> SessionKind {
+    if session_id == 0 {
+        return SessionKind::Service;
+    }
+
+    match session_name {
+        Some(name) if name.starts_with("RDP-") => SessionKind::RemoteDesktop,
+        _ => SessionKind::Desktop,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn kinds() {
+        assert_eq!(SessionKind::Service, session_kind(0, None));
+        assert_eq!(SessionKind::Desktop, session_kind(1, Some("Console")));
+        assert_eq!(SessionKind::RemoteDesktop, session_kind(2, Some("RDP-Tcp#3")));
+        assert_eq!(SessionKind::Desktop, session_kind(1, None));
+    }
+}
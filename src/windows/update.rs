@@ -0,0 +1,208 @@
+use std::cmp::Ordering;
+
+use crate::logging::trace;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{
+    current_version::{CURRENT_VERSION_KEY, get_build},
+    registry::RegistryProvider,
+};
+
+/// Release date of the most recent cumulative update in the table of this crate, e.g. to warn
+/// that `UpdateLevel::status` can't flag machines missing updates released since.
+pub const UPDATE_TABLE_DATE: &str = "2025-06-10";
+
+/// Latest cumulative update known to this crate for each serviced build: the build number, its
+/// feature release, the update build revision (UBR), the knowledge base article and the release
+/// date. Builds out of servicing keep their final update.
+static LATEST_UPDATES: [(u32, &str, u32, &str, &str); 9] = [
+    (14393, "1607", 8148, "KB5061010", "2025-06-10"),
+    (17763, "1809", 7434, "KB5060531", "2025-06-10"),
+    (19044, "21H2", 5965, "KB5060533", "2025-06-10"),
+    (19045, "22H2", 5965, "KB5060533", "2025-06-10"),
+    (20348, "21H2", 3807, "KB5060526", "2025-06-10"),
+    (22000, "21H2", 2538, "KB5031358", "2023-10-10"),
+    (22621, "22H2", 5472, "KB5060999", "2025-06-10"),
+    (22631, "23H2", 5472, "KB5060999", "2025-06-10"),
+    (26100, "24H2", 4349, "KB5060842", "2025-06-10"),
+];
+
+/// Patch level of the installed Windows build.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct UpdateLevel {
+    /// Build number, e.g. 22631 for Windows 11 23H2.
+    pub build: u32,
+    /// Update build revision (UBR), e.g. 3296, which each cumulative update increments.
+    pub ubr: u32,
+    /// Version of the last installed cumulative update (the `LCUVer` registry value), e.g.
+    /// "10.0.22621.3296", if Windows recorded it.
+    pub last_cumulative_update: Option<String>,
+    /// Feature release the build belongs to, e.g. "23H2", if the build is known.
+    pub release: Option<String>,
+    /// Latest cumulative update known to this crate for the build, if the build is known. See
+    /// `UPDATE_TABLE_DATE` for how recent the table is.
+    pub latest: Option<CumulativeUpdate>,
+}
+
+/// Patch level of a build compared with the latest cumulative update known to this crate, see
+/// `UpdateLevel::status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum UpdateStatus {
+    /// The latest known cumulative update is installed. Updates released after
+    /// `UPDATE_TABLE_DATE` may still be missing.
+    Current,
+    /// The latest known cumulative update is missing.
+    Outdated,
+    /// A cumulative update newer than the table of this crate is installed.
+    NewerThanTable,
+}
+
+/// A cumulative update of a Windows build.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CumulativeUpdate {
+    /// Update build revision (UBR) the update installs, e.g. 3296.
+    pub ubr: u32,
+    /// Knowledge base article of the update, e.g. "KB5035853".
+    pub kb: String,
+    /// Release date of the update, e.g. "2024-03-12".
+    pub released: String,
+}
+
+impl UpdateLevel {
+    /// Returns the update level of the given build and UBR, compared with the bundled table of
+    /// cumulative updates.
+    pub fn of(build: u32, ubr: u32) -> Self {
+        let known = LATEST_UPDATES.iter().find(|&&(known, ..)| known == build);
+        Self {
+            build,
+            ubr,
+            last_cumulative_update: None,
+            release: known.map(|&(_, release, ..)| String::from(release)),
+            latest: known.map(|&(_, _, ubr, kb, released)| CumulativeUpdate {
+                ubr,
+                kb: String::from(kb),
+                released: String::from(released),
+            }),
+        }
+    }
+
+    /// Compares the UBR with the latest cumulative update known to this crate, or returns `None`
+    /// if the build isn't known.
+    ///
+    /// The table only goes up to `UPDATE_TABLE_DATE`: a machine missing the updates released
+    /// since is still `Current`, so scanners should update the crate to catch recent patch
+    /// levels.
+    ///
+    /// # Example
+    /// ```
+    /// use osinfo::windows::{UpdateLevel, UpdateStatus};
+    ///
+    /// assert_eq!(Some(UpdateStatus::Outdated), UpdateLevel::of(22631, 3296).status());
+    /// assert_eq!(Some(UpdateStatus::NewerThanTable), UpdateLevel::of(22631, 9999).status());
+    /// assert_eq!(None, UpdateLevel::of(10240, 1).status());
+    /// ```
+    pub fn status(&self) -> Option<UpdateStatus> {
+        let latest = self.latest.as_ref()?;
+        Some(match self.ubr.cmp(&latest.ubr) {
+            Ordering::Less => UpdateStatus::Outdated,
+            Ordering::Equal => UpdateStatus::Current,
+            Ordering::Greater => UpdateStatus::NewerThanTable,
+        })
+    }
+
+    /// Returns whether the latest cumulative update known to this crate is installed, or `None`
+    /// if the build isn't known or has an update newer than the table, see `status`.
+    pub fn is_current(&self) -> Option<bool> {
+        match self.status()? {
+            UpdateStatus::Current => Some(true),
+            UpdateStatus::Outdated => Some(false),
+            UpdateStatus::NewerThanTable => None,
+        }
+    }
+}
+
+/// Returns the patch level of the installed Windows build, e.g. for vulnerability scanners
+/// flagging outdated machines offline.
+///
+/// # Examples
+///
+/// ```
+/// let level = osinfo::windows::update_level();
+/// if level.status() == Some(osinfo::windows::UpdateStatus::Outdated) {
+///     println!("Build {}.{} is missing cumulative updates", level.build, level.ubr);
+/// }
+/// ```
+#[cfg(windows)]
+pub fn update_level() -> UpdateLevel {
+    update_level_from(&super::registry::LiveRegistry)
+}
+
+/// Returns the patch level of the Windows build described by `registry`, e.g. the `SOFTWARE`
+/// hive of an offline image (see `HiveRegistry`).
+pub fn update_level_from(registry: &dyn RegistryProvider) -> UpdateLevel {
+    let current_version = match registry.read_key(CURRENT_VERSION_KEY) {
+        Ok(current_version) => current_version,
+        Err(e) => {
+            trace!("Unable to open {}: {:?}", CURRENT_VERSION_KEY, e);
+            return UpdateLevel::default();
+        }
+    };
+
+    let (build, ubr) = get_build(&current_version);
+    UpdateLevel {
+        last_cumulative_update: current_version.string("LCUVer").map(String::from),
+        ..UpdateLevel::of(build, ubr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::windows::registry::{MemoryRegistry, RegistryKey, RegistryValue};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn levels() {
+        let level = UpdateLevel::of(26100, 4349);
+        assert_eq!(Some(String::from("24H2")), level.release);
+        assert_eq!(Some(UpdateStatus::Current), level.status());
+        assert_eq!(Some(true), level.is_current());
+
+        assert_eq!(Some(false), UpdateLevel::of(19045, 3803).is_current());
+        // The machine is more recent than the table, which can't tell whether it is current.
+        let newer = UpdateLevel::of(17763, 9999);
+        assert_eq!(Some(UpdateStatus::NewerThanTable), newer.status());
+        assert_eq!(None, newer.is_current());
+        assert_eq!(None, UpdateLevel::of(10240, 1).status());
+    }
+
+    #[test]
+    fn registry() {
+        let registry = MemoryRegistry::new().with_key(
+            CURRENT_VERSION_KEY,
+            RegistryKey::new([
+                ("CurrentBuildNumber", RegistryValue::String(String::from("22631"))),
+                ("UBR", RegistryValue::Dword(3155)),
+                ("LCUVer", RegistryValue::String(String::from("10.0.22621.3155"))),
+            ]),
+        );
+        let level = update_level_from(&registry);
+        assert_eq!((22631, 3155), (level.build, level.ubr));
+        assert_eq!(Some("10.0.22621.3155"), level.last_cumulative_update.as_deref());
+        assert_eq!(Some(false), level.is_current());
+
+        assert_eq!(UpdateLevel::default(), update_level_from(&MemoryRegistry::new()));
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn installed() {
+        let level = update_level();
+        assert!(level.build > 0);
+    }
+}
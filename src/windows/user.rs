@@ -0,0 +1,86 @@
+#![allow(unsafe_code)]
+use std::{ffi::c_void, io, mem, ptr};
+
+use crate::logging::trace;
+
+const TOKEN_QUERY: u32 = 0x0008;
+/// `TokenElevation` of the `TOKEN_INFORMATION_CLASS` enumeration.
+const TOKEN_ELEVATION: u32 = 20;
+/// `TokenIsAppContainer` of the `TOKEN_INFORMATION_CLASS` enumeration.
+const TOKEN_IS_APP_CONTAINER: u32 = 29;
+/// Maximum length of a user name (`UNLEN`) plus the terminating NUL.
+const USER_NAME_CAPACITY: u32 = 257;
+
+#[link(name = "kernel32")]
+unsafe extern "system" {
+    fn GetCurrentProcess() -> *mut c_void;
+    fn CloseHandle(handle: *mut c_void) -> i32;
+}
+
+#[link(name = "advapi32")]
+unsafe extern "system" {
+    fn OpenProcessToken(process: *mut c_void, desired_access: u32, token: *mut *mut c_void) -> i32;
+    fn GetTokenInformation(token: *mut c_void, class: u32, information: *mut c_void, length: u32, return_length: *mut u32) -> i32;
+    fn GetUserNameW(buffer: *mut u16, size: *mut u32) -> i32;
+}
+
+/// Returns whether the process token is elevated, i.e. the process runs as administrator with
+/// User Account Control.
+pub fn is_elevated() -> bool {
+    token_flag(TOKEN_ELEVATION)
+}
+
+/// Returns whether the process runs in an AppContainer.
+pub fn is_app_container() -> bool {
+    token_flag(TOKEN_IS_APP_CONTAINER)
+}
+
+/// Reads a token information class consisting of a single `DWORD` flag of the process token.
+fn token_flag(class: u32) -> bool {
+    let mut token = ptr::null_mut();
+    // SAFETY: the pseudo handle of the current process is always valid and `token` is writable.
+    if unsafe { OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) } == 0 {
+        trace!("OpenProcessToken failed: {:?}", io::Error::last_os_error());
+        return false;
+    }
+
+    let mut flag = 0u32;
+    let mut length = 0;
+    // SAFETY: `token` was opened with TOKEN_QUERY and `flag` is a writable `DWORD`, the size of
+    // the information of `class`.
+    let result = unsafe {
+        GetTokenInformation(token, class, (&mut flag as *mut u32).cast(), mem::size_of::<u32>() as u32, &mut length)
+    };
+    if result == 0 {
+        trace!("GetTokenInformation failed: {:?}", io::Error::last_os_error());
+    }
+    // SAFETY: `token` is an open handle owned by this function.
+    unsafe { CloseHandle(token) };
+
+    result != 0 && flag != 0
+}
+
+/// Returns the name of the user the process runs as.
+pub fn current_user() -> Option<String> {
+    let mut buffer = [0u16; USER_NAME_CAPACITY as usize];
+    let mut size = USER_NAME_CAPACITY;
+    // SAFETY: `buffer` has room for `size` UTF-16 code units.
+    if unsafe { GetUserNameW(buffer.as_mut_ptr(), &mut size) } == 0 {
+        trace!("GetUserNameW failed: {:?}", io::Error::last_os_error());
+        return None;
+    }
+
+    // `size` includes the terminating NUL.
+    let length = (size as usize).saturating_sub(1);
+    Some(String::from_utf16_lossy(&buffer[..length]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn user() {
+        assert!(current_user().is_some_and(|name| !name.is_empty()));
+    }
+}
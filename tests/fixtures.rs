@@ -0,0 +1,54 @@
+//! Runs the Linux detection against the release files of many distributions.
+//!
+//! Every directory in `tests/fixtures` is a root file system containing the release files of one
+//! distribution, and its `expected.txt` holds the expected detection result. Run the tests with
+//! `UPDATE_SNAPSHOTS=1` to regenerate the snapshots after an intended change, then review them.
+
+use std::{env, fs, path::Path};
+
+use osinfo::OSInfo;
+use pretty_assertions::assert_eq;
+
+const SNAPSHOT: &str = "expected.txt";
+
+fn snapshot(info: &OSInfo) -> String {
+    format!(
+        "id: {}\nname: {}\nversion: {:?}\nvariant: {}\nedition: {}\ncodename: {}\nlts: {}\n",
+        info.id(),
+        info.name(),
+        info.version(),
+        info.variant().unwrap_or_default(),
+        info.edition().unwrap_or_default(),
+        info.codename().unwrap_or_default(),
+        info.is_lts(),
+    )
+}
+
+#[test]
+fn fixtures() {
+    let update = env::var_os("UPDATE_SNAPSHOTS").is_some();
+    let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut roots: Vec<_> = fs::read_dir(&fixtures)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect();
+    roots.sort();
+    assert!(roots.len() >= 30, "only {} fixtures found", roots.len());
+
+    for root in roots {
+        let name = root.file_name().unwrap().to_string_lossy().into_owned();
+        let info = osinfo::get_from_root(&root).unwrap_or_else(|| panic!("{name}: not detected"));
+        let actual = snapshot(&info);
+
+        let path = root.join(SNAPSHOT);
+        if update {
+            fs::write(&path, &actual).unwrap();
+            continue;
+        }
+
+        let expected = fs::read_to_string(&path).unwrap_or_else(|_| panic!("{name}: missing {SNAPSHOT}"));
+        assert_eq!(expected, actual, "{name}");
+    }
+}